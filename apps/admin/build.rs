@@ -1,4 +1,4 @@
-use std::{env, fs};
+use std::{env, fs, process::Command};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 拷贝 `apps/config.yaml` 文件到当前目录
@@ -7,5 +7,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let target_file = current_dir.join("config.yaml");
     fs::copy(config_file, target_file)?;
 
+    // 构建信息, 用于 `/version` 接口
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|v| v.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_HASH={git_hash}");
+    let build_time = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    println!("cargo:rustc-env=BUILD_TIME={build_time}");
+
     Ok(())
 }
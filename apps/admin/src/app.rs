@@ -0,0 +1,53 @@
+//! 应用依赖
+//!
+//! 服务及命令行共用的配置、数据库及依赖注入.
+use std::sync::Arc;
+
+use config::AppConfig;
+use database::Mdb;
+use inject::InjectProvider;
+
+/// 应用依赖
+pub struct App {
+    /// 配置
+    pub config: AppConfig,
+    /// 数据库
+    pub db_pool: Mdb,
+    /// 依赖注入
+    pub inject_provider: Arc<InjectProvider>,
+}
+
+impl App {
+    /// 加载配置文件并初始化依赖
+    pub async fn new(config_path: &str) -> anyhow::Result<Self> {
+        let config = AppConfig::new(config_path)?;
+        Self::from_config(config).await
+    }
+
+    /// 通过配置初始化依赖
+    pub async fn from_config(config: AppConfig) -> anyhow::Result<Self> {
+        // 初始化数据库
+        let main_db =
+            database::Pool::new(config.sqlite.dns(), config.sqlite.options.clone()).await?;
+        let db_pool = Mdb::new(Arc::new(main_db.clone()), Arc::new(main_db));
+
+        // 初始化邮件发送器
+        let mailer = mailer::build(&config.mailer)?;
+        // 初始化短信发送器
+        let sms = sms::build(&config.sms)?;
+
+        // Using an Arc to share the provider across multiple threads.
+        let inject_provider = Arc::new(InjectProvider::new(db_pool.clone(), mailer, sms));
+
+        Ok(App {
+            config,
+            db_pool,
+            inject_provider,
+        })
+    }
+
+    /// 关闭数据库
+    pub async fn close(&self) {
+        self.db_pool.close().await;
+    }
+}
@@ -0,0 +1,66 @@
+//! 配置管理命令
+use std::net::{IpAddr, SocketAddr};
+
+use anyhow::Context;
+use clap::Subcommand;
+use colored::Colorize;
+
+use axum_jwt::AuthWhiteList;
+use config::AppConfig;
+
+/// 配置管理命令
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// 检查配置文件
+    Check,
+}
+
+impl ConfigCommand {
+    /// 执行命令
+    pub fn run(self, config_path: &str) -> anyhow::Result<()> {
+        match self {
+            ConfigCommand::Check => {
+                let config = AppConfig::new(config_path)
+                    .with_context(|| format!("解析配置文件失败, path: {config_path}"))?;
+                Self::check(&config)?;
+                println!("{} {}", "配置检查通过:".green(), config_path);
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查配置项
+    fn check(config: &AppConfig) -> anyhow::Result<()> {
+        config
+            .server
+            .base
+            .address
+            .parse::<IpAddr>()
+            .context("服务监听地址无效")?;
+        let metrics = &config.server.metrics;
+        if metrics.enable && !metrics.address.is_empty() {
+            metrics
+                .address
+                .parse::<SocketAddr>()
+                .context("指标监听地址无效")?;
+        }
+        AuthWhiteList::new(&config.auth.white_list).context("鉴权白名单无效")?;
+        mailer::build(&config.mailer).context("邮件配置无效")?;
+        sms::build(&config.sms).context("短信配置无效")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut config = AppConfig::default();
+        assert!(ConfigCommand::check(&config).is_ok());
+
+        config.server.base.address = "localhost:8000".to_string();
+        assert!(ConfigCommand::check(&config).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! 数据库备份与恢复命令
+//!
+//! 仅支持 Sqlite 数据库. 恢复前须停止服务, 原数据库文件重命名为 `<文件名>.<时间>.bak` 保留.
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use chrono::Local;
+use clap::Subcommand;
+use colored::Colorize;
+use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+use config::AppConfig;
+
+use crate::app::App;
+
+/// Sqlite 数据库文件头
+const SQLITE_HEADER: &[u8] = b"SQLite format 3\0";
+
+/// 数据库备份与恢复命令
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// 备份数据库
+    Backup {
+        /// 备份文件路径
+        output: PathBuf,
+    },
+    /// 从备份文件恢复数据库
+    Restore {
+        /// 备份文件路径
+        input: PathBuf,
+    },
+}
+
+impl DbCommand {
+    /// 执行命令
+    pub async fn run(self, config_path: &str) -> anyhow::Result<()> {
+        match self {
+            DbCommand::Backup { output } => Self::backup(config_path, &output).await,
+            DbCommand::Restore { input } => {
+                let config = AppConfig::new(config_path)?;
+                let db_file = Self::db_file(&config)?;
+                let bak = Self::restore(&db_file, &input)?;
+                println!("原数据库已保留: {}", bak.display());
+                println!(
+                    "{} {} -> {}",
+                    "已恢复数据库:".green(),
+                    input.display(),
+                    db_file.display()
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// 备份数据库
+    async fn backup(config_path: &str, output: &Path) -> anyhow::Result<()> {
+        if output.exists() {
+            bail!("备份文件已存在: {}", output.display());
+        }
+        let app = App::new(config_path).await?;
+        Self::db_file(&app.config)?;
+
+        // VACUUM INTO 生成一致的数据库快照, 无需停止服务
+        let result = app
+            .db_pool
+            .main_db
+            .db()
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "VACUUM INTO ?",
+                [output.to_string_lossy().to_string().into()],
+            ))
+            .await;
+        app.close().await;
+        result.context("备份数据库失败")?;
+
+        println!("{} {}", "已备份数据库:".green(), output.display());
+        Ok(())
+    }
+
+    /// 恢复数据库, 返回原数据库文件的保留路径
+    fn restore(db_file: &Path, input: &Path) -> anyhow::Result<PathBuf> {
+        let mut header = [0u8; 16];
+        fs::File::open(input)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .with_context(|| format!("读取备份文件失败: {}", input.display()))?;
+        if header != SQLITE_HEADER {
+            bail!("备份文件不是有效的 Sqlite 数据库: {}", input.display());
+        }
+
+        // 保留原数据库文件及其日志文件
+        let suffix = format!("{}.bak", Local::now().format("%Y%m%d%H%M%S"));
+        let bak = Self::with_suffix(db_file, &suffix);
+        for file in [
+            db_file.to_path_buf(),
+            Self::with_suffix(db_file, "-wal"),
+            Self::with_suffix(db_file, "-shm"),
+        ] {
+            if file.exists() {
+                fs::rename(&file, Self::with_suffix(&file, &suffix))
+                    .with_context(|| format!("保留原数据库文件失败: {}", file.display()))?;
+            }
+        }
+
+        fs::copy(input, db_file)
+            .with_context(|| format!("写入数据库文件失败: {}", db_file.display()))?;
+        Ok(bak)
+    }
+
+    /// 数据库文件路径
+    fn db_file(config: &AppConfig) -> anyhow::Result<PathBuf> {
+        config
+            .sqlite
+            .sqlite_file()
+            .context("仅支持备份及恢复 Sqlite 数据库文件")
+    }
+
+    /// 在文件名后追加后缀
+    fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+        let mut name = path.as_os_str().to_os_string();
+        if !suffix.starts_with('-') {
+            name.push(".");
+        }
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        let db_file = dir.join("data.dat");
+        let input = dir.join("backup.dat");
+        fs::write(&db_file, b"old").expect("write db");
+        fs::write(dir.join("data.dat-wal"), b"wal").expect("write wal");
+
+        // 非 Sqlite 文件
+        fs::write(&input, b"not a database file").expect("write input");
+        assert!(DbCommand::restore(&db_file, &input).is_err());
+        assert_eq!(fs::read(&db_file).expect("read db"), b"old");
+
+        let content = [SQLITE_HEADER, b"data"].concat();
+        fs::write(&input, &content).expect("write input");
+        let bak = DbCommand::restore(&db_file, &input).expect("restore");
+        assert_eq!(fs::read(&db_file).expect("read db"), content);
+        assert_eq!(fs::read(&bak).expect("read bak"), b"old");
+        assert!(!dir.join("data.dat-wal").exists());
+    }
+}
@@ -0,0 +1,116 @@
+//! 数据库迁移命令
+use std::io::{self, BufRead, Write};
+
+use clap::Subcommand;
+use colored::Colorize;
+use sea_orm_migration::MigratorTrait;
+
+use migration::Migrator;
+
+use crate::{app::App, initialize::Initializer};
+
+/// 数据库迁移命令
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// 执行待执行的迁移并写入预设数据
+    Up {
+        /// 执行的迁移数量, 为空时执行全部
+        #[arg(short, long)]
+        num: Option<u32>,
+    },
+    /// 回滚已执行的迁移, 回滚将删除相关数据表及数据
+    Down {
+        /// 回滚的迁移数量
+        #[arg(short, long, default_value_t = 1)]
+        num: u32,
+        /// 跳过确认
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// 查询已执行及待执行的迁移
+    Status,
+}
+
+impl MigrateCommand {
+    /// 执行命令
+    pub async fn run(self, app: &App) -> anyhow::Result<()> {
+        let db = app.db_pool.main_db.db();
+        match self {
+            MigrateCommand::Up { num } => {
+                let pending = Initializer::status(db).await?.pending;
+                Migrator::up(db, num).await?;
+                let applied = num.map_or(pending.len(), |v| pending.len().min(v as usize));
+                for name in &pending[..applied] {
+                    println!("{} {}", "applied".green(), name);
+                }
+                if num.is_none() {
+                    Initializer::seed(db, &app.config.initialize.credential_file).await?;
+                }
+                println!("已执行 {} 个迁移", applied);
+            }
+            MigrateCommand::Down { num, yes } => {
+                let applied = Initializer::status(db).await?.applied;
+                let names = applied.iter().rev().take(num as usize).collect::<Vec<_>>();
+                if names.is_empty() {
+                    println!("不存在已执行的迁移");
+                    return Ok(());
+                }
+                if !yes {
+                    for name in &names {
+                        println!("{} {}", "roll back".red(), name);
+                    }
+                    if !confirm(
+                        &mut io::stdin().lock(),
+                        "回滚将删除相关数据表及数据, 是否继续?",
+                    )? {
+                        println!("已取消");
+                        return Ok(());
+                    }
+                }
+                Migrator::down(db, Some(num)).await?;
+                for name in names {
+                    println!("{} {}", "rolled back".yellow(), name);
+                }
+            }
+            MigrateCommand::Status => {
+                let status = Initializer::status(db).await?;
+                for name in &status.applied {
+                    println!("{} {}", "applied".green(), name);
+                }
+                for name in &status.pending {
+                    println!("{} {}", "pending".yellow(), name);
+                }
+                println!(
+                    "已执行: {}, 待执行: {}",
+                    status.applied.len(),
+                    status.pending.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 读取确认输入, 仅 `y` 或 `yes` 视为确认
+fn confirm<R: BufRead>(reader: &mut R, message: &str) -> io::Result<bool> {
+    print!("{} [y/N] ", message.yellow());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    reader.read_line(&mut input)?;
+    Ok(matches!(input.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm() {
+        assert!(confirm(&mut "y\n".as_bytes(), "continue?").expect("confirm"));
+        assert!(confirm(&mut "YES\n".as_bytes(), "continue?").expect("confirm"));
+        assert!(!confirm(&mut "\n".as_bytes(), "continue?").expect("confirm"));
+        assert!(!confirm(&mut "no\n".as_bytes(), "continue?").expect("confirm"));
+        assert!(!confirm(&mut "".as_bytes(), "continue?").expect("confirm"));
+    }
+}
@@ -0,0 +1,152 @@
+//! 命令行
+//!
+//! 未指定子命令时启动服务.
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+
+use app_state::mobile::{AppDirector, AppState};
+
+use crate::{app::App, server::HttpServer};
+
+mod config;
+mod db;
+mod migrate;
+mod user;
+
+pub use config::ConfigCommand;
+pub use db::DbCommand;
+pub use migrate::MigrateCommand;
+pub use user::UserCommand;
+
+/// DeBox Pro Tools 管理后台
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// 配置文件路径
+    #[arg(short, long, global = true, default_value = "config.yaml")]
+    pub config: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// 子命令
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 启动服务
+    Serve,
+    /// 数据库迁移
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// 用户管理
+    #[command(subcommand)]
+    User(UserCommand),
+    /// 配置管理
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// 数据库备份与恢复
+    #[command(subcommand)]
+    Db(DbCommand),
+}
+
+impl Cli {
+    /// 执行命令
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self.command.unwrap_or(Command::Serve) {
+            Command::Serve => serve(&self.config).await,
+            Command::Migrate(cmd) => {
+                let app = App::new(&self.config).await?;
+                let result = cmd.run(&app).await;
+                app.close().await;
+                result
+            }
+            Command::User(cmd) => {
+                let app = App::new(&self.config).await?;
+                let result = cmd.run(&app).await;
+                app.close().await;
+                result
+            }
+            Command::Config(cmd) => cmd.run(&self.config),
+            Command::Db(cmd) => cmd.run(&self.config).await,
+        }
+    }
+}
+
+/// 启动服务
+async fn serve(config_path: &str) -> anyhow::Result<()> {
+    // 加载配置文件
+    let app_config = ::config::AppConfig::new(config_path)?;
+
+    // 初始化日志
+    let log_guards = logger::Logger::build(&app_config.logger).expect("初始化日志失败");
+
+    // 初始化依赖
+    let app = App::from_config(app_config).await?;
+
+    // 全局状态
+    let state = Arc::new(AppState {
+        counter: 0,
+        log_guards,
+        app_directory: AppDirector::default(),
+    });
+
+    // 阻塞运行服务
+    HttpServer::run(
+        app.config.clone(),
+        app.db_pool.clone(),
+        app.inject_provider.clone(),
+        state,
+    )
+    .await?;
+
+    // 关闭数据库
+    app.close().await;
+
+    println!("{}", "See you again~".yellow());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::try_parse_from(["admin"]).expect("parse");
+        assert_eq!(cli.config, "config.yaml");
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["admin", "serve", "--config", "prod.yaml"]).expect("parse");
+        assert_eq!(cli.config, "prod.yaml");
+        assert!(matches!(cli.command, Some(Command::Serve)));
+
+        let cli = Cli::try_parse_from(["admin", "migrate", "down", "-n", "2"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Migrate(MigrateCommand::Down {
+                num: 2,
+                yes: false
+            }))
+        ));
+
+        let cli = Cli::try_parse_from(["admin", "migrate", "down", "--yes"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Migrate(MigrateCommand::Down { num: 1, yes: true }))
+        ));
+
+        let cli =
+            Cli::try_parse_from(["admin", "user", "create", "alice", "--admin"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::User(UserCommand::Create { admin: true, .. }))
+        ));
+
+        assert!(Cli::try_parse_from(["admin", "db", "restore"]).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! 用户管理命令
+use clap::Subcommand;
+use colored::Colorize;
+use uuid::Uuid;
+
+use migration::seed::ADMIN_ROLE_NAME;
+use service_hub::user::{
+    RoleService, UserBaseService,
+    dto::user_base::{CreateUserBaseReq, ResetUserBasePasswordReq, UpdateUserBaseStatusReq},
+    enums::user_base::Gender,
+};
+
+use crate::app::App;
+
+/// 用户管理命令
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// 添加用户
+    Create {
+        /// 用户名
+        username: String,
+        /// 密码, 为空时随机生成
+        #[arg(short, long)]
+        password: Option<String>,
+        /// 角色名称, 可指定多个
+        #[arg(short, long)]
+        role: Vec<String>,
+        /// 授予管理员角色
+        #[arg(long)]
+        admin: bool,
+    },
+    /// 重置用户密码
+    ResetPassword {
+        /// 用户名
+        username: String,
+        /// 新密码, 为空时随机生成
+        #[arg(short, long)]
+        password: Option<String>,
+    },
+    /// 停用用户
+    Disable {
+        /// 用户名
+        username: String,
+    },
+}
+
+impl UserCommand {
+    /// 执行命令
+    pub async fn run(self, app: &App) -> anyhow::Result<()> {
+        let user_base_service: UserBaseService = app.inject_provider.provide();
+        match self {
+            UserCommand::Create {
+                username,
+                password,
+                mut role,
+                admin,
+            } => {
+                if admin && !role.iter().any(|v| v == ADMIN_ROLE_NAME) {
+                    role.push(ADMIN_ROLE_NAME.to_string());
+                }
+                let role_service: RoleService = app.inject_provider.provide();
+                let mut role_ids = Vec::with_capacity(role.len());
+                for name in role {
+                    role_ids.push(role_service.info_by_name(name).await?.id);
+                }
+
+                let (password, generated) = Self::password(password);
+                let user = user_base_service
+                    .create(CreateUserBaseReq {
+                        username,
+                        real_name: None,
+                        gender: Gender::Undisclosed,
+                        password: password.clone(),
+                        status: true,
+                        age: None,
+                        date_birth: None,
+                        avatar: None,
+                        intro: None,
+                        desc: None,
+                        address: None,
+                        preferences: None,
+                        department_id: None,
+                        position_id: None,
+                        rank_id: None,
+                        member_level_id: None,
+                        role_ids,
+                    })
+                    .await?;
+                println!("已添加用户, id: {}, username: {}", user.id, user.username);
+                Self::print_password(&password, generated);
+            }
+            UserCommand::ResetPassword { username, password } => {
+                let user = user_base_service.info_by_username(username).await?;
+                let (password, generated) = Self::password(password);
+                user_base_service
+                    .reset_password(ResetUserBasePasswordReq {
+                        id: user.id,
+                        password: password.clone(),
+                    })
+                    .await?;
+                println!("已重置密码, username: {}", user.username);
+                Self::print_password(&password, generated);
+            }
+            UserCommand::Disable { username } => {
+                let user = user_base_service.info_by_username(username).await?;
+                user_base_service
+                    .update_status(UpdateUserBaseStatusReq {
+                        id: user.id,
+                        status: false,
+                    })
+                    .await?;
+                println!("已停用用户, username: {}", user.username);
+            }
+        }
+        Ok(())
+    }
+
+    /// 未指定密码时随机生成, 返回密码及是否随机生成
+    fn password(password: Option<String>) -> (String, bool) {
+        match password {
+            Some(v) if !v.is_empty() => (v, false),
+            _ => (Uuid::new_v4().simple().to_string()[..16].to_string(), true),
+        }
+    }
+
+    /// 输出随机生成的密码
+    fn print_password(password: &str, generated: bool) {
+        if generated {
+            println!(
+                "{}",
+                format!("password: {password} (仅显示一次, 请登录后修改密码)").yellow()
+            );
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! 健康检查
+//!
+//! - `/health`: 存活检查, 服务可响应即正常
+//! - `/ready`: 就绪检查, 检查数据库、数据库日志写入队列及后台任务, 未就绪时返回 503
+//! - `/version`: 构建信息
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    http::StatusCode,
+    response::{IntoResponse, Response as HttpResponse},
+    routing::get,
+};
+use log::error;
+use sea_orm::{ConnectionTrait, DatabaseBackend};
+use sea_orm_migration::MigratorTrait;
+use serde::{Deserialize, Serialize};
+
+use axum_response::{Responder, Response};
+use database::{Mdb, PoolTrait};
+use err_code::Error;
+use inject::AInjectProvider;
+use logger::DbWriterMonitor;
+use migration::Migrator;
+use service_hub::log::TaskMonitor;
+
+/// 存活状态
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResp {
+    /// 服务状态
+    pub status: String,
+}
+
+/// 就绪检查项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadyCheck {
+    /// 检查项名称
+    pub name: String,
+    /// 是否正常
+    pub ok: bool,
+    /// 检查信息
+    pub msg: Option<String>,
+}
+
+/// 就绪状态
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadyResp {
+    /// 是否就绪
+    pub ready: bool,
+    /// 检查项列表
+    pub checks: Vec<ReadyCheck>,
+}
+
+/// 构建信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResp {
+    /// 程序名称
+    pub name: String,
+    /// 程序版本
+    pub version: String,
+    /// Git 提交
+    pub git_hash: String,
+    /// 构建时间
+    pub build_time: String,
+    /// 数据库类型
+    pub db_backend: String,
+    /// 已执行的迁移数量
+    pub migrations: usize,
+}
+
+/// 控制器
+pub struct HealthController;
+
+impl HealthController {
+    /// 存活检查
+    pub async fn health() -> Responder<HealthResp> {
+        let resp = Response::data(HealthResp {
+            status: "up".to_string(),
+        })
+        .to_json()?;
+        Ok(resp)
+    }
+
+    /// 就绪检查
+    pub async fn ready(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(task_monitor): Extension<TaskMonitor>,
+    ) -> HttpResponse {
+        let mdb: &Mdb = provider.provide();
+        let task_ok = task_monitor.is_healthy();
+        let checks = vec![
+            Self::check_db("main_db", &mdb.main_db).await,
+            Self::check_db("config_db", &mdb.config_db).await,
+            Self::check_log_writer(),
+            ReadyCheck {
+                name: "retention_task".to_string(),
+                ok: task_ok,
+                msg: (!task_ok).then(|| "日志清理任务已停止".to_string()),
+            },
+        ];
+
+        let ready = checks.iter().all(|v| v.ok);
+        let resp = Response::data(ReadyResp { ready, checks });
+        if ready {
+            return resp.into_response();
+        }
+        (StatusCode::SERVICE_UNAVAILABLE, resp.with_msg("服务未就绪")).into_response()
+    }
+
+    /// 构建信息
+    pub async fn version(
+        Extension(provider): Extension<AInjectProvider>,
+    ) -> Responder<VersionResp> {
+        let mdb: &Mdb = provider.provide();
+        let db = mdb.main_db.db();
+        let migrations = Migrator::get_applied_migrations(db)
+            .await
+            .map_err(|err| {
+                error!("查询数据库迁移记录失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询数据库迁移记录失败")
+            })?
+            .len();
+        let db_backend = match db.get_database_backend() {
+            DatabaseBackend::MySql => "mysql",
+            DatabaseBackend::Postgres => "postgres",
+            DatabaseBackend::Sqlite => "sqlite",
+        };
+
+        let resp = Response::data(VersionResp {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: env!("GIT_HASH").to_string(),
+            build_time: env!("BUILD_TIME").to_string(),
+            db_backend: db_backend.to_string(),
+            migrations,
+        })
+        .to_json()?;
+        Ok(resp)
+    }
+
+    /// 检查数据库连接
+    async fn check_db(name: &str, db: &Arc<dyn PoolTrait>) -> ReadyCheck {
+        let result = db.db().ping().await;
+        if let Err(err) = &result {
+            error!("数据库连接检查失败, name: {name}, err: {:#?}", err);
+        }
+        ReadyCheck {
+            name: name.to_string(),
+            ok: result.is_ok(),
+            msg: result.err().map(|err| err.to_string()),
+        }
+    }
+
+    /// 检查数据库日志写入队列, 未启用数据库日志时视为正常
+    fn check_log_writer() -> ReadyCheck {
+        let name = "db_log_writer".to_string();
+        let Some(monitor) = DbWriterMonitor::global() else {
+            return ReadyCheck {
+                name,
+                ok: true,
+                msg: Some("未启用".to_string()),
+            };
+        };
+
+        let status = monitor.status();
+        let ok = status.running && status.queued < status.capacity;
+        ReadyCheck {
+            name,
+            ok,
+            msg: Some(format!(
+                "queued: {}/{}, dropped: {}",
+                status.queued, status.capacity, status.dropped
+            )),
+        }
+    }
+}
+
+/// 路由器
+pub struct HealthRouter;
+
+impl HealthRouter {
+    /// 注册`健康检查`路由
+    pub fn register() -> Router {
+        Router::new()
+            .route("/health", get(HealthController::health))
+            .route("/ready", get(HealthController::ready))
+            .route("/version", get(HealthController::version))
+    }
+}
@@ -0,0 +1,342 @@
+//! 数据库初始化
+//!
+//! - 启动时按 [`MigrateMode`] 执行或检查数据库迁移, 自动迁移时写入预设数据
+//! - `POST /initialize/table`: 执行待执行的迁移并写入预设数据
+//! - `GET /initialize/status`: 查询已执行及待执行的迁移
+//!
+//! 初始化接口位于鉴权白名单内, 由接口自行校验: 携带有效的管理员 Token,
+//! 或通过 `X-Initialize-Token` 请求头携带配置的一次性令牌, 令牌在初始化成功后失效.
+use std::{io::Write, path::Path, sync::Arc};
+
+use axum::{
+    Extension, Router,
+    http::{HeaderMap, header},
+    routing::{get, post},
+};
+use colored::Colorize;
+use log::{error, info, warn};
+use sea_orm::DbErr;
+use sea_orm_migration::{MigrationStatus, MigratorTrait};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use axum_jwt::Claims;
+use axum_response::{Responder, Response};
+use config::initialize::{Initialize, MigrateMode};
+use database::Mdb;
+use err_code::{Error, ErrorMsg};
+use inject::AInjectProvider;
+use migration::{Migrator, SeedReport, Seeder, seed::AdminCredential};
+use service_hub::debox::{DataScopeService, enums::DataScope};
+
+/// 一次性令牌请求头
+pub const INITIALIZE_TOKEN_HEADER: &str = "X-Initialize-Token";
+
+/// 数据库迁移状态
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MigrationStatusResp {
+    /// 已执行的迁移
+    pub applied: Vec<String>,
+    /// 待执行的迁移
+    pub pending: Vec<String>,
+}
+
+/// 初始化数据表结果
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InitializeTableResp {
+    /// 本次执行的迁移
+    pub applied: Vec<String>,
+    /// 添加的角色数量
+    pub roles: usize,
+    /// 添加的系统配置数量
+    pub configs: usize,
+    /// 添加的字典维度数量
+    pub dict_dimensions: usize,
+    /// 创建的管理员用户名, 随机生成的密码不通过接口返回, 仅输出到标准输出及凭据文件
+    pub admin: Option<String>,
+}
+
+/// 初始化状态
+#[derive(Clone)]
+pub struct InitializeState {
+    /// 一次性令牌, 使用后置空
+    token: Arc<Mutex<Option<String>>>,
+    /// 初始管理员随机密码的写入文件
+    credential_file: Arc<String>,
+}
+
+impl InitializeState {
+    pub fn new(config: &Initialize) -> Self {
+        let token = (!config.token.is_empty()).then(|| config.token.clone());
+        InitializeState {
+            token: Arc::new(Mutex::new(token)),
+            credential_file: Arc::new(config.credential_file.clone()),
+        }
+    }
+}
+
+/// 数据库初始化
+pub struct Initializer;
+
+impl Initializer {
+    /// 启动时按配置执行或检查数据库迁移, 自动迁移完成后写入预设数据
+    pub async fn migrate_on_startup(config: &Initialize, db_pool: &Mdb) -> anyhow::Result<()> {
+        let db = db_pool.main_db.db();
+        match config.migrate {
+            MigrateMode::Off => {}
+            MigrateMode::Auto => {
+                let applied = Self::migrate(db).await?;
+                if !applied.is_empty() {
+                    info!("已执行数据库迁移: {:?}", applied);
+                }
+                Self::seed(db, &config.credential_file).await?;
+            }
+            MigrateMode::Check => {
+                let status = Self::status(db).await?;
+                if !status.pending.is_empty() {
+                    anyhow::bail!("存在待执行的数据库迁移, 拒绝启动: {:?}", status.pending);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 写入预设数据
+    ///
+    /// 创建初始管理员时记录用户名, 随机生成的密码不写入日志, 仅输出到标准输出,
+    /// 并在配置了 `credential_file` 时写入该文件, 便于无终端的环境(如移动端)获取.
+    pub async fn seed(
+        db: &sea_orm::DatabaseConnection,
+        credential_file: &str,
+    ) -> Result<SeedReport, DbErr> {
+        let report = Seeder::run(db).await?;
+        if report.roles + report.configs + report.dict_dimensions > 0 {
+            info!(
+                "已写入预设数据, roles: {}, configs: {}, dict_dimensions: {}",
+                report.roles, report.configs, report.dict_dimensions
+            );
+        }
+        if let Some(admin) = &report.admin {
+            info!("已创建初始管理员, username: {}", admin.username);
+            if admin.generated {
+                println!(
+                    "{}",
+                    format!(
+                        "初始管理员 username: {}, password: {} (仅显示一次, 请登录后修改密码)",
+                        admin.username, admin.password
+                    )
+                    .yellow()
+                );
+                if !credential_file.is_empty() {
+                    Self::write_credential(Path::new(credential_file), admin)?;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// 写入初始管理员账号, 文件仅当前用户可读写
+    fn write_credential(path: &Path, admin: &AdminCredential) -> Result<(), DbErr> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        options
+            .open(path)
+            .and_then(|mut file| {
+                writeln!(
+                    file,
+                    "username: {}\npassword: {}",
+                    admin.username, admin.password
+                )
+            })
+            .map_err(|err| {
+                error!(
+                    "写入初始管理员账号失败, path: {}, err: {:#?}",
+                    path.display(),
+                    err
+                );
+                DbErr::Custom(format!("写入初始管理员账号失败, err: {err}"))
+            })?;
+        info!(
+            "已写入初始管理员账号, 登录修改密码后请删除该文件, path: {}",
+            path.display()
+        );
+        Ok(())
+    }
+
+    /// 查询数据库迁移状态
+    pub async fn status(db: &sea_orm::DatabaseConnection) -> Result<MigrationStatusResp, DbErr> {
+        let mut resp = MigrationStatusResp::default();
+        for migration in Migrator::get_migration_with_status(db).await? {
+            let name = migration.name().to_string();
+            match migration.status() {
+                MigrationStatus::Applied => resp.applied.push(name),
+                MigrationStatus::Pending => resp.pending.push(name),
+            }
+        }
+        Ok(resp)
+    }
+
+    /// 执行待执行的迁移, 返回本次执行的迁移
+    pub async fn migrate(db: &sea_orm::DatabaseConnection) -> Result<Vec<String>, DbErr> {
+        let pending = Self::status(db).await?.pending;
+        if pending.is_empty() {
+            return Ok(pending);
+        }
+        Migrator::up(db, None).await?;
+        Ok(pending)
+    }
+}
+
+/// 控制器
+pub struct InitializeController;
+
+impl InitializeController {
+    /// 初始化数据表
+    pub async fn table(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(state): Extension<InitializeState>,
+        headers: HeaderMap,
+    ) -> Responder<InitializeTableResp> {
+        // 持有锁直至初始化完成, 避免并发执行迁移
+        let mut token = state.token.lock().await;
+        let by_token = Self::authorize(&provider, token.as_deref(), &headers).await?;
+
+        let mdb: &Mdb = provider.provide();
+        let applied = Initializer::migrate(mdb.main_db.db())
+            .await
+            .map_err(|err| {
+                error!("初始化数据表失败, err: {:#?}", err);
+                Error::DbTableMigration(err.to_string()).into_err_with_msg("初始化数据表失败")
+            })?;
+        let report = Initializer::seed(mdb.main_db.db(), &state.credential_file)
+            .await
+            .map_err(|err| {
+                error!("写入预设数据失败, err: {:#?}", err);
+                Error::DbDataInit.into_err_with_msg("写入预设数据失败")
+            })?;
+        if by_token {
+            warn!("初始化令牌已使用, 后续请使用管理员账号访问");
+            *token = None;
+        }
+        info!("初始化数据表完成, applied: {:?}", applied);
+
+        let resp = Response::data(InitializeTableResp {
+            applied,
+            roles: report.roles,
+            configs: report.configs,
+            dict_dimensions: report.dict_dimensions,
+            admin: report.admin.map(|v| v.username),
+        })
+        .to_json()?;
+        Ok(resp)
+    }
+
+    /// 查询数据库迁移状态
+    pub async fn status(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(state): Extension<InitializeState>,
+        headers: HeaderMap,
+    ) -> Responder<MigrationStatusResp> {
+        {
+            let token = state.token.lock().await;
+            Self::authorize(&provider, token.as_deref(), &headers).await?;
+        }
+
+        let mdb: &Mdb = provider.provide();
+        let result = Initializer::status(mdb.main_db.db()).await.map_err(|err| {
+            error!("查询数据库迁移状态失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询数据库迁移状态失败")
+        })?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+
+    /// 校验访问权限, 返回是否通过一次性令牌访问
+    async fn authorize(
+        provider: &AInjectProvider,
+        token: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Result<bool, ErrorMsg> {
+        let header_token = headers
+            .get(INITIALIZE_TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok());
+        if let Some(header_token) = header_token {
+            if token.is_some_and(|v| v == header_token) {
+                return Ok(true);
+            }
+            error!("初始化令牌无效或已使用");
+            return Err(Error::AuthIllegalRequest.into_err_with_msg("初始化令牌无效或已使用"));
+        }
+
+        let claims = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or_else(|| Error::HeadersNotAuthorization.into_err())
+            .and_then(|v| {
+                let claims = Claims::decode_token(v).map_err(|err| {
+                    Error::TokenDecode(err.to_string()).into_err_with_msg("鉴权解析失败")
+                })?;
+                claims.verify().map_err(|err| {
+                    Error::TokenDecode(err.to_string()).into_err_with_msg("鉴权解析失败")
+                })?;
+                Ok(claims)
+            })?;
+
+        let data_scope_service: DataScopeService = provider.provide();
+        if data_scope_service.scope(claims.user_id).await? != DataScope::All {
+            error!("非管理员访问初始化接口, user_id: {}", claims.user_id);
+            return Err(Error::AuthIllegalRequest.into_err_with_msg("仅管理员可访问"));
+        }
+        Ok(false)
+    }
+}
+
+/// 路由器
+pub struct InitializeRouter;
+
+impl InitializeRouter {
+    /// 注册`数据库初始化`路由
+    pub fn register() -> Router {
+        Router::new().nest(
+            "/initialize",
+            Router::new()
+                .route("/table", post(InitializeController::table))
+                .route("/status", get(InitializeController::status)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_credential() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("admin_credential.txt");
+        let admin = AdminCredential {
+            user_id: 1,
+            username: "admin".to_string(),
+            password: "secret".to_string(),
+            generated: true,
+        };
+        Initializer::write_credential(&path, &admin).expect("write credential");
+
+        let content = std::fs::read_to_string(&path).expect("read credential");
+        assert_eq!(content, "username: admin\npassword: secret\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path)
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,2 +1,7 @@
+pub mod app;
+pub mod cli;
+pub mod health;
+pub mod initialize;
+pub mod metrics;
 pub mod router;
 pub mod server;
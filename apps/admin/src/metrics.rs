@@ -0,0 +1,146 @@
+//! Prometheus 指标
+//!
+//! HTTP 请求指标由 [`http_metrics_layer`] 中间件记录;
+//! 数据库连接池及数据库日志写入队列指标在采集时更新.
+//!
+//! [`http_metrics_layer`]: axum_middleware::http_metrics::http_metrics_layer
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    Router,
+    extract::State,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use colored::Colorize;
+use log::{error, info, warn};
+use metrics::{counter, gauge};
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use tokio::net::TcpListener;
+
+use axum_middleware::http_metrics::{HTTP_DURATION_BUCKETS, HTTP_REQUEST_DURATION_SECONDS};
+use config::server::Metrics;
+use database::Mdb;
+use logger::DbWriterMonitor;
+
+use crate::router;
+
+/// 指标状态
+#[derive(Clone)]
+pub struct MetricsState {
+    handle: PrometheusHandle,
+    config: Metrics,
+    db_pool: Mdb,
+}
+
+impl MetricsState {
+    /// 安装全局指标记录器
+    pub fn install(config: Metrics, db_pool: Mdb) -> Result<Self, BuildError> {
+        let handle = PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+                HTTP_DURATION_BUCKETS,
+            )?
+            .install_recorder()?;
+        Ok(MetricsState {
+            handle,
+            config,
+            db_pool,
+        })
+    }
+
+    /// 更新采集时的指标
+    fn collect(&self) {
+        for (name, db) in [
+            ("main", &self.db_pool.main_db),
+            ("config", &self.db_pool.config_db),
+        ] {
+            let Some(status) = db.status() else {
+                continue;
+            };
+            let active = status.size.saturating_sub(status.idle);
+            gauge!("db_pool_connections", "db" => name, "state" => "active").set(active);
+            gauge!("db_pool_connections", "db" => name, "state" => "idle").set(status.idle);
+            gauge!("db_pool_max_connections", "db" => name).set(status.max);
+        }
+
+        if let Some(monitor) = DbWriterMonitor::global() {
+            let status = monitor.status();
+            gauge!("db_log_writer_running").set(status.running as u8);
+            gauge!("db_log_writer_queue_depth").set(status.queued as f64);
+            gauge!("db_log_writer_queue_capacity").set(status.capacity as f64);
+            counter!("db_log_writer_dropped_total").absolute(status.dropped);
+        }
+    }
+}
+
+/// 控制器
+pub struct MetricsController;
+
+impl MetricsController {
+    /// 获取指标
+    pub async fn metrics(State(state): State<Arc<MetricsState>>, headers: HeaderMap) -> Response {
+        let authorization = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        if !state.config.authorize(authorization) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+
+        state.collect();
+        state.handle.run_upkeep();
+        let content_type = HeaderValue::from_static("text/plain; version=0.0.4; charset=utf-8");
+        (
+            [(header::CONTENT_TYPE, content_type)],
+            state.handle.render(),
+        )
+            .into_response()
+    }
+}
+
+/// 路由器
+pub struct MetricsRouter;
+
+impl MetricsRouter {
+    /// 注册`指标`路由
+    pub fn register(state: Arc<MetricsState>) -> Router {
+        Router::new()
+            .route("/metrics", get(MetricsController::metrics))
+            .with_state(state)
+    }
+
+    /// 按配置提供指标接口
+    ///
+    /// 配置独立监听地址时在该地址启动指标服务并返回 `None`;
+    /// 否则返回挂载到服务地址的路由, 未配置访问令牌时不提供指标接口.
+    pub async fn serve(state: MetricsState) -> anyhow::Result<Option<Router>> {
+        let address = state.config.address.clone();
+        let has_token = !state.config.token.is_empty();
+        let app = Self::register(Arc::new(state));
+
+        if address.is_empty() {
+            if !has_token {
+                warn!("指标接口未配置访问令牌或独立监听地址, 已禁用");
+                return Ok(None);
+            }
+            return Ok(Some(app));
+        }
+
+        let addr: SocketAddr = address.parse()?;
+        let listener = TcpListener::bind(addr).await?;
+        info!(
+            "metrics listening on {}",
+            listener.local_addr()?.to_string().yellow()
+        );
+        tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, app)
+                .with_graceful_shutdown(router::shutdown_signal())
+                .await
+            {
+                error!("指标服务异常退出, err: {:#?}", err);
+            }
+        });
+        Ok(None)
+    }
+}
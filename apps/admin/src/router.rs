@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use axum::{Router, extract::DefaultBodyLimit};
+use axum::{Router, extract::DefaultBodyLimit, http::StatusCode};
 use log::warn;
 use tokio::signal;
 use tower::ServiceBuilder;
@@ -16,14 +16,21 @@ use tower_http::{
 };
 
 use axum_context::ContextLayer;
-use axum_jwt::JwtLayer;
+use axum_jwt::{AuthWhiteList, JwtLayer};
 use axum_middleware::{
-    constant::AUTH_WHITE_LIST, cors::cors_layer, empty_wrapper::empty_wrapper_layer,
+    api_operation::ApiOperationLayer, cors::cors_layer, empty_wrapper::empty_wrapper_layer,
+    http_metrics::http_metrics_layer,
 };
 use service_hub::{
-    auth::AuthRouter, debox::DeboxRouter, log::LogRouter, system::SystemRouter, user::UserRouter,
+    auth::{AccountRouter, AuthRouter},
+    debox::DeboxRouter,
+    log::LogRouter,
+    system::SystemRouter,
+    user::UserRouter,
 };
 
+use crate::{health::HealthRouter, initialize::InitializeRouter};
+
 /// axum handler for any request that fails to match the router routes.
 /// This implementation returns HTTP status code Not Found (404).
 pub async fn fallback(uri: axum::http::Uri) -> impl axum::response::IntoResponse {
@@ -60,19 +67,18 @@ pub async fn shutdown_signal() {
 }
 
 /// 注册路由
-pub fn register() -> Router {
+pub fn register(auth_white_list: AuthWhiteList, api_operation_layer: ApiOperationLayer) -> Router {
     let my_layers = ServiceBuilder::new()
         .layer(ContextLayer::new()) // 上下文
-        .layer(
-            JwtLayer::default()
-                .with_auth_white_list(AUTH_WHITE_LIST.iter().map(|v| v.to_string()).collect()),
-        ) // JWT 权限
+        .layer(JwtLayer::default().with_auth_white_list(auth_white_list)) // JWT 权限
+        .layer(api_operation_layer) // API操作日志
         .layer(axum::middleware::from_fn(empty_wrapper_layer)); // 空包装
 
     // 注意中间件加载顺序: Last in, first loading
     let layers = ServiceBuilder::new()
         // make sure to set request ids before the request reaches `TraceLayer`
         .set_x_request_id(MakeRequestUuid)
+        .layer(axum::middleware::from_fn(http_metrics_layer)) // HTTP 请求指标
         // .layer(HandleErrorLayer::new(handle_error)) // 自定义错误类型需要添加该中间件
         .layer(
             // set request_id log requests and responses
@@ -82,7 +88,10 @@ pub fn register() -> Router {
         ) // 高级跟踪/记录
         .layer(cors_layer()) // 为CORS添加标头的中间件
         .layer(CompressionLayer::new()) // 自动压缩响应
-        .layer(TimeoutLayer::new(Duration::from_secs(30))) // Timeout requests after 30 seconds
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            Duration::from_secs(30),
+        )) // Timeout requests after 30 seconds
         .layer(my_layers)
         .layer(DefaultBodyLimit::disable()) // Disable the default limit
         .layer(RequestBodyLimitLayer::new(250 * 1024 * 1024)) //250mb, 限制了传入请求的大小，防止试图通过大量请求压垮服务器的攻击
@@ -90,7 +99,10 @@ pub fn register() -> Router {
         .propagate_x_request_id();
 
     Router::new()
+        .merge(HealthRouter::register()) // 健康检查
+        .merge(InitializeRouter::register()) // 数据库初始化
         .merge(AuthRouter::register()) // 用户认证
+        .merge(AccountRouter::register()) // 个人账户
         .merge(UserRouter::register()) // 用户管理
         .merge(DeboxRouter::register()) // Debox管理
         .merge(SystemRouter::register()) // 系统管理
@@ -10,11 +10,19 @@ use listenfd::ListenFd;
 use tokio::net::TcpListener;
 
 use app_state::mobile::AppState;
+use axum_jwt::AuthWhiteList;
+use axum_middleware::api_operation::{ApiOperationLayer, ApiOperationWriter};
 use config::AppConfig;
 use database::Mdb;
 use inject::InjectProvider;
+use logger::redact::Redactor;
+use service_hub::log::RetentionTask;
 
-use crate::router;
+use crate::{
+    initialize::{InitializeState, Initializer},
+    metrics::{MetricsRouter, MetricsState},
+    router,
+};
 
 /// Http 服务
 pub struct HttpServer {}
@@ -23,16 +31,53 @@ impl HttpServer {
     /// 运行服务
     pub async fn run(
         app_config: AppConfig,
-        _db_pool: Mdb,
+        db_pool: Mdb,
         inject_provider: Arc<InjectProvider>,
         _app_state: Arc<AppState>,
     ) -> anyhow::Result<()> {
+        // 数据库迁移
+        Initializer::migrate_on_startup(&app_config.initialize, &db_pool).await?;
+
+        // 鉴权白名单
+        let auth_white_list = AuthWhiteList::new(&app_config.auth.white_list)?;
+        // API操作日志
+        let api_operation_writer = ApiOperationWriter::new(
+            db_pool.main_db.clone(),
+            app_config.api_operation.channel_capacity,
+        );
+        let api_operation_layer =
+            ApiOperationLayer::new(app_config.api_operation.clone(), api_operation_writer)
+                .with_redactor(Redactor::new(&app_config.logger.redact));
+
+        // 日志保留清理任务
+        let retention_task =
+            RetentionTask::new(inject_provider.clone(), app_config.logger.retention.clone());
+        let task_monitor = retention_task.monitor();
+        retention_task.spawn();
+
+        // Prometheus 指标
+        let metrics_router = if app_config.server.metrics.enable {
+            let state = MetricsState::install(app_config.server.metrics.clone(), db_pool.clone())?;
+            MetricsRouter::serve(state).await?
+        } else {
+            None
+        };
+
         // Build our application by creating our router.
-        let app = Router::new()
-            .nest("/api/v1", router::register()) // API 服务
+        let mut app = Router::new()
+            .nest(
+                "/api/v1",
+                router::register(auth_white_list, api_operation_layer),
+            ) // API 服务
             .fallback(router::fallback) // 用于处理与路由器路由不匹配的任何请求
+            .layer(Extension(app_config.logger.retention.clone())) // 日志保留配置
+            .layer(Extension(task_monitor)) // 后台任务状态
+            .layer(Extension(InitializeState::new(&app_config.initialize))) // 数据库初始化状态
             .layer(Extension(app_config.clone())) // 全局配置文件
             .layer(Extension(inject_provider)); // 依赖注入
+        if let Some(metrics_router) = metrics_router {
+            app = app.merge(metrics_router); // 指标
+        }
 
         // Run our application as a hyper server
         let mut listenfd = ListenFd::from_env();
@@ -2,7 +2,7 @@
 
 use std::path::PathBuf;
 
-use tracing_appender::non_blocking::WorkerGuard;
+use logger::LoggerGuard;
 
 /// 应用目录
 #[derive(Debug, Default, Clone)]
@@ -17,5 +17,5 @@ pub struct AppState {
     pub counter: u32,
     pub app_directory: AppDirector,
 
-    pub log_guards: Vec<WorkerGuard>,
+    pub log_guards: Vec<LoggerGuard>,
 }
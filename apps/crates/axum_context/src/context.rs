@@ -73,6 +73,15 @@ impl Context {
         self.role_ids = role_ids;
     }
 
+    /// 获取接口请求UUID
+    pub fn get_request_id(&self) -> String {
+        self.request_id.clone()
+    }
+    /// 设置接口请求UUID
+    pub fn set_request_id(&mut self, request_id: String) {
+        self.request_id = request_id;
+    }
+
     /// 获取接口鉴权类型
     pub fn get_api_auth_type(&self) -> Option<ApiAuthType> {
         self.api_auth_type.clone()
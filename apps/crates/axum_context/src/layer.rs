@@ -5,16 +5,36 @@ use std::task::Poll;
 use axum::{body::Body, extract::Request, http::Response};
 use futures::future::BoxFuture;
 use tower::{Layer, Service};
+use tracing::{Instrument, field::Empty, info_span};
 
 use crate::Context;
 
 /// 上下文中间件
-#[derive(Debug, Default, Clone)]
-pub struct ContextLayer {}
+///
+/// 从请求头中读取请求UUID, 并创建携带请求UUID及用户信息的 span,
+/// 用户信息由鉴权中间件通过 `Span::current().record` 补充
+#[derive(Debug, Clone)]
+pub struct ContextLayer {
+    request_id_header: String,
+}
+
+impl Default for ContextLayer {
+    fn default() -> Self {
+        ContextLayer {
+            request_id_header: "x-request-id".to_string(),
+        }
+    }
+}
 
 impl ContextLayer {
     pub fn new() -> Self {
-        ContextLayer {}
+        ContextLayer::default()
+    }
+
+    /// 设置请求UUID请求头
+    pub fn with_request_id_header(mut self, request_id_header: String) -> Self {
+        self.request_id_header = request_id_header;
+        self
     }
 }
 
@@ -22,13 +42,17 @@ impl<S> Layer<S> for ContextLayer {
     type Service = ContextService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        ContextService { inner }
+        ContextService {
+            inner,
+            request_id_header: self.request_id_header.clone(),
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct ContextService<S> {
     inner: S,
+    request_id_header: String,
 }
 
 impl<S> Service<Request> for ContextService<S>
@@ -51,16 +75,59 @@ where
         let not_ready_inner = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
 
-        Box::pin(async move {
-            // See `axum::RequestExt` for how to run extractors directly from  a `Request`.
-            let context = Context {
-                ..Default::default()
-            };
+        let request_id = req
+            .headers()
+            .get(&self.request_id_header)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let span = info_span!(
+            "context",
+            request_id = %request_id,
+            user_id = Empty,
+            username = Empty
+        );
+
+        Box::pin(
+            async move {
+                // See `axum::RequestExt` for how to run extractors directly from  a `Request`.
+                let context = Context {
+                    request_id,
+                    ..Default::default()
+                };
+
+                req.extensions_mut().insert(context);
+
+                let resp = inner.call(req).await?;
+                Ok(resp)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::to_bytes, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
 
-            req.extensions_mut().insert(context);
+    #[tokio::test]
+    async fn test_request_id() {
+        let app = Router::new()
+            .route("/", get(|ctx: Context| async move { ctx.get_request_id() }))
+            .layer(ContextLayer::new());
 
-            let resp = inner.call(req).await?;
-            Ok(resp)
-        })
+        let req = Request::builder()
+            .uri("/")
+            .header("x-request-id", "req-01")
+            .body(Body::empty())
+            .expect("build request");
+        let resp = app.oneshot(req).await.expect("call");
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        assert_eq!(body, "req-01");
     }
 }
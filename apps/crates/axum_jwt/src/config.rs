@@ -0,0 +1,55 @@
+//! 鉴权配置
+use serde::{Deserialize, Serialize};
+
+/// 鉴权配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// 请求白名单, 命中的请求无需鉴权
+    #[serde(default)]
+    pub white_list: Vec<WhiteListRule>,
+    /// 邮箱验证、密码重置等验证令牌的签名密钥, 为空时使用进程内随机密钥, 重启后已签发的令牌失效
+    #[serde(default)]
+    pub token_secret: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        let white_list = [
+            "/health",
+            "/ready",
+            "/version",
+            "/auth/captcha",
+            "/auth/sms-code",
+            "/auth/login",
+            "/auth/register",
+            "/auth/verify-email",
+            "/auth/verify-email/resend",
+            "/auth/forgot-password",
+            "/auth/reset-password",
+            "/initialize/table",
+            "/initialize/status",
+            "/template/axum-validators/say-hello",
+        ]
+        .into_iter()
+        .map(|path| WhiteListRule {
+            path: path.to_string(),
+            methods: Vec::new(),
+        })
+        .collect();
+
+        AuthConfig {
+            white_list,
+            token_secret: String::new(),
+        }
+    }
+}
+
+/// 白名单规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhiteListRule {
+    /// 请求路径, 支持 `{param}` 路径参数, `*` 匹配单段路径, 末尾的 `*` 或 `{*rest}` 匹配剩余路径
+    pub path: String,
+    /// 请求方法, 为空时匹配所有方法
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
@@ -38,6 +38,13 @@ pub enum Error {
     /// JWT 错误
     #[error(transparent)]
     JsonWebToken(#[from] jsonwebtoken::errors::Error),
+
+    /// 白名单路径规则错误
+    #[error("invalid white list path, {0}")]
+    WhiteListRoute(String),
+    /// 白名单请求方法错误
+    #[error("invalid white list method, {0}")]
+    WhiteListMethod(String),
 }
 
 impl Serialize for Error {
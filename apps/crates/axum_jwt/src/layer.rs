@@ -1,20 +1,24 @@
 //! JWT权限中间件
 use std::{boxed::Box, task::Poll};
 
-use axum::{body::Body, extract::Request, http::Response};
+use axum::{
+    body::Body,
+    extract::{OriginalUri, Request},
+    http::Response,
+};
 use axum_context::{ApiAuthType, Context};
 use futures::future::BoxFuture;
 use tower::{Layer, Service};
-use tracing::error;
+use tracing::{Span, error};
 
-use crate::{Claims, Error};
+use crate::{AuthWhiteList, Claims, Error};
 
 /// JWT权限中间件
 #[derive(Clone)]
 pub struct JwtLayer {
     authorization: String,
     authorization_bearer: String,
-    auth_white_list: Vec<String>,
+    auth_white_list: AuthWhiteList,
 }
 
 impl Default for JwtLayer {
@@ -22,13 +26,14 @@ impl Default for JwtLayer {
         JwtLayer {
             authorization: "Authorization".to_string(),
             authorization_bearer: "Bearer ".to_string(),
-            auth_white_list: vec![],
+            auth_white_list: AuthWhiteList::default(),
         }
     }
 }
 
 impl JwtLayer {
-    pub fn with_auth_white_list(mut self, auth_white_list: Vec<String>) -> Self {
+    /// 设置鉴权白名单
+    pub fn with_auth_white_list(mut self, auth_white_list: AuthWhiteList) -> Self {
         self.auth_white_list = auth_white_list;
         self
     }
@@ -62,7 +67,7 @@ pub struct JwtService<S> {
     inner: S,
     authorization: String,
     authorization_bearer: String,
-    auth_white_list: Vec<String>,
+    auth_white_list: AuthWhiteList,
 }
 
 impl<S> Service<Request> for JwtService<S>
@@ -95,9 +100,14 @@ where
             //     return Ok(resp);
             // }
 
-            // 白名单放行
-            let path = req.uri().path();
-            if auth_white_list.clone().contains(&path.to_string()) {
+            // 白名单放行, 同时匹配嵌套路由内的路径及原始请求路径
+            let method = req.method();
+            let in_white_list = auth_white_list.contains(method, req.uri().path())
+                || req
+                    .extensions()
+                    .get::<OriginalUri>()
+                    .is_some_and(|uri| auth_white_list.contains(method, uri.path()));
+            if in_white_list {
                 let resp = inner.call(req).await?;
                 return Ok(resp);
             }
@@ -120,7 +130,10 @@ where
                 }
             };
 
-            // 设置上下文
+            // 设置上下文, 同时将用户信息记录至 ContextLayer 创建的 span
+            let span = Span::current();
+            span.record("user_id", claims.user_id);
+            span.record("username", claims.username.as_str());
             if let Some(ctx) = req.extensions_mut().get_mut::<Context>() {
                 ctx.set_user_id(claims.user_id);
                 ctx.set_user_name(claims.username);
@@ -172,3 +185,67 @@ impl<S> JwtService<S> {
         Ok(claims)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Router,
+        body::to_bytes,
+        http::Method,
+        routing::{get, post},
+    };
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::WhiteListRule;
+
+    async fn call(app: Router, method: Method, uri: &str) -> String {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .expect("build request");
+        let resp = app.oneshot(req).await.expect("call");
+        let body = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        String::from_utf8_lossy(&body).to_string()
+    }
+
+    fn app() -> Router {
+        let white_list = AuthWhiteList::new(&[
+            WhiteListRule {
+                path: "/auth/login".to_string(),
+                methods: vec!["POST".to_string()],
+            },
+            WhiteListRule {
+                path: "/api/v1/public/*".to_string(),
+                methods: vec![],
+            },
+        ])
+        .expect("white list");
+
+        let api = Router::new()
+            .route(
+                "/auth/login",
+                post(|| async { "ok" }).get(|| async { "ok" }),
+            )
+            .route("/public/{*rest}", get(|| async { "ok" }))
+            .route("/user/me", get(|| async { "ok" }))
+            .layer(JwtLayer::default().with_auth_white_list(white_list));
+        Router::new().nest("/api/v1", api)
+    }
+
+    #[tokio::test]
+    async fn test_white_list() {
+        // 嵌套路由内的路径
+        assert_eq!(call(app(), Method::POST, "/api/v1/auth/login").await, "ok");
+        // 请求方法不匹配
+        assert_ne!(call(app(), Method::GET, "/api/v1/auth/login").await, "ok");
+        // 原始请求路径
+        assert_eq!(call(app(), Method::GET, "/api/v1/public/a/b").await, "ok");
+        // 非白名单
+        let body = call(app(), Method::GET, "/api/v1/user/me").await;
+        assert!(body.contains("Authorization"));
+    }
+}
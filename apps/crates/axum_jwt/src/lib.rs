@@ -4,5 +4,11 @@ pub use claims::Claims;
 mod error;
 pub use error::Error;
 
+mod config;
+pub use config::{AuthConfig, WhiteListRule};
+
+mod white_list;
+pub use white_list::AuthWhiteList;
+
 mod layer;
 pub use layer::JwtLayer;
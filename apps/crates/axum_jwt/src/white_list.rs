@@ -0,0 +1,147 @@
+//! 鉴权白名单
+//!
+//! 基于 matchit 的路由匹配, 支持路径参数、通配符及请求方法限制。
+//! 规则路径既可以相对于 API 挂载点(如 `/auth/login`), 也可以是完整路径(如 `/api/v1/auth/login`)。
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::http::Method;
+use matchit::Router;
+
+use crate::{Error, config::WhiteListRule};
+
+/// 鉴权白名单
+#[derive(Debug, Clone, Default)]
+pub struct AuthWhiteList {
+    /// 路径 -> 允许的请求方法, 为空时允许所有方法
+    router: Arc<Router<Vec<Method>>>,
+}
+
+impl AuthWhiteList {
+    /// 根据白名单规则构建
+    pub fn new(rules: &[WhiteListRule]) -> Result<Self, Error> {
+        // 合并相同路径的规则, 任一规则未限制方法时则允许所有方法
+        let mut routes: BTreeMap<String, Vec<Method>> = BTreeMap::new();
+        for rule in rules {
+            let mut methods = Vec::with_capacity(rule.methods.len());
+            for method in &rule.methods {
+                let method = Method::from_bytes(method.trim().to_uppercase().as_bytes())
+                    .map_err(|_| Error::WhiteListMethod(method.clone()))?;
+                methods.push(method);
+            }
+
+            let path = Self::to_route(&rule.path);
+            match routes.get_mut(&path) {
+                Some(v) if v.is_empty() => {}
+                Some(v) if methods.is_empty() => v.clear(),
+                Some(v) => {
+                    for method in methods {
+                        if !v.contains(&method) {
+                            v.push(method);
+                        }
+                    }
+                }
+                None => {
+                    routes.insert(path, methods);
+                }
+            }
+        }
+
+        let mut router = Router::new();
+        for (path, methods) in routes {
+            router
+                .insert(path.clone(), methods)
+                .map_err(|err| Error::WhiteListRoute(format!("{path}: {err}")))?;
+        }
+
+        Ok(AuthWhiteList {
+            router: Arc::new(router),
+        })
+    }
+
+    /// 请求是否命中白名单
+    pub fn contains(&self, method: &Method, path: &str) -> bool {
+        match self.router.at(path) {
+            Ok(matched) => matched.value.is_empty() || matched.value.contains(method),
+            Err(_) => false,
+        }
+    }
+
+    /// 将 `*` 通配符转换为 matchit 路由语法
+    fn to_route(path: &str) -> String {
+        let segments: Vec<&str> = path.split('/').collect();
+        let last = segments.len() - 1;
+        segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| match *segment {
+                "*" if index == last => "{*rest}".to_string(),
+                "*" => format!("{{_{index}}}"),
+                _ => segment.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(path: &str, methods: &[&str]) -> WhiteListRule {
+        WhiteListRule {
+            path: path.to_string(),
+            methods: methods.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_exact_path() -> Result<(), Error> {
+        let white_list = AuthWhiteList::new(&[rule("/auth/login", &[])])?;
+        assert!(white_list.contains(&Method::POST, "/auth/login"));
+        assert!(white_list.contains(&Method::GET, "/auth/login"));
+        assert!(!white_list.contains(&Method::POST, "/auth/login/x"));
+        assert!(!white_list.contains(&Method::POST, "/auth"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wildcard_path() -> Result<(), Error> {
+        let white_list = AuthWhiteList::new(&[
+            rule("/public/*", &[]),
+            rule("/user/*/avatar", &[]),
+            rule("/file/{id}", &["GET"]),
+        ])?;
+        assert!(white_list.contains(&Method::GET, "/public/a"));
+        assert!(white_list.contains(&Method::GET, "/public/a/b/c"));
+        assert!(!white_list.contains(&Method::GET, "/public"));
+        assert!(white_list.contains(&Method::GET, "/user/1/avatar"));
+        assert!(!white_list.contains(&Method::GET, "/user/1/2/avatar"));
+        assert!(white_list.contains(&Method::GET, "/file/10"));
+        assert!(!white_list.contains(&Method::DELETE, "/file/10"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_methods_merge() -> Result<(), Error> {
+        let white_list = AuthWhiteList::new(&[
+            rule("/dict/options", &["get"]),
+            rule("/dict/options", &["HEAD"]),
+            rule("/health", &["GET"]),
+            rule("/health", &[]),
+        ])?;
+        assert!(white_list.contains(&Method::GET, "/dict/options"));
+        assert!(white_list.contains(&Method::HEAD, "/dict/options"));
+        assert!(!white_list.contains(&Method::POST, "/dict/options"));
+        assert!(white_list.contains(&Method::POST, "/health"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_rule() {
+        let result = AuthWhiteList::new(&[rule("/a", &["GE T"])]);
+        assert!(matches!(result, Err(Error::WhiteListMethod(_))));
+
+        let result = AuthWhiteList::new(&[rule("/a/{id}", &[]), rule("/a/{name}", &[])]);
+        assert!(matches!(result, Err(Error::WhiteListRoute(_))));
+    }
+}
@@ -0,0 +1,97 @@
+//! API操作日志配置
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+/// API操作日志配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiOperationConfig {
+    /// 是否启用
+    pub enable: bool,
+    /// 需要记录的路径, 为空时记录所有路径
+    ///
+    /// 路径不包含 API 前缀, 以 `*` 结尾时按前缀匹配
+    pub include_paths: Vec<String>,
+    /// 不需要记录的路径, 优先于 include_paths
+    pub exclude_paths: Vec<String>,
+    /// 是否记录请求体与响应体
+    pub capture_body: bool,
+    /// 请求体与响应体记录的最大字节数, 超出部分将被截断
+    pub max_body_size: usize,
+    /// 写入通道容量, 通道已满时丢弃日志
+    pub channel_capacity: usize,
+    /// 受信任的反向代理地址
+    ///
+    /// 仅当连接来自受信任的代理时才使用 `X-Forwarded-For`/`X-Real-IP` 请求头中的客户端地址
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl Default for ApiOperationConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            capture_body: true,
+            max_body_size: 4096,
+            channel_capacity: 1000,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl ApiOperationConfig {
+    /// 路径是否需要记录
+    pub fn should_record(&self, path: &str) -> bool {
+        if !self.enable {
+            return false;
+        }
+        if self.exclude_paths.iter().any(|v| Self::match_path(v, path)) {
+            return false;
+        }
+        if self.include_paths.is_empty() {
+            return true;
+        }
+        self.include_paths.iter().any(|v| Self::match_path(v, path))
+    }
+
+    /// 是否为受信任的代理地址
+    pub fn is_trusted_proxy(&self, addr: &IpAddr) -> bool {
+        self.trusted_proxies.contains(addr)
+    }
+
+    /// 路径匹配, 以 `*` 结尾时按前缀匹配
+    fn match_path(pattern: &str, path: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => pattern == path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_record() {
+        let config = ApiOperationConfig {
+            include_paths: vec!["/user/*".to_owned(), "/debox/debox-accounts".to_owned()],
+            exclude_paths: vec!["/user/me/*".to_owned()],
+            ..Default::default()
+        };
+        assert!(config.should_record("/user/users"));
+        assert!(config.should_record("/debox/debox-accounts"));
+        assert!(!config.should_record("/debox/debox-accounts/1"));
+        assert!(!config.should_record("/user/me/password"));
+        assert!(!config.should_record("/log/system-logs"));
+
+        let config = ApiOperationConfig {
+            enable: false,
+            ..Default::default()
+        };
+        assert!(!config.should_record("/user/users"));
+    }
+}
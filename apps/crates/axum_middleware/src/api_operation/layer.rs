@@ -0,0 +1,458 @@
+//! API操作日志中间件
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    task::Poll,
+    time::Instant,
+};
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::{ConnectInfo, OriginalUri, Request},
+    http::{HeaderMap, Response, header},
+};
+use chrono::Local;
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+use tracing::error;
+
+use axum_context::Context;
+use entity::log::log_api_operation::{self, enums::HttpType};
+use logger::redact::Redactor;
+
+use super::{ApiOperationConfig, ApiOperationWriter};
+
+/// 请求ID请求头
+const REQUEST_ID: &str = "x-request-id";
+/// 允许缓冲的最大请求体/响应体字节数, 超出时不记录内容
+const MAX_BUFFER_SIZE: u64 = 1024 * 1024;
+
+/// API操作日志中间件
+///
+/// 需要放在 `ContextLayer` 与 `JwtLayer` 之后, 以获取当前用户信息
+#[derive(Clone)]
+pub struct ApiOperationLayer {
+    config: Arc<ApiOperationConfig>,
+    writer: ApiOperationWriter,
+    redactor: Redactor,
+}
+
+impl ApiOperationLayer {
+    pub fn new(config: ApiOperationConfig, writer: ApiOperationWriter) -> Self {
+        ApiOperationLayer {
+            config: Arc::new(config),
+            writer,
+            redactor: Redactor::default(),
+        }
+    }
+
+    /// 设置脱敏器
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+}
+
+impl<S> Layer<S> for ApiOperationLayer {
+    type Service = ApiOperationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiOperationService {
+            inner,
+            config: self.config.clone(),
+            writer: self.writer.clone(),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiOperationService<S> {
+    inner: S,
+    config: Arc<ApiOperationConfig>,
+    writer: ApiOperationWriter,
+    redactor: Redactor,
+}
+
+impl<S> Service<Request> for ApiOperationService<S>
+where
+    S: Service<Request, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + Sync,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    // `BoxFuture` is a type alias for `Pin<Box<dyn Future + Send + 'a>>`
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let not_ready_inner = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, not_ready_inner);
+        let config = self.config.clone();
+        let writer = self.writer.clone();
+        let redactor = self.redactor.clone();
+
+        Box::pin(async move {
+            if !config.should_record(req.uri().path()) {
+                return inner.call(req).await;
+            }
+
+            let start = Instant::now();
+            let (parts, body) = req.into_parts();
+            let (body, req_body) = capture_body(&config, &redactor, &parts.headers, body).await;
+
+            let path = parts
+                .extensions
+                .get::<OriginalUri>()
+                .map(|uri| uri.path().to_string())
+                .unwrap_or_else(|| parts.uri.path().to_string());
+            let (user_id, username) = match parts.extensions.get::<Context>() {
+                Some(ctx) if ctx.get_user_id() != 0 => {
+                    (Some(ctx.get_user_id()), Some(ctx.get_user_name()))
+                }
+                _ => (None, None),
+            };
+            // 优先使用上下文中的请求UUID, 与系统日志保持一致
+            let request_id = parts
+                .extensions
+                .get::<Context>()
+                .map(|ctx| ctx.get_request_id())
+                .filter(|v| !v.is_empty())
+                .or_else(|| header_value(&parts.headers, REQUEST_ID));
+            let record = log_api_operation::Model {
+                id: 0,
+                user_id,
+                username,
+                request_id,
+                status_code: 0,
+                method: parts.method.to_string(),
+                path,
+                content_type: header_value(&parts.headers, header::CONTENT_TYPE.as_str())
+                    .unwrap_or_default(),
+                query: parts.uri.query().map(|v| redactor.redact_form(v)),
+                body: req_body,
+                remote_addr: remote_addr(&config, &parts.headers, &parts.extensions),
+                user_agent: header_value(&parts.headers, header::USER_AGENT.as_str())
+                    .unwrap_or_default(),
+                cost: 0,
+                http_type: HttpType::Req.into(),
+                desc: None,
+                created_at: Local::now().naive_local(),
+            };
+
+            let resp = inner.call(Request::from_parts(parts, body)).await?;
+
+            let (parts, body) = resp.into_parts();
+            let (body, resp_body) = capture_body(&config, &redactor, &parts.headers, body).await;
+            let cost = start.elapsed().as_millis().min(i16::MAX as u128) as i16;
+            let resp_record = log_api_operation::Model {
+                status_code: parts.status.as_u16() as i32,
+                content_type: header_value(&parts.headers, header::CONTENT_TYPE.as_str())
+                    .unwrap_or_default(),
+                body: resp_body,
+                cost,
+                http_type: HttpType::Resp.into(),
+                created_at: Local::now().naive_local(),
+                ..record.clone()
+            };
+            writer.write(log_api_operation::Model {
+                status_code: resp_record.status_code,
+                cost,
+                ..record
+            });
+            writer.write(resp_record);
+
+            Ok(Response::from_parts(parts, body))
+        })
+    }
+}
+
+/// 获取请求头的值
+fn header_value(headers: &HeaderMap, key: &str) -> Option<String> {
+    headers
+        .get(key)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+}
+
+/// 获取客户端地址
+///
+/// 仅当连接来自受信任的代理时使用代理转发的地址, 转发链路从右向左取第一个非受信任代理的地址,
+/// 否则使用连接的对端地址, 避免客户端伪造请求头.
+fn remote_addr(
+    config: &ApiOperationConfig,
+    headers: &HeaderMap,
+    extensions: &axum::http::Extensions,
+) -> String {
+    let Some(peer) = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|v| v.0.ip())
+    else {
+        return String::new();
+    };
+    if !config.is_trusted_proxy(&peer) {
+        return peer.to_string();
+    }
+
+    if let Some(forwarded) = header_value(headers, "x-forwarded-for") {
+        let addrs = forwarded
+            .split(',')
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        let addr = addrs
+            .iter()
+            .rev()
+            .find(|v| {
+                !v.parse::<IpAddr>()
+                    .is_ok_and(|addr| config.is_trusted_proxy(&addr))
+            })
+            .or(addrs.first());
+        if let Some(addr) = addr {
+            return addr.to_string();
+        }
+    }
+    if let Some(addr) = header_value(headers, "x-real-ip") {
+        return addr;
+    }
+    peer.to_string()
+}
+
+/// 读取文本类型的请求体/响应体, 返回重建后的 Body 及脱敏截断后的内容
+///
+/// 文件上传、下载及流式响应等不记录内容
+async fn capture_body(
+    config: &ApiOperationConfig,
+    redactor: &Redactor,
+    headers: &HeaderMap,
+    body: Body,
+) -> (Body, Option<String>) {
+    if !config.capture_body {
+        return (body, None);
+    }
+    let content_type = header_value(headers, header::CONTENT_TYPE.as_str()).unwrap_or_default();
+    if !is_text_content(&content_type) {
+        return (body, None);
+    }
+    match body.size_hint().exact() {
+        Some(size) if size <= MAX_BUFFER_SIZE => {}
+        _ => return (body, None),
+    }
+
+    let bytes = match axum::body::to_bytes(body, MAX_BUFFER_SIZE as usize).await {
+        Ok(v) => v,
+        Err(err) => {
+            error!("读取请求体/响应体失败, err: {:#?}", err);
+            return (Body::empty(), None);
+        }
+    };
+    if bytes.is_empty() {
+        return (Body::from(bytes), None);
+    }
+
+    let text = String::from_utf8_lossy(&bytes);
+    let text = if content_type.starts_with("application/json") {
+        redactor.redact_json(&text)
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        redactor.redact_form(&text)
+    } else {
+        redactor.redact_text(&text).into_owned()
+    };
+    (
+        Body::from(bytes),
+        Some(truncate(text, config.max_body_size)),
+    )
+}
+
+/// 是否为需要记录内容的文本类型
+fn is_text_content(content_type: &str) -> bool {
+    content_type.starts_with("application/json")
+        || content_type.starts_with("application/x-www-form-urlencoded")
+        || content_type.starts_with("text/plain")
+}
+
+/// 按字节数截断, 保证字符边界完整
+fn truncate(mut text: String, max_size: usize) -> String {
+    if text.len() <= max_size {
+        return text;
+    }
+    let mut index = max_size;
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    text.truncate(index);
+    text.push_str("...");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        Json, Router,
+        http::{Request, StatusCode},
+        routing::post,
+    };
+    use entity::log::LogApiOperationEntity;
+    use migration::mock::mock_db;
+    use sea_orm::{EntityTrait, QueryOrder};
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn records(
+        db: &Arc<dyn database::PoolTrait>,
+        total: usize,
+    ) -> Vec<log_api_operation::Model> {
+        for _ in 0..50 {
+            let results = LogApiOperationEntity::find()
+                .order_by_asc(log_api_operation::Column::Id)
+                .all(db.db())
+                .await
+                .expect("query records");
+            if results.len() >= total {
+                return results;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("api operation records not written");
+    }
+
+    fn app(config: ApiOperationConfig, writer: ApiOperationWriter) -> Router {
+        let inner = Router::new()
+            .route(
+                "/login",
+                post(|Json(body): Json<Value>| async move { Json(body) }),
+            )
+            .route("/secret", post(|| async { StatusCode::NO_CONTENT }))
+            .layer(ApiOperationLayer::new(config, writer))
+            .layer(axum::middleware::from_fn(
+                |mut req: Request<Body>, next: axum::middleware::Next| async move {
+                    let mut ctx = Context::default();
+                    ctx.set_user_id(7);
+                    ctx.set_user_name("user07".to_string());
+                    req.extensions_mut().insert(ctx);
+                    next.run(req).await
+                },
+            ));
+        Router::new().nest("/api/v1", inner)
+    }
+
+    #[tokio::test]
+    async fn test_record_request_and_response() {
+        let db = mock_db().await;
+        let writer = ApiOperationWriter::new(db.clone(), 10);
+        let config = ApiOperationConfig {
+            exclude_paths: vec!["/secret".to_owned()],
+            max_body_size: 64,
+            trusted_proxies: vec![[127, 0, 0, 1].into(), [10, 0, 0, 2].into()],
+            ..Default::default()
+        };
+
+        let body = json!({
+            "username": "user07",
+            "password": "123456",
+            "access_token": "token-01",
+            "remark": "x".repeat(64),
+        });
+        let req = Request::post("/api/v1/login?password=123456&page=1")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::USER_AGENT, "test-agent")
+            .header(REQUEST_ID, "req-01")
+            .header("x-forwarded-for", "10.0.0.1, 10.0.0.2")
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 8000))))
+            .body(Body::from(body.to_string()))
+            .expect("build request");
+        let resp = app(config.clone(), writer.clone())
+            .oneshot(req)
+            .await
+            .expect("call");
+        assert_eq!(resp.status(), StatusCode::OK);
+        // 响应体保持不变
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        assert_eq!(serde_json::from_slice::<Value>(&bytes).expect("json"), body);
+
+        // 排除的路径不记录
+        let req = Request::post("/api/v1/secret")
+            .body(Body::empty())
+            .expect("build request");
+        app(config, writer).oneshot(req).await.expect("call");
+
+        let results = records(&db, 2).await;
+        assert_eq!(results.len(), 2);
+        let (req_record, resp_record) = (&results[0], &results[1]);
+        assert_eq!(req_record.http_type, "REQ");
+        assert_eq!(resp_record.http_type, "RESP");
+        for record in &results {
+            assert_eq!(record.path, "/api/v1/login");
+            assert_eq!(record.method, "POST");
+            assert_eq!(record.status_code, 200);
+            assert_eq!(record.user_id, Some(7));
+            assert_eq!(record.username.as_deref(), Some("user07"));
+            assert_eq!(record.request_id.as_deref(), Some("req-01"));
+            assert_eq!(record.remote_addr, "10.0.0.1");
+            assert_eq!(record.user_agent, "test-agent");
+            assert_eq!(record.query.as_deref(), Some("password=******&page=1"));
+            let body = record.body.as_deref().expect("body");
+            assert!(!body.contains("123456"));
+            assert!(!body.contains("token-01"));
+            assert!(body.ends_with("..."));
+            assert!(body.len() <= 64 + 3);
+        }
+        assert!(
+            req_record
+                .body
+                .as_deref()
+                .expect("body")
+                .contains(r#""password":"******""#)
+        );
+    }
+
+    #[test]
+    fn test_remote_addr() {
+        let config = ApiOperationConfig {
+            trusted_proxies: vec![[127, 0, 0, 1].into()],
+            ..Default::default()
+        };
+        let request = |peer: [u8; 4], forwarded: Option<&str>| {
+            let mut builder =
+                Request::get("/").extension(ConnectInfo(SocketAddr::from((peer, 80))));
+            if let Some(forwarded) = forwarded {
+                builder = builder.header("x-forwarded-for", forwarded);
+            }
+            builder.body(()).expect("build request").into_parts().0
+        };
+
+        // 非受信任代理的连接忽略转发请求头
+        let parts = request([10, 0, 0, 9], Some("1.1.1.1"));
+        assert_eq!(
+            remote_addr(&config, &parts.headers, &parts.extensions),
+            "10.0.0.9"
+        );
+        // 取最右侧的非受信任代理地址, 客户端伪造的地址被忽略
+        let parts = request([127, 0, 0, 1], Some("6.6.6.6, 1.1.1.1"));
+        assert_eq!(
+            remote_addr(&config, &parts.headers, &parts.extensions),
+            "1.1.1.1"
+        );
+        let parts = request([127, 0, 0, 1], None);
+        assert_eq!(
+            remote_addr(&config, &parts.headers, &parts.extensions),
+            "127.0.0.1"
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("abc".to_string(), 3), "abc");
+        assert_eq!(truncate("中文字符".to_string(), 4), "中...");
+    }
+}
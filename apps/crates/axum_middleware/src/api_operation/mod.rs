@@ -0,0 +1,10 @@
+//! API操作日志
+//!
+//! 记录接口的请求与响应信息, 通过有界通道异步写入 `t_log_api_operation` 表
+pub mod config;
+pub mod layer;
+pub mod writer;
+
+pub use config::ApiOperationConfig;
+pub use layer::ApiOperationLayer;
+pub use writer::ApiOperationWriter;
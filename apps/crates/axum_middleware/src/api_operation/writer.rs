@@ -0,0 +1,50 @@
+//! API操作日志写入
+use std::sync::Arc;
+
+use sea_orm::{ActiveModelTrait, ActiveValue::NotSet};
+use tokio::sync::mpsc::{self, Receiver, Sender, error::TrySendError};
+use tracing::{error, warn};
+
+use database::PoolTrait;
+use entity::log::log_api_operation;
+
+/// API操作日志写入器
+///
+/// 日志通过有界通道发送至后台任务入库, 通道已满时直接丢弃, 不阻塞请求
+#[derive(Clone)]
+pub struct ApiOperationWriter {
+    tx: Sender<log_api_operation::Model>,
+}
+
+impl ApiOperationWriter {
+    /// 创建写入器, 并启动后台入库任务
+    pub fn new(db: Arc<dyn PoolTrait>, capacity: usize) -> Self {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        tokio::spawn(Self::loop_data(db, rx));
+        ApiOperationWriter { tx }
+    }
+
+    /// 发送日志数据到通道
+    pub fn write(&self, data: log_api_operation::Model) {
+        match self.tx.try_send(data) {
+            Ok(_) => {}
+            Err(TrySendError::Full(data)) => {
+                warn!("API操作日志通道已满, 丢弃日志, path: {}", data.path);
+            }
+            Err(TrySendError::Closed(data)) => {
+                warn!("API操作日志通道已关闭, 丢弃日志, path: {}", data.path);
+            }
+        }
+    }
+
+    /// 循环接收数据入库
+    async fn loop_data(db: Arc<dyn PoolTrait>, mut rx: Receiver<log_api_operation::Model>) {
+        while let Some(data) = rx.recv().await {
+            let mut active_model: log_api_operation::ActiveModel = data.into();
+            active_model.id = NotSet;
+            if let Err(err) = active_model.insert(db.db()).await {
+                error!("添加API操作日志失败, err: {:#?}", err);
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! HTTP 请求指标
+//!
+//! 按路由模板、请求方法及响应状态统计请求数量及耗时, 并统计处理中的请求数量.
+//! 未匹配路由的请求统一记录为 `unmatched`, 避免指标标签数量无限增长.
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics::{Gauge, counter, gauge, histogram};
+
+/// 请求数量
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+/// 请求耗时, 秒
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+/// 处理中的请求数量
+pub const HTTP_REQUESTS_IN_FLIGHT: &str = "http_requests_in_flight";
+
+/// 请求耗时分桶, 秒
+pub const HTTP_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// HTTP 请求指标中间件
+/// ```ignore
+/// use axum::Router;
+///
+/// Router::new().layer(axum::middleware::from_fn(http_metrics_layer))
+/// ```
+pub async fn http_metrics_layer(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|v| v.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+
+    let in_flight = InFlightGuard::new(gauge!(HTTP_REQUESTS_IN_FLIGHT));
+    let start = Instant::now();
+
+    let resp = next.run(request).await;
+
+    drop(in_flight);
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", resp.status().as_u16().to_string()),
+    ];
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(start.elapsed().as_secs_f64());
+
+    resp
+}
+
+/// 处理中的请求计数守卫
+///
+/// 释放时减少计数, 请求被取消(如客户端断开、超时)时同样生效
+struct InFlightGuard(Gauge);
+
+impl InFlightGuard {
+    fn new(gauge: Gauge) -> Self {
+        gauge.increment(1);
+        InFlightGuard(gauge)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.decrement(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::StatusCode, routing::get};
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_http_metrics() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let app = Router::new()
+            .route("/users/{id}", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(http_metrics_layer));
+        for uri in ["/users/1", "/users/2", "/missing"] {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_ne!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        let output = handle.render();
+        assert!(
+            output.contains(
+                r#"http_requests_total{method="GET",route="/users/{id}",status="200"} 2"#
+            )
+        );
+        assert!(
+            output
+                .contains(r#"http_requests_total{method="GET",route="unmatched",status="404"} 1"#)
+        );
+        assert!(output.contains("http_requests_in_flight 0"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_in_flight_cancelled() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let app = Router::new()
+            .route("/slow", get(std::future::pending::<&str>))
+            .layer(axum::middleware::from_fn(http_metrics_layer));
+        let req = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+        let mut call = Box::pin(app.oneshot(req));
+        // 请求处理中
+        assert!(futures::poll!(&mut call).is_pending());
+        assert!(handle.render().contains("http_requests_in_flight 1"));
+
+        // 请求被取消
+        drop(call);
+        assert!(handle.render().contains("http_requests_in_flight 0"));
+    }
+}
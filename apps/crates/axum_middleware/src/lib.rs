@@ -5,3 +5,7 @@ pub mod error;
 pub mod empty_wrapper;
 
 pub mod cors;
+
+pub mod api_operation;
+
+pub mod http_metrics;
@@ -2,12 +2,16 @@
 use std::fs::read_to_string;
 use std::sync::OnceLock;
 
+use axum_jwt::AuthConfig;
+use axum_middleware::api_operation::ApiOperationConfig;
 use err_code::Error;
 use logger::config::LoggerConfig;
+use mailer::MailerConfig;
+use sms::SmsConfig;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{env, server};
+use crate::{env, initialize, server};
 
 /// 全局配置对象
 static GLOBAL_CONFIG: OnceLock<AppConfig> = OnceLock::new();
@@ -21,6 +25,12 @@ pub struct AppConfig {
     /// 服务配置
     #[serde(default)]
     pub server: server::Server,
+    /// 鉴权配置
+    #[serde(default)]
+    pub auth: AuthConfig,
+    /// API操作日志配置
+    #[serde(default)]
+    pub api_operation: ApiOperationConfig,
     /// MySQL 数据库配置
     #[serde(default)]
     pub mysql: database::Config,
@@ -30,9 +40,18 @@ pub struct AppConfig {
     /// Sqlite3 数据库配置
     #[serde(default)]
     pub sqlite: database::Config,
+    /// 数据库初始化配置
+    #[serde(default)]
+    pub initialize: initialize::Initialize,
     /// 日志配置
     #[serde(default)]
     pub logger: LoggerConfig,
+    /// 邮件配置
+    #[serde(default)]
+    pub mailer: MailerConfig,
+    /// 短信配置
+    #[serde(default)]
+    pub sms: SmsConfig,
 }
 
 impl AppConfig {
@@ -0,0 +1,46 @@
+//! 数据库初始化配置
+use serde::{Deserialize, Serialize};
+
+/// 启动时的数据库迁移方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMode {
+    /// 自动执行待执行的迁移
+    #[default]
+    Auto,
+    /// 仅检查, 存在待执行的迁移时拒绝启动
+    Check,
+    /// 不执行也不检查
+    Off,
+}
+
+/// 数据库初始化配置
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Initialize {
+    /// 启动时的数据库迁移方式
+    #[serde(default)]
+    pub migrate: MigrateMode,
+    /// 初始化接口的一次性令牌, 通过 `X-Initialize-Token` 请求头访问, 为空时仅管理员可访问
+    #[serde(default)]
+    pub token: String,
+    /// 初始管理员随机密码的写入文件, 为空时仅输出到标准输出
+    #[serde(default)]
+    pub credential_file: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let config: Initialize = serde_yaml::from_str("migrate: check").expect("deserialize");
+        assert_eq!(config.migrate, MigrateMode::Check);
+        assert!(config.token.is_empty());
+        assert!(config.credential_file.is_empty());
+
+        let config: Initialize = serde_yaml::from_str("token: secret").expect("deserialize");
+        assert_eq!(config.migrate, MigrateMode::Auto);
+        assert!(serde_yaml::from_str::<Initialize>("migrate: always").is_err());
+    }
+}
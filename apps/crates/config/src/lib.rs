@@ -1,6 +1,7 @@
 //! 配置
 pub mod app_config;
 pub mod env;
+pub mod initialize;
 pub mod server;
 
 pub use app_config::AppConfig;
@@ -10,6 +10,9 @@ pub struct Server {
     pub upload: Upload,
     /// 验证码配置
     pub captcha: Captcha,
+    /// 指标配置
+    #[serde(default)]
+    pub metrics: Metrics,
 }
 
 impl Default for Server {
@@ -19,10 +22,9 @@ impl Default for Server {
                 address: String::from("0.0.0.0"),
                 port: 8000,
             },
-            upload: Upload {
-                filepath: "./upload".to_string(),
-            },
+            upload: Upload::default(),
             captcha: Captcha { expire: 30 },
+            metrics: Metrics::default(),
         }
     }
 }
@@ -43,7 +45,70 @@ impl Base {
 /// 上传文件配置
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Upload {
+    /// 上传文件路径
     pub filepath: String,
+    /// 单个文件大小上限, 单位MB
+    #[serde(default = "Upload::default_max_size")]
+    pub max_size: u64,
+    /// 不超过该大小的文件保存到数据库, 超过则保存到上传文件路径, 单位KB
+    #[serde(default = "Upload::default_db_max_size")]
+    pub db_max_size: u64,
+    /// 允许上传的文件类型, 支持 `image/*` 通配, 为空时不限制
+    #[serde(default = "Upload::default_allowed_types")]
+    pub allowed_types: Vec<String>,
+}
+
+impl Default for Upload {
+    fn default() -> Self {
+        Upload {
+            filepath: "./upload".to_string(),
+            max_size: Self::default_max_size(),
+            db_max_size: Self::default_db_max_size(),
+            allowed_types: Self::default_allowed_types(),
+        }
+    }
+}
+
+impl Upload {
+    fn default_max_size() -> u64 {
+        50
+    }
+
+    fn default_db_max_size() -> u64 {
+        256
+    }
+
+    fn default_allowed_types() -> Vec<String> {
+        ["image/*", "application/pdf", "application/zip"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// 单个文件大小上限, 单位字节
+    pub fn max_bytes(&self) -> u64 {
+        self.max_size * 1024 * 1024
+    }
+
+    /// 保存到数据库的文件大小上限, 单位字节
+    pub fn db_max_bytes(&self) -> u64 {
+        self.db_max_size * 1024
+    }
+
+    /// 文件类型是否允许上传
+    pub fn is_allowed(&self, mime_type: &str) -> bool {
+        if self.allowed_types.is_empty() {
+            return true;
+        }
+        self.allowed_types
+            .iter()
+            .any(|allowed| match allowed.strip_suffix("/*") {
+                Some(prefix) => mime_type
+                    .split_once('/')
+                    .is_some_and(|(kind, _)| kind == prefix),
+                None => allowed == mime_type,
+            })
+    }
 }
 
 /// 验证码配置
@@ -51,3 +116,66 @@ pub struct Upload {
 pub struct Captcha {
     pub expire: i8,
 }
+
+/// 指标配置
+///
+/// 指标接口须配置访问令牌或独立监听地址, 二者均未配置时不提供指标接口.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct Metrics {
+    /// 是否启用
+    #[serde(default)]
+    pub enable: bool,
+    /// 访问令牌, 通过 `Authorization: Bearer <token>` 访问, 为空时不校验
+    #[serde(default)]
+    pub token: String,
+    /// 独立监听地址, 如 `127.0.0.1:9100`, 为空时在服务地址的 `/metrics` 提供指标接口
+    #[serde(default)]
+    pub address: String,
+}
+
+impl Metrics {
+    /// 校验访问令牌
+    pub fn authorize(&self, authorization: Option<&str>) -> bool {
+        if self.token.is_empty() {
+            return true;
+        }
+        authorization
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v.trim() == self.token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_is_allowed() {
+        let upload = Upload::default();
+        assert!(upload.is_allowed("image/png"));
+        assert!(upload.is_allowed("application/pdf"));
+        assert!(!upload.is_allowed("application/x-msdownload"));
+        assert!(!upload.is_allowed("imagex/png"));
+
+        let upload = Upload {
+            allowed_types: vec![],
+            ..Default::default()
+        };
+        assert!(upload.is_allowed("application/x-msdownload"));
+    }
+
+    #[test]
+    fn test_metrics_authorize() {
+        let metrics = Metrics::default();
+        assert!(metrics.authorize(None));
+
+        let metrics = Metrics {
+            token: "secret".to_string(),
+            ..Default::default()
+        };
+        assert!(metrics.authorize(Some("Bearer secret")));
+        assert!(!metrics.authorize(Some("Bearer other")));
+        assert!(!metrics.authorize(Some("secret")));
+        assert!(!metrics.authorize(None));
+    }
+}
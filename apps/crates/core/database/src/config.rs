@@ -1,4 +1,6 @@
 //! 数据库配置
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 /// 数据库类型
@@ -91,10 +93,27 @@ impl Config {
                 // In memory: sqlite::memory:
                 self.sqlite_path
                     .clone()
-                    .map_or_else(|| "data.dat?mode=rwc".to_string(), |v| v)
+                    .unwrap_or_else(|| "data.dat?mode=rwc".to_string())
             }
         }
     }
+
+    /// Sqlite 数据库文件路径, 非 Sqlite 数据库或内存数据库时返回 `None`
+    pub fn sqlite_file(&self) -> Option<PathBuf> {
+        if self.r#type != DbType::Sqlite {
+            return None;
+        }
+        let dns = self.dns();
+        let path = dns
+            .strip_prefix("sqlite://")
+            .or_else(|| dns.strip_prefix("sqlite:"))
+            .unwrap_or(&dns);
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        if path.is_empty() || path == ":memory:" {
+            return None;
+        }
+        Some(PathBuf::from(path))
+    }
 }
 
 /// 参数配置
@@ -163,3 +182,27 @@ impl From<Level> for log::LevelFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_file() {
+        let config = |path: &str| Config {
+            r#type: DbType::Sqlite,
+            sqlite_path: Some(path.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config("sqlite://../mobile/data.dat?mode=rwc").sqlite_file(),
+            Some(PathBuf::from("../mobile/data.dat"))
+        );
+        assert_eq!(
+            config("sqlite:data.dat").sqlite_file(),
+            Some(PathBuf::from("data.dat"))
+        );
+        assert_eq!(config("sqlite::memory:").sqlite_file(), None);
+        assert_eq!(Config::default().sqlite_file(), None);
+    }
+}
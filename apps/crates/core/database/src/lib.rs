@@ -7,7 +7,7 @@ mod pagination;
 pub use pagination::Pagination;
 
 mod pool;
-pub use pool::{Pool, PoolTrait};
+pub use pool::{Pool, PoolStatus, PoolTrait};
 pub use sea_orm::DatabaseConnection;
 
 mod config;
@@ -4,7 +4,7 @@ use std::{future::Future, pin::Pin, time::Duration};
 use crate::config::Options;
 
 use sea_orm::{
-    ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DbErr,
+    ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DbErr, sqlx,
 };
 
 /// 数据库特征
@@ -14,6 +14,48 @@ pub trait PoolTrait: Send + Sync {
 
     /// 关闭数据库实例
     fn close(&self) -> Pin<Box<dyn Future<Output = Result<(), DbErr>> + Send>>;
+
+    /// 获取连接池状态
+    fn status(&self) -> Option<PoolStatus> {
+        PoolStatus::from_connection(self.db())
+    }
+}
+
+/// 连接池状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// 当前连接数
+    pub size: u32,
+    /// 空闲连接数
+    pub idle: u32,
+    /// 最大连接数
+    pub max: u32,
+}
+
+impl PoolStatus {
+    /// 获取数据库连接的连接池状态, 非连接池连接返回 `None`
+    pub fn from_connection(db: &DatabaseConnection) -> Option<PoolStatus> {
+        match db {
+            DatabaseConnection::SqlxMySqlPoolConnection(_) => {
+                Some(Self::from_pool(db.get_mysql_connection_pool()))
+            }
+            DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+                Some(Self::from_pool(db.get_postgres_connection_pool()))
+            }
+            DatabaseConnection::SqlxSqlitePoolConnection(_) => {
+                Some(Self::from_pool(db.get_sqlite_connection_pool()))
+            }
+            _ => None,
+        }
+    }
+
+    fn from_pool<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> PoolStatus {
+        PoolStatus {
+            size: pool.size(),
+            idle: pool.num_idle() as u32,
+            max: pool.options().get_max_connections(),
+        }
+    }
 }
 
 /// 数据库连接池
@@ -123,6 +165,24 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_status() -> Result<(), DbErr> {
+        let db_url = "sqlite::memory:".to_owned();
+        let options = Options::default();
+        let pool = Pool::new(db_url, options).await?;
+
+        let status = pool.status().expect("pool status");
+        assert!(status.size >= status.idle);
+        assert!(status.max >= status.size);
+        assert_eq!(
+            PoolStatus::from_connection(&DatabaseConnection::Disconnected),
+            None
+        );
+
+        let _ = pool.close().await;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_connect() -> Result<(), DbErr> {
         let db_url = "sqlite::memory:".to_owned();
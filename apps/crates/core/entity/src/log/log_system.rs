@@ -18,6 +18,8 @@ pub struct Model {
     pub user_id: Option<i32>,
     /// 用户名称
     pub username: Option<String>,
+    /// 请求ID
+    pub request_id: Option<String>,
 
     /// 日志记录器名称
     #[serde(default)]
@@ -19,7 +19,7 @@ pub struct Model {
     /// 字典维度ID
     pub dim_id: i32,
     /// 字典项标签
-    pub lable: String,
+    pub label: String,
     /// 字典项值
     pub value: String,
     /// 排序
@@ -15,18 +15,22 @@ pub struct Model {
     pub id: i32,
     /// 文件名称
     pub file_name: String,
-    /// 文件HASH值
+    /// 文件HASH值, SHA-256
     #[sea_orm(unique)]
     pub hash: String,
-    /// 文件数据, Base64编码
+    /// 文件数据, 保存在数据库中时有效
     pub data: Vec<u8>,
     /// 文件文件扩展名, 如svg, png
     pub extension: String,
     /// 内容类型, text/html
     /// [content-type](https://www.runoob.com/http/http-content-type.html)
     pub content_type: String,
-    /// 文件大小
-    pub size: u16,
+    /// 文件大小, 单位为字节
+    pub size: i64,
+    /// 存储方式(db:数据库,local:本地文件)
+    pub storage: String,
+    /// 本地文件相对路径
+    pub path: Option<String>,
     /// 描述信息
     pub desc: Option<String>,
     /// 创建时间
@@ -59,4 +63,33 @@ pub mod enums {
             }
         }
     }
+
+    /// 文件存储方式
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum StorageType {
+        /// 数据库
+        #[serde(rename = "db")]
+        Db,
+        /// 本地文件
+        #[serde(rename = "local")]
+        Local,
+    }
+
+    impl From<StorageType> for String {
+        fn from(value: StorageType) -> Self {
+            match value {
+                StorageType::Db => "db".to_owned(),
+                StorageType::Local => "local".to_owned(),
+            }
+        }
+    }
+
+    impl From<&str> for StorageType {
+        fn from(value: &str) -> Self {
+            match value {
+                "local" => StorageType::Local,
+                _ => StorageType::Db,
+            }
+        }
+    }
 }
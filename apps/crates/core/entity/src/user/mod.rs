@@ -2,13 +2,17 @@
 
 pub mod email;
 pub mod phone;
+pub mod phone_code;
 pub mod role;
 pub mod user_base;
 pub mod user_role_rel;
+pub mod verify_token;
 
 pub use email::Entity as EmailEntity;
 pub use phone::Entity as PhoneEntity;
+pub use phone_code::Entity as PhoneCodeEntity;
 pub use user_base::Entity as UserBaseEntity;
+pub use verify_token::Entity as VerifyTokenEntity;
 
 pub use role::Entity as RoleEntity;
 pub use user_role_rel::Entity as UserRoleRelEntity;
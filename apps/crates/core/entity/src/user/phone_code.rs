@@ -0,0 +1,67 @@
+//! 用户短信验证码表
+
+use chrono::Local;
+use sea_orm::{
+    ActiveModelBehavior, ConnectionTrait, DbErr, DeriveEntityModel, DerivePrimaryKey,
+    DeriveRelation, EnumIter, PrimaryKeyTrait, Set,
+    prelude::{DateTime, async_trait::async_trait},
+};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// 用户短信验证码表
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DeriveEntityModel)]
+#[sea_orm(table_name = "t_user_phone_code")]
+pub struct Model {
+    /// 自增ID
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// 手机号码
+    pub phone: String,
+    /// 使用场景(0:注册,1:登录,2:绑定手机号)
+    pub scene: i8,
+    /// 验证码摘要
+    #[serde(skip_serializing)]
+    pub code_hash: String,
+    /// 校验失败次数
+    pub attempts: i32,
+    /// 过期时间
+    pub expired_at: DateTime,
+    /// 状态(false:失效,true:有效)
+    pub status: bool,
+    /// 创建时间
+    pub created_at: DateTime,
+    /// 更新时间
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Will be triggered before insert / update
+    async fn before_save<C>(mut self, _db: &C, _insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.updated_at = Set(Local::now().naive_local());
+        Ok(self)
+    }
+}
+
+pub mod enums {
+    use super::*;
+
+    /// 验证码使用场景
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize_repr, Deserialize_repr)]
+    #[repr(i8)]
+    pub enum CodeScene {
+        /// 注册
+        Register = 0,
+        /// 登录
+        Login = 1,
+        /// 绑定手机号
+        Bind = 2,
+    }
+}
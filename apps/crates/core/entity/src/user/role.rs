@@ -8,6 +8,9 @@ use sea_orm::{
 };
 use serde::{Deserialize, Serialize};
 
+/// 管理员角色名称, 拥有该角色的用户可访问所有用户的数据
+pub const ADMIN_ROLE_NAME: &str = "管理员";
+
 /// 角色表
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DeriveEntityModel)]
 #[sea_orm(table_name = "t_user_role")]
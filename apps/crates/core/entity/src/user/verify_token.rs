@@ -0,0 +1,99 @@
+//! 用户验证令牌表
+
+use chrono::Local;
+use sea_orm::{
+    ActiveModelBehavior, ConnectionTrait, DbErr, DeriveEntityModel, DerivePrimaryKey, EntityTrait,
+    EnumIter, PrimaryKeyTrait, Related, RelationDef, RelationTrait, Set,
+    prelude::{DateTime, async_trait::async_trait},
+};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// 用户验证令牌表
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DeriveEntityModel)]
+#[sea_orm(table_name = "t_user_verify_token")]
+pub struct Model {
+    /// 令牌ID
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// 用户ID
+    pub user_id: i32,
+    /// 令牌类型(0:邮箱验证,1:重置密码,2:绑定邮箱)
+    pub token_type: i8,
+    /// 令牌摘要
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    /// 附加数据, 如待绑定的邮箱
+    pub payload: Option<String>,
+    /// 过期时间
+    pub expired_at: DateTime,
+    /// 使用时间
+    pub used_at: Option<DateTime>,
+    /// 创建时间
+    pub created_at: DateTime,
+    /// 更新时间
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    UserBase,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::UserBase => Entity::belongs_to(super::user_base::Entity)
+                .from(Column::UserId)
+                .to(super::user_base::Column::Id)
+                .into(),
+        }
+    }
+}
+
+impl Related<super::user_base::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserBase.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Will be triggered before insert / update
+    async fn before_save<C>(mut self, _db: &C, _insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.updated_at = Set(Local::now().naive_local());
+        Ok(self)
+    }
+}
+
+pub mod enums {
+    use super::*;
+
+    /// 令牌类型
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize_repr, Deserialize_repr)]
+    #[repr(i8)]
+    pub enum TokenType {
+        /// 邮箱验证
+        EmailVerify = 0,
+        /// 重置密码
+        PasswordReset = 1,
+        /// 绑定邮箱
+        EmailBind = 2,
+    }
+
+    impl TryFrom<i8> for TokenType {
+        type Error = ();
+
+        fn try_from(value: i8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(TokenType::EmailVerify),
+                1 => Ok(TokenType::PasswordReset),
+                2 => Ok(TokenType::EmailBind),
+                _ => Err(()),
+            }
+        }
+    }
+}
@@ -158,6 +158,24 @@ pub enum Error {
     #[error("No access permission")]
     CasbinNoAccessPermission,
 
+    // 消息通知与验证码
+    #[error("邮件发送失败, {0}")]
+    MailSendError(String) = 10451,
+    #[error("验证令牌无效")]
+    VerifyTokenInvalid,
+    #[error("验证令牌已过期")]
+    VerifyTokenExpire,
+    #[error("验证令牌已使用")]
+    VerifyTokenUsed,
+    #[error("短信发送失败, {0}")]
+    SmsSendError(String),
+    #[error("短信验证码错误")]
+    PhoneCodeInvalid,
+    #[error("短信验证码已过期, 请重新获取")]
+    PhoneCodeExpire,
+    #[error("短信验证码发送过于频繁, 请稍后重试")]
+    PhoneCodeTooFrequent,
+
     // 文件或目录操作
     #[error("parse file extension failed, {0}")]
     ParseFileExtension(String) = 10501,
@@ -181,6 +199,8 @@ pub enum Error {
     UploadFileError(String) = 20001,
     #[error("failed to generate user sharing code")]
     GenerateUserShareCore,
+    #[error("原密码错误")]
+    UserOldPasswordError,
 
     // SDK API
     #[error("comfyui error, {0}")]
@@ -2,22 +2,33 @@
 use std::sync::Arc;
 
 use database::{Mdb, PoolTrait};
+use mailer::Mailer;
+use sms::SmsSender;
 
 use nject::provider;
 
+#[cfg(feature = "mock")]
+pub mod mock;
+
 #[provider]
 pub struct InjectProvider {
     #[provide(Arc<dyn PoolTrait>, |x| x.clone())]
     db: Arc<dyn PoolTrait>,
     #[provide]
     mdb: Mdb,
+    #[provide(Arc<dyn Mailer>, |x| x.clone())]
+    mailer: Arc<dyn Mailer>,
+    #[provide(Arc<dyn SmsSender>, |x| x.clone())]
+    sms: Arc<dyn SmsSender>,
 }
 
 impl InjectProvider {
-    pub fn new(db_pool: Mdb) -> Self {
+    pub fn new(db_pool: Mdb, mailer: Arc<dyn Mailer>, sms: Arc<dyn SmsSender>) -> Self {
         InjectProvider {
             db: db_pool.main_db.clone(),
             mdb: db_pool,
+            mailer,
+            sms,
         }
     }
 }
@@ -0,0 +1,26 @@
+//! Mock 模拟测试
+
+use std::sync::Arc;
+
+use database::Mdb;
+use mailer::MemoryMailer;
+use sms::LogSender;
+
+use crate::InjectProvider;
+
+impl InjectProvider {
+    /// 创建已执行迁移的内存数据库的依赖注入提供者
+    pub async fn mock() -> Self {
+        Self::mock_with(MemoryMailer::default(), LogSender::default()).await
+    }
+
+    /// 使用指定的邮件及短信发送器创建, 便于读取发送的内容
+    pub async fn mock_with(mailer: MemoryMailer, sms: LogSender) -> Self {
+        let db = migration::mock::mock_db().await;
+        InjectProvider::new(
+            Mdb::new(db.clone(), db.clone()),
+            Arc::new(mailer),
+            Arc::new(sms),
+        )
+    }
+}
@@ -44,6 +44,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             enable: false,
             filepath: "logs".to_owned(),
             filename: "app.log".to_owned(),
+            ..Default::default()
         },
         db: DbConfig {
             level: config::Level::Debug,
@@ -51,7 +52,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             address: "sqlite://./data.dat?mode=rwc".to_owned(),
             log_name: "db_layer".to_owned(),
             options: Options::default(),
+            ..Default::default()
         },
+        redact: Default::default(),
+        retention: Default::default(),
     };
     let _guards = Logger::build(&conf).expect("日志初始化失败");
 
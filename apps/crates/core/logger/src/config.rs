@@ -21,6 +21,12 @@ pub struct LoggerConfig {
     /// 数据库配置
     #[serde(default)]
     pub db: DbConfig,
+    /// 脱敏配置
+    #[serde(default)]
+    pub redact: RedactConfig,
+    /// 日志保留配置
+    #[serde(default)]
+    pub retention: RetentionConfig,
 }
 
 /// 终端配置参数
@@ -71,6 +77,30 @@ pub struct FileConfig {
     pub level: Level,
     /// 是否启用，默认不启用
     pub enable: bool,
+    /// 轮转方式, never/hourly/daily/size
+    #[serde(default)]
+    pub rotation: Rotation,
+    /// 按大小轮转时单个文件的最大大小, MB
+    #[serde(default = "FileConfig::default_max_file_size")]
+    pub max_file_size: u64,
+    /// 最多保留的历史文件数量, 0 表示不限制
+    #[serde(default)]
+    pub max_files: usize,
+    /// 历史文件的最大总大小, MB, 0 表示不限制
+    #[serde(default)]
+    pub max_total_size: u64,
+    /// 是否使用 gzip 压缩历史文件
+    #[serde(default)]
+    pub compress: bool,
+    /// 日志格式, text/json
+    #[serde(default)]
+    pub format: FileFormat,
+}
+
+impl FileConfig {
+    fn default_max_file_size() -> u64 {
+        100
+    }
 }
 
 impl Default for FileConfig {
@@ -80,10 +110,42 @@ impl Default for FileConfig {
             filename: "app.log".to_owned(),
             level: Level::Warn,
             enable: false,
+            rotation: Rotation::default(),
+            max_file_size: Self::default_max_file_size(),
+            max_files: 0,
+            max_total_size: 0,
+            compress: false,
+            format: FileFormat::default(),
         }
     }
 }
 
+/// 文件轮转方式
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+    /// 不轮转
+    Never,
+    /// 每小时
+    Hourly,
+    /// 每天
+    #[default]
+    Daily,
+    /// 按文件大小
+    Size,
+}
+
+/// 文件日志格式
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    /// 文本
+    #[default]
+    Text,
+    /// JSON, 每行一条日志
+    Json,
+}
+
 /// 数据库配置参数
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DbConfig {
@@ -97,6 +159,29 @@ pub struct DbConfig {
     pub enable: bool,
     /// 数据库配置
     pub options: Options,
+    /// 日志队列容量, 队列已满时丢弃日志并记录丢弃数量
+    #[serde(default = "DbConfig::default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// 批量写入的最大条数
+    #[serde(default = "DbConfig::default_batch_size")]
+    pub batch_size: usize,
+    /// 批量写入的最长间隔, 毫秒
+    #[serde(default = "DbConfig::default_flush_interval")]
+    pub flush_interval: u64,
+}
+
+impl DbConfig {
+    fn default_queue_capacity() -> usize {
+        10000
+    }
+
+    fn default_batch_size() -> usize {
+        100
+    }
+
+    fn default_flush_interval() -> u64 {
+        1000
+    }
 }
 
 impl Default for DbConfig {
@@ -107,12 +192,108 @@ impl Default for DbConfig {
             level: Level::Warn,
             enable: false,
             options: Options::default(),
+            queue_capacity: Self::default_queue_capacity(),
+            batch_size: Self::default_batch_size(),
+            flush_interval: Self::default_flush_interval(),
         }
     }
 }
 
+/// 脱敏配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactConfig {
+    /// 是否启用
+    pub enable: bool,
+    /// 需要脱敏的字段名称, 不区分大小写, 支持 `*` 通配符
+    pub keys: Vec<String>,
+    /// 需要脱敏的请求头名称, 不区分大小写
+    pub headers: Vec<String>,
+    /// 脱敏后的替换值
+    pub mask: String,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            keys: vec![
+                "*password*".to_owned(),
+                "*secret*".to_owned(),
+                "*token".to_owned(),
+                "api_key".to_owned(),
+                "sms_code".to_owned(),
+            ],
+            headers: vec![
+                "authorization".to_owned(),
+                "cookie".to_owned(),
+                "set-cookie".to_owned(),
+                "x-sr-token".to_owned(),
+                "x-sr-passphrase".to_owned(),
+            ],
+            mask: "******".to_owned(),
+        }
+    }
+}
+
+/// 日志保留配置
+///
+/// 作用于系统日志、WEB日志及API操作日志表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// 是否启用后台清理任务
+    pub enable: bool,
+    /// 清理任务执行间隔, 秒
+    pub interval: u64,
+    /// 每批删除的行数
+    pub batch_size: u64,
+    /// 批次之间的间隔, 毫秒, 避免长时间占用数据库
+    pub batch_interval: u64,
+    /// SQLite VACUUM 执行间隔, 秒, 0 表示不执行
+    pub vacuum_interval: u64,
+    /// 系统日志保留策略
+    pub system: RetentionPolicy,
+    /// WEB日志保留策略
+    pub web: RetentionPolicy,
+    /// API操作日志保留策略
+    pub api_operation: RetentionPolicy,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            interval: 3600,
+            batch_size: 1000,
+            batch_interval: 100,
+            vacuum_interval: 0,
+            system: RetentionPolicy::default(),
+            web: RetentionPolicy::default(),
+            api_operation: RetentionPolicy::default(),
+        }
+    }
+}
+
+/// 日志表保留策略, 满足任一条件的日志将被清理
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// 最长保留天数
+    pub max_age_days: Option<u32>,
+    /// 最多保留行数
+    pub max_rows: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// 是否未配置任何保留条件
+    pub fn is_empty(&self) -> bool {
+        self.max_age_days.is_none() && self.max_rows.is_none()
+    }
+}
+
 /// 日志级别
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, PartialOrd)]
 pub enum Level {
     #[serde(rename = "trace")]
     Trace,
@@ -120,18 +301,13 @@ pub enum Level {
     Debug,
     #[serde(rename = "info")]
     Info,
+    #[default]
     #[serde(rename = "warn")]
     Warn,
     #[serde(rename = "error")]
     Error,
 }
 
-impl Default for Level {
-    fn default() -> Self {
-        Self::Warn
-    }
-}
-
 // Level 别转换为 tracing::Level
 impl From<Level> for tracing::Level {
     fn from(level: Level) -> Self {
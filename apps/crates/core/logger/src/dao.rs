@@ -4,7 +4,7 @@ use database::PoolTrait;
 use entity::log::log_system;
 
 use sea_orm::ActiveValue::NotSet;
-use sea_orm::{ActiveModelTrait, DbErr};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait};
 
 pub struct Dao<DB: PoolTrait> {
     db: DB,
@@ -22,4 +22,18 @@ impl<DB: PoolTrait> Dao<DB> {
         active_model.id = NotSet;
         active_model.insert(self.db.db()).await
     }
+
+    /// 批量添加数据
+    pub async fn add_many(&self, data: Vec<log_system::Model>) -> Result<u64, DbErr> {
+        let count = data.len() as u64;
+        let active_models = data.into_iter().map(|v| {
+            let mut active_model: log_system::ActiveModel = v.into();
+            active_model.id = NotSet;
+            active_model
+        });
+        log_system::Entity::insert_many(active_models)
+            .exec_without_returning(self.db.db())
+            .await?;
+        Ok(count)
+    }
 }
@@ -0,0 +1,292 @@
+//! 日志过滤器
+//!
+//! 各日志输出层使用可重载的过滤器, 支持在运行时调整日志级别及按目标设置过滤指令,
+//! 如 `info,sea_orm=warn,service_hub::log=debug`.
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use tokio::{runtime::Handle, task::AbortHandle};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{EnvFilter, reload};
+
+use crate::{Error, RegistrySubscriber, config::Level};
+
+/// 全局日志过滤器
+static GLOBAL_FILTER: OnceLock<LoggerFilter> = OnceLock::new();
+
+/// 可重载的过滤器
+pub(crate) type ReloadFilter = reload::Layer<EnvFilter, RegistrySubscriber>;
+
+/// 日志输出层
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerKind {
+    /// 终端
+    Console,
+    /// Bunyan 终端
+    ConsoleBunyan,
+    /// 文件
+    File,
+    /// 数据库
+    Db,
+}
+
+/// 日志输出层的过滤器信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerFilter {
+    /// 日志输出层
+    pub layer: LayerKind,
+    /// 当前过滤指令
+    pub directives: String,
+    /// 启动时的过滤指令
+    pub default_directives: String,
+    /// 自动恢复时间
+    pub revert_at: Option<NaiveDateTime>,
+}
+
+struct Entry {
+    handle: reload::Handle<EnvFilter, RegistrySubscriber>,
+    info: LayerFilter,
+    /// 修改版本, 用于判断自动恢复前是否已被再次修改
+    version: u64,
+    /// 自动恢复定时器, 再次修改时取消
+    revert_task: Option<AbortHandle>,
+}
+
+/// 日志过滤器
+#[derive(Clone, Default)]
+pub struct LoggerFilter {
+    entries: Arc<Mutex<BTreeMap<LayerKind, Entry>>>,
+}
+
+impl LoggerFilter {
+    /// 获取全局日志过滤器, 日志未初始化时返回 `None`
+    pub fn global() -> Option<&'static LoggerFilter> {
+        GLOBAL_FILTER.get()
+    }
+
+    /// 设置为全局日志过滤器, 仅首次设置生效
+    pub(crate) fn set_global(self) {
+        let _ = GLOBAL_FILTER.set(self);
+    }
+
+    /// 创建输出层的可重载过滤器, 初始过滤指令为配置的日志级别
+    pub(crate) fn layer(&self, kind: LayerKind, level: &Level) -> ReloadFilter {
+        let directives = LevelFilter::from_level(level.clone().into()).to_string();
+        let (layer, handle) = reload::Layer::new(EnvFilter::new(&directives));
+        let entry = Entry {
+            handle,
+            info: LayerFilter {
+                layer: kind,
+                directives: directives.clone(),
+                default_directives: directives,
+                revert_at: None,
+            },
+            version: 0,
+            revert_task: None,
+        };
+        self.lock().insert(kind, entry);
+        layer
+    }
+
+    /// 获取已启用输出层的过滤器信息
+    pub fn filters(&self) -> Vec<LayerFilter> {
+        self.lock().values().map(|v| v.info.clone()).collect()
+    }
+
+    /// 更新输出层的过滤指令
+    ///
+    /// 指定 `revert_after` 时, 到期后恢复为更新前的过滤指令; 期间再次修改则取消恢复.
+    /// 自动恢复使用 Tokio 定时器, 需在 Tokio 运行时中调用.
+    pub fn update(
+        &self,
+        kind: LayerKind,
+        directives: &str,
+        revert_after: Option<Duration>,
+    ) -> Result<LayerFilter, Error> {
+        let filter = Self::parse(directives)?;
+        let runtime = match revert_after {
+            Some(_) => Some(Handle::try_current().map_err(Error::FilterRevertRuntime)?),
+            None => None,
+        };
+        let mut entries = self.lock();
+        let entry = entries
+            .get_mut(&kind)
+            .ok_or(Error::FilterLayerNotFound(kind))?;
+        entry.handle.reload(filter).map_err(Error::FilterReload)?;
+
+        let previous = std::mem::replace(&mut entry.info.directives, directives.to_string());
+        entry.version += 1;
+        entry.info.revert_at = revert_after.map(|v| {
+            Local::now().naive_local() + chrono::Duration::from_std(v).unwrap_or_default()
+        });
+        if let Some(task) = entry.revert_task.take() {
+            task.abort();
+        }
+        if let (Some(runtime), Some(revert_after)) = (runtime, revert_after) {
+            let filter = self.clone();
+            let version = entry.version;
+            let task = runtime.spawn(async move {
+                tokio::time::sleep(revert_after).await;
+                filter.revert(kind, &previous, version);
+            });
+            entry.revert_task = Some(task.abort_handle());
+        }
+        Ok(entry.info.clone())
+    }
+
+    /// 恢复输出层为启动时的过滤指令
+    pub fn reset(&self, kind: LayerKind) -> Result<LayerFilter, Error> {
+        let directives = self
+            .lock()
+            .get(&kind)
+            .map(|v| v.info.default_directives.clone())
+            .ok_or(Error::FilterLayerNotFound(kind))?;
+        self.update(kind, &directives, None)
+    }
+
+    /// 到期自动恢复
+    fn revert(&self, kind: LayerKind, directives: &str, version: u64) {
+        let mut entries = self.lock();
+        let Some(entry) = entries.get_mut(&kind) else {
+            return;
+        };
+        if entry.version != version {
+            return;
+        }
+        let Ok(filter) = Self::parse(directives) else {
+            return;
+        };
+        if entry.handle.reload(filter).is_err() {
+            return;
+        }
+        entry.info.directives = directives.to_string();
+        entry.info.revert_at = None;
+        entry.version += 1;
+        entry.revert_task = None;
+    }
+
+    /// 解析过滤指令
+    fn parse(directives: &str) -> Result<EnvFilter, Error> {
+        if directives.trim().is_empty() {
+            return Err(Error::FilterDirectivesEmpty);
+        }
+        EnvFilter::builder()
+            .parse(directives)
+            .map_err(Error::FilterParse)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<LayerKind, Entry>> {
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tracing::{Subscriber, debug, info, warn};
+    use tracing_error::ErrorLayer;
+    use tracing_subscriber::{Layer, Registry, layer::Context, layer::SubscriberExt};
+
+    use super::*;
+
+    /// 统计事件数量
+    #[derive(Clone, Default)]
+    struct Counter(Arc<AtomicUsize>);
+
+    impl<S: Subscriber> Layer<S> for Counter {
+        fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let filter = LoggerFilter::default();
+        let counter = Counter::default();
+        let layer = counter
+            .clone()
+            .with_filter(filter.layer(LayerKind::Console, &Level::Warn));
+        let subscriber = Registry::default().with(ErrorLayer::default()).with(layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        info!("skip");
+        warn!("warn");
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+        // 按目标调整日志级别
+        let info = filter
+            .update(LayerKind::Console, "warn,logger::filter=debug", None)
+            .expect("update filter");
+        assert_eq!(info.directives, "warn,logger::filter=debug");
+        assert_eq!(info.default_directives, "warn");
+        debug!("debug");
+        info!(target: "other", "skip");
+        assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+
+        // 非法指令及未启用的输出层
+        assert!(filter.update(LayerKind::Console, "", None).is_err());
+        assert!(
+            filter
+                .update(LayerKind::Console, "logger=verbose", None)
+                .is_err()
+        );
+        assert!(filter.update(LayerKind::Db, "info", None).is_err());
+
+        // 恢复启动时的过滤指令
+        let info = filter.reset(LayerKind::Console).expect("reset filter");
+        assert_eq!(info.directives, "warn");
+        debug!("skip");
+        assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_revert() {
+        let filter = LoggerFilter::default();
+        let _layer = filter.layer(LayerKind::File, &Level::Info);
+
+        let info = filter
+            .update(LayerKind::File, "debug", Some(Duration::from_millis(50)))
+            .expect("update filter");
+        assert!(info.revert_at.is_some());
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let info = &filter.filters()[0];
+        assert_eq!(info.directives, "info");
+        assert_eq!(info.revert_at, None);
+
+        // 到期前再次修改, 取消自动恢复
+        filter
+            .update(LayerKind::File, "debug", Some(Duration::from_millis(50)))
+            .expect("update filter");
+        filter
+            .update(LayerKind::File, "trace", None)
+            .expect("update filter");
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(filter.filters()[0].directives, "trace");
+
+        // 再次设置自动恢复时取消原定时器, 按新的期限恢复
+        filter
+            .update(LayerKind::File, "debug", Some(Duration::from_millis(50)))
+            .expect("update filter");
+        filter
+            .update(LayerKind::File, "warn", Some(Duration::from_secs(3600)))
+            .expect("update filter");
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(filter.filters()[0].directives, "warn");
+
+        // 运行时外不能设置自动恢复
+        let result = std::thread::spawn(move || {
+            filter.update(LayerKind::File, "debug", Some(Duration::from_millis(50)))
+        })
+        .join()
+        .expect("join");
+        assert!(matches!(result, Err(Error::FilterRevertRuntime(_))));
+    }
+}
@@ -1,15 +1,17 @@
 //!输出到控制台
-use crate::config::ConsoleConfig;
+use crate::redact::{RedactMakeWriter, Redactor};
 use crate::utils::time::local_time;
 
 use tracing_subscriber::{
-    fmt::{self, writer::MakeWriterExt},
+    fmt,
     layer::{Layer, SubscriberExt},
     registry::LookupSpan,
 };
 
 /// 输出到控制台中
-pub fn layer<S>(config: &ConsoleConfig) -> Box<dyn Layer<S> + Send + Sync + 'static>
+///
+/// 日志级别由可重载的过滤器控制
+pub fn layer<S>(redactor: Redactor) -> Box<dyn Layer<S> + Send + Sync + 'static>
 where
     S: SubscriberExt,
     S: for<'a> LookupSpan<'a>,
@@ -28,7 +30,7 @@ where
         .with_timer(timer)
         .with_thread_names(true)
         .log_internal_errors(true)
-        .with_writer(std::io::stderr.with_max_level(config.level.clone().into()));
+        .with_writer(RedactMakeWriter::new(std::io::stderr, redactor));
     Box::new(layer)
 }
 
@@ -36,17 +38,11 @@ where
 mod tests {
     use super::*;
 
-    use crate::config;
-
     use tracing::{debug, error, info, trace, warn};
 
     #[test]
     fn test_layer() {
-        let conf = ConsoleConfig {
-            level: config::Level::Debug,
-            enable: true,
-        };
-        let layer = layer(&conf);
+        let layer = layer(Redactor::default());
         let subscriber = tracing_subscriber::registry().with(layer);
         let _guard = tracing::subscriber::set_default(subscriber);
 
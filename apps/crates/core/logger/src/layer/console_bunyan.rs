@@ -1,15 +1,15 @@
 //! 输出到控制台
 //! 该层专门涉及使用Bunyan格式格式化信息。
 //! 它依赖于上游的JsonStorageLayer来访问连接到每个跨度的字段。
-use crate::config::ConsoleBunyanConfig;
+use crate::redact::{RedactMakeWriter, Redactor};
 
 use tracing_bunyan_formatter::BunyanFormattingLayer;
-use tracing_subscriber::{
-    Layer, fmt::writer::MakeWriterExt, layer::SubscriberExt, registry::LookupSpan,
-};
+use tracing_subscriber::{Layer, layer::SubscriberExt, registry::LookupSpan};
 
 /// 输出到控制台中
-pub fn layer<S>(config: &ConsoleBunyanConfig) -> Box<dyn Layer<S> + Send + Sync + 'static>
+///
+/// 日志级别由可重载的过滤器控制
+pub fn layer<S>(redactor: Redactor) -> Box<dyn Layer<S> + Send + Sync + 'static>
 where
     S: SubscriberExt,
     S: for<'a> LookupSpan<'a>,
@@ -17,7 +17,7 @@ where
     // Shared configuration regardless of where logs are output to.
     let layer = BunyanFormattingLayer::new(
         "console_bunyan_layer".into(),
-        std::io::stdout.with_max_level(config.level.clone().into()),
+        RedactMakeWriter::new(std::io::stdout, redactor),
     );
     Box::new(layer)
 }
@@ -26,8 +26,6 @@ where
 mod tests {
     use super::*;
 
-    use crate::config;
-
     use tracing::{
         Level, debug, debug_span, error, event, info, info_span, subscriber::DefaultGuard, trace,
         warn,
@@ -35,11 +33,7 @@ mod tests {
 
     /// 注册日志订阅器
     fn setup() -> DefaultGuard {
-        let conf = ConsoleBunyanConfig {
-            level: config::Level::Debug,
-            enable: true,
-        };
-        let layer = layer(&conf);
+        let layer = layer(Redactor::default());
         let subscriber = tracing_subscriber::registry().with(layer);
         tracing::subscriber::set_default(subscriber)
     }
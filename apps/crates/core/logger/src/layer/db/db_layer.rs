@@ -4,42 +4,30 @@ use std::sync::Arc;
 
 use super::{
     layer::LayerHandler,
-    writer::{DbWriter, GuardWriter},
+    writer::{DbWriter, DbWriterGuard},
 };
 use crate::config::DbConfig;
+use crate::redact::Redactor;
 
 use tracing::Subscriber;
-use tracing_appender::non_blocking;
-use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{Layer, registry::LookupSpan};
 
 /// 输出到数据库中
 pub fn non_blocking_layer<S>(
     config: DbConfig,
-) -> (Box<dyn Layer<S> + Send + Sync + 'static>, WorkerGuard)
+    redactor: Redactor,
+) -> (Box<dyn Layer<S> + Send + Sync + 'static>, DbWriterGuard)
 where
     S: Subscriber,
     for<'lookup> S: LookupSpan<'lookup>,
 {
-    let config1 = config.clone();
-    let writer = std::thread::spawn(move || {
-        let rt = tokio::runtime::Runtime::new().unwrap();
-        rt.block_on(async {
-            let w = DbWriter::new(config1).await;
-            Arc::new(w)
-        })
-    })
-    .join()
-    .unwrap();
-
-    let layer = LayerHandler::new(writer.clone());
-
-    // 日志循环处理
-    writer.loop_data();
-
-    let guard_writer = GuardWriter(writer);
-    let (_non_blocking, guard) = non_blocking(guard_writer);
-    (Box::new(layer), guard)
+    let writer = Arc::new(DbWriter::new(config));
+    // 注册全局写入监控, 用于健康检查及指标统计
+    writer.monitor().set_global();
+    let layer = LayerHandler::new(writer.clone(), redactor);
+
+    // 守卫释放时写入队列中剩余的日志
+    (Box::new(layer), DbWriterGuard::new(writer))
 }
 
 #[cfg(test)]
@@ -58,7 +46,7 @@ mod tests {
     use tracing_subscriber::layer::SubscriberExt;
 
     /// 注册日志订阅器
-    fn setup() -> (DefaultGuard, WorkerGuard) {
+    fn setup() -> (DefaultGuard, DbWriterGuard) {
         let conf = DbConfig {
             // server/core/logger/data.dat
             address: "sqlite://./data.dat?mode=rwc".to_string(),
@@ -67,7 +55,7 @@ mod tests {
             ..Default::default()
         };
 
-        let (layer, guard) = non_blocking_layer(conf);
+        let (layer, guard) = non_blocking_layer(conf, Redactor::default());
         let subscriber = tracing_subscriber::registry().with(layer);
         let trace_guard = tracing::subscriber::set_default(subscriber);
 
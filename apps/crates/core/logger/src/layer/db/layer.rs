@@ -3,21 +3,47 @@
 use std::sync::Arc;
 
 use super::{
-    visitor::{Storage, StorageVisitor},
+    visitor::{Identity, Storage, StorageVisitor},
     writer::DbWriter,
 };
+use crate::redact::Redactor;
 
 use tracing::{Event, Metadata, span};
-use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+use tracing_subscriber::{
+    Layer,
+    layer::Context,
+    registry::{LookupSpan, SpanRef},
+};
 
 /// 日志处理 Layer
 pub struct LayerHandler {
     writer: Arc<DbWriter>,
+    redactor: Redactor,
 }
 
 impl LayerHandler {
-    pub fn new(writer: Arc<DbWriter>) -> Self {
-        LayerHandler { writer }
+    pub fn new(writer: Arc<DbWriter>, redactor: Redactor) -> Self {
+        LayerHandler { writer, redactor }
+    }
+
+    /// 从当前 span 及其父级 span 中获取请求标识
+    fn identity<S>(span: Option<SpanRef<'_, S>>) -> Identity
+    where
+        S: for<'lookup> LookupSpan<'lookup>,
+    {
+        let mut identity = Identity::default();
+        let Some(span) = span else {
+            return identity;
+        };
+        for span in span.scope() {
+            if let Some(storage) = span.extensions().get::<Storage>() {
+                identity.merge(storage);
+            }
+            if identity.is_complete() {
+                break;
+            }
+        }
+        identity
     }
 }
 
@@ -55,21 +81,26 @@ where
         };
 
         // 基于 field 值来构建我们自己的 JSON 对象
-        let mut visitor = StorageVisitor::default();
+        let mut visitor = StorageVisitor::default().with_redactor(self.redactor.clone());
         attrs.record(&mut visitor);
 
-        // 获取扩展，用于存储我们的 span 数据
-        let mut ext = span.extensions_mut();
-        // 存储至 span
-        ext.insert::<Storage>(visitor.storage());
+        // 获取扩展，用于存储我们的 span 数据, 存储至 span
+        span.extensions_mut().insert::<Storage>(visitor.storage());
 
         // 输出日志
         let span_id = Some(id.into_u64());
         let span_pid = span.parent().map(|v| v.id().into_u64());
         let metadata = span.metadata();
-
-        self.writer
-            .emit(span_pid, span_id, metadata, visitor.storage(), "new_span");
+        let identity = Self::identity(Some(span));
+
+        self.writer.emit(
+            span_pid,
+            span_id,
+            metadata,
+            visitor.storage(),
+            identity,
+            "new_span",
+        );
     }
 
     /// 事件用于处理每次记录 span 的值，也就是每次调用 record! 宏或其简写形式时触发的事件。
@@ -88,25 +119,31 @@ where
             None => return,
         };
 
-        // 获取数据的可变引用，该数据是在 on_new_span 中创建的
-        let mut ext = span.extensions_mut();
-        // 获取自定义存储器
-        let storage = match ext.get_mut::<Storage>() {
-            Some(v) => v,
-            None => return,
+        let storage = {
+            // 获取数据的可变引用，该数据是在 on_new_span 中创建的
+            let mut ext = span.extensions_mut();
+            // 获取自定义存储器
+            let storage = match ext.get_mut::<Storage>() {
+                Some(v) => v,
+                None => return,
+            };
+
+            // 使用访问器, 并将记录的数据写回 span
+            let mut visitor =
+                StorageVisitor::from(storage.clone()).with_redactor(self.redactor.clone());
+            values.record(&mut visitor);
+            *storage = visitor.storage();
+            storage.clone()
         };
 
-        // 使用访问器
-        let mut visitor = StorageVisitor::from(storage.clone());
-        values.record(&mut visitor);
-
         // 输出日志
         let span_id = Some(id.into_u64());
         let span_pid = span.parent().map(|v| v.id().into_u64());
         let metadata = span.metadata();
+        let identity = Self::identity(Some(span));
 
         self.writer
-            .emit(span_pid, span_id, metadata, storage.clone(), "record");
+            .emit(span_pid, span_id, metadata, storage, identity, "record");
     }
 
     /// 用于判断是否启用某个事件
@@ -119,17 +156,24 @@ where
     /// 在这个方法中，您可以获取事件的元数据和字段，这些元数据和字段是在创建事件时指定的，
     /// 例如级别、目标、消息等。
     /// 您可以使用这些信息来记录或过滤事件，或者将它们存储在事件的扩展中以供后续使用。
-    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
         // 创建新的访问器
-        let mut visitor = StorageVisitor::default();
+        let mut visitor = StorageVisitor::default().with_redactor(self.redactor.clone());
         event.record(&mut visitor);
 
         // 输出日志
         let span_pid = event.parent().map(|v| v.into_u64());
         let metadata = event.metadata();
-
-        self.writer
-            .emit(span_pid, None, metadata, visitor.storage(), "event");
+        let identity = Self::identity(ctx.event_span(event));
+
+        self.writer.emit(
+            span_pid,
+            None,
+            metadata,
+            visitor.storage(),
+            identity,
+            "event",
+        );
     }
 
     /// 用于处理每次进入 span 的事件，也就是每次调用 span::Span::enter 方法
@@ -141,19 +185,18 @@ where
             None => return,
         };
 
-        let mut ext = span.extensions_mut();
-        let storage = match ext.get_mut::<Storage>() {
-            Some(v) => v,
-            None => return,
+        let Some(storage) = span.extensions().get::<Storage>().cloned() else {
+            return;
         };
 
         // 输出日志
         let span_id = Some(id.into_u64());
         let span_pid = span.parent().map(|v| v.id().into_u64());
         let metadata = span.metadata();
+        let identity = Self::identity(Some(span));
 
         self.writer
-            .emit(span_pid, span_id, metadata, storage.clone(), "enter");
+            .emit(span_pid, span_id, metadata, storage, identity, "enter");
     }
 
     /// 用于处理每个关闭 span 的事件，也就是每次调用 span::Span::close 方法
@@ -165,19 +208,18 @@ where
             None => return,
         };
 
-        let mut ext = span.extensions_mut();
-        let storage = match ext.get_mut::<Storage>() {
-            Some(v) => v,
-            None => return,
+        let Some(storage) = span.extensions().get::<Storage>().cloned() else {
+            return;
         };
 
         // 输出日志
         let span_id = Some(id.into_u64());
         let span_pid = span.parent().map(|v| v.id().into_u64());
         let metadata = span.metadata();
+        let identity = Self::identity(Some(span));
 
         self.writer
-            .emit(span_pid, span_id, metadata, storage.clone(), "close");
+            .emit(span_pid, span_id, metadata, storage, identity, "close");
     }
 
     /// 用于处理每次退出 span 的事件，也就是每次调用 span::Span::exit 方法
@@ -189,18 +231,17 @@ where
             None => return,
         };
 
-        let mut ext = span.extensions_mut();
-        let storage = match ext.get_mut::<Storage>() {
-            Some(v) => v,
-            None => return,
+        let Some(storage) = span.extensions().get::<Storage>().cloned() else {
+            return;
         };
 
         // 输出日志
         let span_id = Some(id.into_u64());
         let span_pid = span.parent().map(|v| v.id().into_u64());
         let metadata = span.metadata();
+        let identity = Self::identity(Some(span));
 
         self.writer
-            .emit(span_pid, span_id, metadata, storage.clone(), "exit");
+            .emit(span_pid, span_id, metadata, storage, identity, "exit");
     }
 }
@@ -6,6 +6,8 @@ use serde_json::Value;
 
 use err_code::Error;
 
+use crate::redact::Redactor;
+
 /// 日志字段和值
 #[derive(Debug, Default, Clone)]
 pub struct StorageFiled {
@@ -64,12 +66,57 @@ impl Storage {
     pub fn code_msg(&self) -> Option<String> {
         self.code_msg.clone()
     }
+    /// 获取字段值
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.metadata.get(name)
+    }
+}
+
+/// 请求标识
+///
+/// 来源于 `ContextLayer` 创建的请求 span, 用于关联同一请求的系统日志与API操作日志
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Identity {
+    pub request_id: Option<String>,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+}
+
+impl Identity {
+    /// 从 span 存储器中补充缺失的标识
+    pub fn merge(&mut self, storage: &Storage) {
+        if self.request_id.is_none() {
+            self.request_id = storage
+                .get("request_id")
+                .and_then(Value::as_str)
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string());
+        }
+        if self.user_id.is_none() {
+            self.user_id = storage
+                .get("user_id")
+                .and_then(Value::as_i64)
+                .map(|v| v as i32);
+        }
+        if self.username.is_none() {
+            self.username = storage
+                .get("username")
+                .and_then(Value::as_str)
+                .map(|v| v.to_string());
+        }
+    }
+
+    /// 标识是否已完整
+    pub fn is_complete(&self) -> bool {
+        self.request_id.is_some() && self.user_id.is_some() && self.username.is_some()
+    }
 }
 
 /// 自定义访问者
 #[derive(Debug, Default, Clone)]
 pub struct StorageVisitor {
     storage: Storage,
+    redactor: Redactor,
 }
 
 impl StorageVisitor {
@@ -80,58 +127,59 @@ impl StorageVisitor {
 
     /// 从存储器创建访问者
     pub fn from(storage: Storage) -> Self {
-        StorageVisitor { storage }
+        StorageVisitor {
+            storage,
+            ..Default::default()
+        }
+    }
+
+    /// 设置脱敏器
+    pub fn with_redactor(mut self, redactor: Redactor) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// 添加脱敏后的字段
+    fn add_filed(&mut self, name: &str, mut value: Value) {
+        if self.redactor.is_sensitive_key(name) {
+            value = Value::String(self.redactor.mask().to_owned());
+        } else {
+            self.redactor.redact_value(&mut value);
+        }
+        self.storage.add_filed(StorageFiled {
+            name: name.to_string(),
+            value,
+        });
     }
 }
 
 impl tracing::field::Visit for StorageVisitor {
     fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_i128(&mut self, field: &tracing::field::Field, value: i128) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_u128(&mut self, field: &tracing::field::Field, value: u128) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value),
-        });
+        self.add_filed(field.name(), serde_json::json!(value));
     }
 
     fn record_error(
@@ -144,16 +192,55 @@ impl tracing::field::Visit for StorageVisitor {
             return;
         }
 
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(value.to_string()),
-        });
+        self.add_filed(field.name(), serde_json::json!(value.to_string()));
     }
 
     fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
-        self.storage.add_filed(StorageFiled {
-            name: field.name().to_string(),
-            value: serde_json::json!(format!("{:?}", value)),
+        self.add_filed(field.name(), serde_json::json!(format!("{:?}", value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::info;
+    use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+    use super::*;
+
+    /// 记录事件字段的测试 Layer
+    struct CaptureLayer(Arc<Mutex<Vec<Storage>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for CaptureLayer {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut visitor = StorageVisitor::default().with_redactor(Redactor::default());
+            event.record(&mut visitor);
+            self.0.lock().unwrap().push(visitor.storage());
+        }
+    }
+
+    #[test]
+    fn test_redact_fields() {
+        let storages = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(CaptureLayer(storages.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            info!(
+                password = "123456",
+                username = "user01",
+                "login with {}",
+                "password=123456"
+            );
         });
+
+        let storages = storages.lock().unwrap();
+        let storage = &storages[0];
+        assert_eq!(storage.metadata["password"], "******");
+        assert_eq!(storage.metadata["username"], "user01");
+        assert_eq!(storage.message(), r#""login with password=******""#);
     }
 }
@@ -1,81 +1,199 @@
 //! 日志写入
-use std::{ops::Deref, sync::Arc};
+//!
+//! 日志经有界队列发送到后台写入线程, 按条数或时间阈值批量写入数据库;
+//! 队列已满时丢弃日志并记录丢弃数量, 关闭时写入队列中剩余的日志.
+use std::{
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
 
-use super::visitor::Storage;
+use super::visitor::{Identity, Storage};
 use crate::config::DbConfig;
 use crate::dao::Dao;
+use crate::tail;
 
 use database::{Pool, PoolTrait};
 use entity::log::log_system;
 
 use chrono::Local;
-use tokio::sync::{
-    Mutex,
-    mpsc::{self, Receiver, Sender},
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{
+        mpsc::{self, Receiver, Sender, WeakSender, error::TrySendError},
+        oneshot,
+    },
+    time::{MissedTickBehavior, interval},
 };
 
-use tracing::{Metadata, info};
+use tracing::Metadata;
 use tracing_error::SpanTraceStatus;
 
+/// 全局数据库日志写入监控
+static GLOBAL_MONITOR: OnceLock<DbWriterMonitor> = OnceLock::new();
+
 pub struct DbWriter {
-    config: DbConfig,
-    db: Pool,
-    dao: Arc<Dao<Pool>>,
-    /// 通道发送者, 可以有多个发送者
+    /// 有界队列发送者, 可在任意线程中发送
     tx: Sender<log_system::Model>,
-    rx: Arc<Mutex<Receiver<log_system::Model>>>,
+    /// 队列已满时丢弃的日志数量, 写入线程上报后清零
+    dropped: Arc<AtomicU64>,
+    /// 累计丢弃的日志数量
+    dropped_total: Arc<AtomicU64>,
+    /// 后台写入线程是否运行中
+    running: Arc<AtomicBool>,
+    /// 后台写入线程
+    worker: Mutex<Option<Worker>>,
+}
+
+/// 后台写入线程句柄
+struct Worker {
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
 }
 
 impl DbWriter {
-    pub async fn new(config: DbConfig) -> Self {
-        // 初始化数据库
-        let db = database::Pool::new(config.address.clone(), config.options.clone())
-            .await
-            .expect("初始化数据库失败");
-        let dao = Dao::new(db.clone());
+    /// 连接数据库并启动后台写入线程
+    pub fn new(config: DbConfig) -> Self {
+        let (tx, rx) = mpsc::channel::<log_system::Model>(config.queue_capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+
+        let running = Arc::new(AtomicBool::new(false));
 
-        let (tx, rx) = mpsc::channel::<log_system::Model>(1000);
+        let worker_dropped = dropped.clone();
+        let worker_running = running.clone();
+        let handle = thread::Builder::new()
+            .name("db-log-writer".to_owned())
+            .spawn(move || {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("初始化日志写入运行时失败");
+                rt.block_on(async move {
+                    // 初始化数据库
+                    let db = match Pool::new(config.address.clone(), config.options.clone()).await {
+                        Ok(v) => v,
+                        Err(err) => {
+                            let _ = ready_tx.send(Err(err));
+                            return;
+                        }
+                    };
+                    worker_running.store(true, Ordering::Relaxed);
+                    let _ = ready_tx.send(Ok(()));
+
+                    let batch = BatchWriter {
+                        dao: Dao::new(db.clone()),
+                        batch_size: config.batch_size.max(1),
+                        flush_interval: Duration::from_millis(config.flush_interval.max(1)),
+                        dropped: worker_dropped,
+                    };
+                    batch.run(rx, shutdown_rx).await;
+                    worker_running.store(false, Ordering::Relaxed);
+                    _ = db.close().await;
+                })
+            })
+            .expect("启动日志写入线程失败");
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => panic!("初始化数据库失败, err: {err:#?}"),
+            Err(_) => panic!("初始化数据库失败"),
+        }
 
         DbWriter {
-            config,
-            db,
-            dao: Arc::new(dao),
             tx,
-            rx: Arc::new(Mutex::new(rx)),
+            dropped,
+            dropped_total: Arc::new(AtomicU64::new(0)),
+            running,
+            worker: Mutex::new(Some(Worker {
+                shutdown: shutdown_tx,
+                handle,
+            })),
         }
     }
 
-    /// 写入日志
-    pub async fn _write(&self, data: log_system::Model) {
-        if let Err(err) = self.dao.add(data.clone()).await {
-            println!("log add filed, data: {:?} \nerr: {:?}", data, err);
+    /// 关闭写入线程, 等待队列中剩余的日志写入数据库
+    pub fn close(&self) {
+        let worker = match self.worker.lock() {
+            Ok(mut v) => v.take(),
+            Err(err) => err.into_inner().take(),
+        };
+        let Some(worker) = worker else {
+            return;
+        };
+        let _ = worker.shutdown.send(());
+        if worker.handle.join().is_err() {
+            eprintln!("db log writer thread panicked");
         }
     }
 
-    /// 关闭数据库和数据通道
-    pub fn close(&self) {
-        let tx = self.tx.clone();
-        let db = self.db.clone();
-        std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async move {
-                tx.closed().await;
-                _ = db.close().await;
-                info!("close database log channel");
-            })
-        })
-        .join()
-        .unwrap();
+    /// 获取写入状态监控
+    pub fn monitor(&self) -> DbWriterMonitor {
+        DbWriterMonitor {
+            tx: self.tx.downgrade(),
+            dropped_total: self.dropped_total.clone(),
+            running: self.running.clone(),
+        }
     }
 }
 
-impl DbWriter {
-    /// 过滤日志级别
-    fn filter_level(&self, level: &tracing::Level) -> bool {
-        let max_level: tracing::Level = self.config.level.clone().into();
-        max_level.lt(level)
+/// 数据库日志写入状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbWriterStatus {
+    /// 后台写入线程是否运行中
+    pub running: bool,
+    /// 队列容量
+    pub capacity: usize,
+    /// 队列中等待写入的日志数量
+    pub queued: usize,
+    /// 累计丢弃的日志数量
+    pub dropped: u64,
+}
+
+/// 数据库日志写入监控
+#[derive(Clone)]
+pub struct DbWriterMonitor {
+    tx: WeakSender<log_system::Model>,
+    dropped_total: Arc<AtomicU64>,
+    running: Arc<AtomicBool>,
+}
+
+impl DbWriterMonitor {
+    /// 获取全局数据库日志写入监控, 未启用数据库日志时返回 `None`
+    pub fn global() -> Option<&'static DbWriterMonitor> {
+        GLOBAL_MONITOR.get()
     }
 
+    /// 设置为全局数据库日志写入监控, 仅首次设置生效
+    pub(crate) fn set_global(self) {
+        let _ = GLOBAL_MONITOR.set(self);
+    }
+
+    /// 获取当前写入状态
+    pub fn status(&self) -> DbWriterStatus {
+        let dropped = self.dropped_total.load(Ordering::Relaxed);
+        let Some(tx) = self.tx.upgrade() else {
+            return DbWriterStatus {
+                running: false,
+                capacity: 0,
+                queued: 0,
+                dropped,
+            };
+        };
+        DbWriterStatus {
+            running: self.running.load(Ordering::Relaxed) && !tx.is_closed(),
+            capacity: tx.max_capacity(),
+            queued: tx.max_capacity() - tx.capacity(),
+            dropped,
+        }
+    }
+}
+
+impl DbWriter {
     /// 过滤target日志数据
     ///
     /// 当日志数据库分离后, 将不会再次产生循环日志，因此可进行选择性的忽略日志
@@ -99,12 +217,9 @@ impl DbWriter {
         span_id: Option<u64>,
         metadata: &Metadata,
         storage: Storage,
+        identity: Identity,
         kind: &str,
     ) -> Option<log_system::Model> {
-        // 日志级别过滤
-        if self.filter_level(metadata.level()) {
-            return None;
-        }
         // 过滤target日志数据
         if self.filter_target(metadata.target()) {
             return None;
@@ -118,8 +233,9 @@ impl DbWriter {
         }
 
         let output = log_system::Model {
-            // user_id: todo!(),
-            // username: todo!(),
+            user_id: identity.user_id,
+            username: identity.username,
+            request_id: identity.request_id,
             span_pid: span_pid.map(|v| v as u32),
             span_id: span_id.map(|v| v as u32),
             module_path: metadata.module_path().map(|v| v.to_string()),
@@ -143,68 +259,320 @@ impl DbWriter {
         Some(output)
     }
 
-    /// 发送日志数据到通道
+    /// 发送日志数据到队列
+    ///
+    /// 不依赖 tokio 运行时, 队列已满时丢弃日志并计数
     pub fn emit(
         &self,
         span_pid: Option<u64>,
         span_id: Option<u64>,
         metadata: &Metadata,
         storage: Storage,
+        identity: Identity,
         kind: &str,
     ) {
-        let output = match self.output_log(span_pid, span_id, metadata, storage, kind) {
+        let output = match self.output_log(span_pid, span_id, metadata, storage, identity, kind) {
             Some(v) => v,
             None => return,
         };
-        if self.tx.is_closed() {
-            return;
-        }
-        let tx = self.tx.clone();
+        // 实时日志订阅
+        tail::publish(&output);
 
-        // 尝试获取当前的 tokio 运行时的句柄。
-        // 如果能够获取到句柄，那么我们就可以认为当前是在 tokio 运行时的上下文中。
-        if tokio::runtime::Handle::try_current().is_err() {
-            return;
-        }
-        tokio::spawn(async move {
-            if let Err(err) = tx.send(output).await {
-                println!("receiver closed, err: {:#?}", err);
+        match self.tx.try_send(output) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                self.dropped_total.fetch_add(1, Ordering::Relaxed);
             }
-        });
-    }
-
-    /// 循环接收数据入库
-    pub fn loop_data(&self) {
-        let rx = self.rx.clone();
-        let dao = self.dao.clone();
-        tokio::spawn(async move {
-            let mut rx = rx.lock().await;
-            while let Some(output) = rx.recv().await {
-                if let Err(err) = dao.add(output.clone()).await {
-                    println!("log add filed, data: {:?} \nerr: {:?}", output, err);
+            // 写入线程已关闭
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+/// 批量写入
+struct BatchWriter {
+    dao: Dao<Pool>,
+    batch_size: usize,
+    flush_interval: Duration,
+    dropped: Arc<AtomicU64>,
+}
+
+impl BatchWriter {
+    /// 循环接收日志, 达到条数或时间阈值时批量写入
+    async fn run(&self, mut rx: Receiver<log_system::Model>, mut shutdown: oneshot::Receiver<()>) {
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut ticker = interval(self.flush_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            let limit = self.batch_size - batch.len();
+            tokio::select! {
+                count = rx.recv_many(&mut batch, limit) => {
+                    // 所有发送者均已关闭
+                    if count == 0 {
+                        break;
+                    }
+                    if batch.len() >= self.batch_size {
+                        self.flush(&mut batch).await;
+                    }
+                }
+                _ = ticker.tick() => {
+                    self.flush(&mut batch).await;
+                }
+                _ = &mut shutdown => {
+                    // 停止接收新的日志, 写入队列中剩余的日志
+                    rx.close();
+                    while let Some(output) = rx.recv().await {
+                        batch.push(output);
+                        if batch.len() >= self.batch_size {
+                            self.flush(&mut batch).await;
+                        }
+                    }
+                    break;
                 }
             }
-        });
+        }
+        self.flush(&mut batch).await;
+    }
+
+    /// 写入当前批次, 并记录丢弃的日志数量
+    async fn flush(&self, batch: &mut Vec<log_system::Model>) {
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            batch.push(Self::dropped_log(dropped));
+        }
+        if batch.is_empty() {
+            return;
+        }
+
+        let data = std::mem::replace(batch, Vec::with_capacity(self.batch_size));
+        let count = data.len();
+        if let Err(err) = self.dao.add_many(data).await {
+            eprintln!("log batch add failed, count: {count}, err: {err:?}");
+        }
+    }
+
+    /// 丢弃日志数量的告警日志
+    fn dropped_log(count: u64) -> log_system::Model {
+        log_system::Model {
+            target: module_path!().to_string(),
+            module_path: Some(module_path!().to_string()),
+            level: tracing::Level::WARN.to_string(),
+            kind: "event".to_string(),
+            is_event: true,
+            message: Some(format!("日志队列已满, 丢弃日志数量: {count}")),
+            created_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        }
     }
 }
 
-pub struct GuardWriter(pub Arc<DbWriter>);
+/// 数据库日志写入守卫
+///
+/// 释放时关闭写入线程, 并等待队列中剩余的日志写入数据库.
+#[must_use]
+pub struct DbWriterGuard(Arc<DbWriter>);
 
-impl std::io::Write for GuardWriter {
-    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-        Ok(0)
+impl DbWriterGuard {
+    pub fn new(writer: Arc<DbWriter>) -> Self {
+        DbWriterGuard(writer)
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
+impl std::fmt::Debug for DbWriterGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DbWriterGuard").finish_non_exhaustive()
+    }
+}
+
+impl Drop for DbWriterGuard {
+    fn drop(&mut self) {
         self.0.close();
-        Ok(())
     }
 }
 
-impl Deref for GuardWriter {
-    type Target = DbWriter;
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ConnectionTrait, EntityTrait, QueryOrder, Schema};
+    use tracing::{field::Empty, info, info_span};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::{config, layer::db::layer::LayerHandler, redact::Redactor};
+    use entity::log::LogSystemEntity;
+
+    /// 创建包含日志表的临时数据库
+    async fn database(dir: &std::path::Path) -> (DbConfig, Pool) {
+        let address = format!("sqlite://{}/log.dat?mode=rwc", dir.display());
+        let db = Pool::new(address.clone(), Default::default())
+            .await
+            .expect("connect db");
+        let backend = db.db().get_database_backend();
+        let stmt = Schema::new(backend).create_table_from_entity(LogSystemEntity);
+        db.db()
+            .execute(backend.build(&stmt))
+            .await
+            .expect("create table");
+
+        let conf = DbConfig {
+            address,
+            level: config::Level::Info,
+            enable: true,
+            batch_size: 2,
+            flush_interval: 60_000,
+            ..Default::default()
+        };
+        (conf, db)
+    }
+
+    async fn logs(db: &Pool) -> Vec<log_system::Model> {
+        LogSystemEntity::find()
+            .order_by_asc(log_system::Column::Id)
+            .all(db.db())
+            .await
+            .expect("query logs")
+    }
+
+    #[tokio::test]
+    async fn test_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let (conf, db) = database(dir.path()).await;
+        let writer = Arc::new(DbWriter::new(conf));
+        let layer = LayerHandler::new(writer.clone(), Redactor::default());
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        {
+            let _guard = tracing::subscriber::set_default(subscriber);
+            let span = info_span!(
+                "context",
+                request_id = "req-01",
+                user_id = Empty,
+                username = Empty
+            );
+            let _entered = span.enter();
+            span.record("user_id", 7);
+            span.record("username", "user07");
+            info_span!("inner").in_scope(|| info!("inner event"));
+        }
+        writer.close();
+
+        let events = logs(&db)
+            .await
+            .into_iter()
+            .filter(|v| v.is_event)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].request_id.as_deref(), Some("req-01"));
+        assert_eq!(events[0].user_id, Some(7));
+        assert_eq!(events[0].username.as_deref(), Some("user07"));
+    }
+
+    #[test]
+    fn test_batch_from_threads() {
+        let dir = tempfile::tempdir().unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (conf, db) = rt.block_on(database(dir.path()));
+
+        // 在非 tokio 线程中写入日志, 关闭时写入剩余日志
+        let writer = Arc::new(DbWriter::new(conf));
+        assert!(writer.monitor().status().running);
+        let handles = (0..4)
+            .map(|i| {
+                let writer = writer.clone();
+                thread::spawn(move || {
+                    for j in 0..5 {
+                        let output = log_system::Model {
+                            target: "batch_test".to_string(),
+                            message: Some(format!("{i}-{j}")),
+                            created_at: Some(Local::now().naive_local()),
+                            ..Default::default()
+                        };
+                        writer.tx.try_send(output).expect("send log");
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        handles.into_iter().for_each(|v| v.join().unwrap());
+        writer.close();
+        writer.close();
+        assert!(!writer.monitor().status().running);
+
+        let logs = rt.block_on(logs(&db));
+        assert_eq!(logs.len(), 20);
+    }
+
+    #[test]
+    fn test_guard_drain() {
+        let dir = tempfile::tempdir().unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (mut conf, db) = rt.block_on(database(dir.path()));
+        conf.batch_size = 1000;
+
+        // 未达到批量阈值的日志在守卫释放时写入
+        let writer = Arc::new(DbWriter::new(conf));
+        let guard = DbWriterGuard::new(writer.clone());
+        for i in 0..50 {
+            let output = log_system::Model {
+                target: "guard_test".to_string(),
+                message: Some(i.to_string()),
+                created_at: Some(Local::now().naive_local()),
+                ..Default::default()
+            };
+            writer.tx.try_send(output).expect("send log");
+        }
+        drop(guard);
+        assert!(!writer.monitor().status().running);
+
+        let logs = rt.block_on(logs(&db));
+        assert_eq!(logs.len(), 50);
+    }
+
+    #[test]
+    fn test_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (_, db) = rt.block_on(database(dir.path()));
+
+        // 队列已满时丢弃日志, 写入时记录丢弃数量
+        let (tx, _rx) = mpsc::channel(1);
+        let writer = DbWriter {
+            tx,
+            dropped: Arc::new(AtomicU64::new(0)),
+            dropped_total: Arc::new(AtomicU64::new(0)),
+            running: Arc::new(AtomicBool::new(true)),
+            worker: Mutex::new(None),
+        };
+        let span = info_span!("dropped");
+        for _ in 0..3 {
+            let metadata = span.metadata().expect("metadata");
+            writer.emit(
+                None,
+                None,
+                metadata,
+                Storage::default(),
+                Identity::default(),
+                "span",
+            );
+        }
+        assert_eq!(writer.dropped.load(Ordering::Relaxed), 2);
+        let status = writer.monitor().status();
+        assert_eq!((status.capacity, status.queued, status.dropped), (1, 1, 2));
+
+        let batch = BatchWriter {
+            dao: Dao::new(db.clone()),
+            batch_size: 10,
+            flush_interval: Duration::from_secs(1),
+            dropped: writer.dropped.clone(),
+        };
+        rt.block_on(batch.flush(&mut Vec::new()));
+        assert_eq!(writer.dropped.load(Ordering::Relaxed), 0);
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        let logs = rt.block_on(logs(&db));
+        assert_eq!(logs.len(), 1);
+        assert_eq!(
+            logs[0].message.as_deref(),
+            Some("日志队列已满, 丢弃日志数量: 2")
+        );
     }
 }
@@ -1,68 +1,84 @@
 //! 输出到文件
 #![allow(unused)]
 
-use crate::config::FileConfig;
+use std::sync::Mutex;
+
+use super::rolling::RollingFileWriter;
+use crate::config::{FileConfig, FileFormat};
+use crate::redact::{RedactMakeWriter, Redactor};
 use crate::utils::time::local_time;
 
 use tracing::Subscriber;
+use tracing_appender::non_blocking;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_appender::{non_blocking, rolling};
 use tracing_subscriber::Layer;
-use tracing_subscriber::fmt;
-use tracing_subscriber::fmt::writer::MakeWriterExt;
+use tracing_subscriber::fmt::{self, MakeWriter};
 use tracing_subscriber::registry::LookupSpan;
 
 /// 同步输出到文件中
-/// 每天时轮换的文件追加器
-pub fn blocking_layer<S>(config: &FileConfig) -> Box<dyn Layer<S> + Send + Sync + 'static>
+/// 按配置轮转的文件追加器, 日志级别由可重载的过滤器控制
+pub fn blocking_layer<S>(
+    config: &FileConfig,
+    redactor: Redactor,
+) -> Box<dyn Layer<S> + Send + Sync + 'static>
 where
     S: Subscriber,
     S: for<'a> LookupSpan<'a>,
 {
-    // 本地时间
-    let timer = local_time();
-
-    // Shared configuration regardless of where logs are output to.
-    let file_appender = rolling::daily(config.filepath.clone(), config.filename.clone());
-    let layer = fmt::layer()
-        .with_ansi(false)
-        .with_level(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(false)
-        .with_timer(timer)
-        .with_writer(file_appender.with_max_level(config.level.clone().into()));
-    Box::new(layer)
+    let file_appender = RollingFileWriter::new(config).expect("初始化日志文件失败");
+    format_layer(
+        config,
+        RedactMakeWriter::new(Mutex::new(file_appender), redactor),
+        false,
+    )
 }
 
 /// 非阻塞日志输出到文件中
-/// 每天时轮换的文件追加器
+/// 按配置轮转的文件追加器, 日志级别由可重载的过滤器控制
 pub fn non_blocking_layer<S>(
     config: &FileConfig,
+    redactor: Redactor,
 ) -> (Box<dyn Layer<S> + Send + Sync + 'static>, WorkerGuard)
 where
     S: Subscriber,
     for<'a> S: LookupSpan<'a>,
 {
-    // 本地时间
-    let timer = local_time();
-
-    // Shared configuration regardless of where logs are output to.
-    let file_appender = rolling::daily(config.filepath.clone(), config.filename.clone());
+    let file_appender = RollingFileWriter::new(config).expect("初始化日志文件失败");
 
     // 非阻塞
     let (non_blocking, guard) = non_blocking(file_appender);
 
+    let layer = format_layer(config, RedactMakeWriter::new(non_blocking, redactor), true);
+    (layer, guard)
+}
+
+/// 按配置的日志格式创建输出层
+fn format_layer<S, W>(
+    config: &FileConfig,
+    writer: W,
+    with_target: bool,
+) -> Box<dyn Layer<S> + Send + Sync + 'static>
+where
+    S: Subscriber,
+    for<'a> S: LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    // 本地时间
+    let timer = local_time();
+
+    // Shared configuration regardless of where logs are output to.
     let layer = fmt::layer()
         .with_ansi(false)
         .with_level(true)
         .with_file(true)
         .with_line_number(true)
-        .with_target(true)
+        .with_target(with_target)
         .with_timer(timer)
-        .with_writer(non_blocking.with_max_level(config.level.clone().into()))
-        .boxed();
-    (layer, guard)
+        .with_writer(writer);
+    match config.format {
+        FileFormat::Text => layer.boxed(),
+        FileFormat::Json => layer.json().boxed(),
+    }
 }
 
 #[cfg(test)]
@@ -82,7 +98,7 @@ mod tests {
             ..FileConfig::default()
         };
 
-        let layer = blocking_layer(&conf);
+        let layer = blocking_layer(&conf, Redactor::default());
         let subscriber = tracing_subscriber::registry().with(layer);
         let _guard = tracing::subscriber::set_default(subscriber);
 
@@ -101,7 +117,7 @@ mod tests {
             ..FileConfig::default()
         };
 
-        let (layer, _guard) = non_blocking_layer(&conf);
+        let (layer, _guard) = non_blocking_layer(&conf, Redactor::default());
         let subscriber = tracing_subscriber::registry().with(layer);
         let _guard = tracing::subscriber::set_default(subscriber);
 
@@ -111,4 +127,27 @@ mod tests {
         warn!("this is warn");
         error!("this is error");
     }
+
+    #[test]
+    fn test_json_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf = FileConfig {
+            filepath: dir.path().to_string_lossy().to_string(),
+            format: FileFormat::Json,
+            ..FileConfig::default()
+        };
+
+        let layer = blocking_layer(&conf, Redactor::default());
+        let subscriber = tracing_subscriber::registry().with(layer);
+        {
+            let _guard = tracing::subscriber::set_default(subscriber);
+            info!(password = "123456", "this is json");
+        }
+
+        let text = std::fs::read_to_string(dir.path().join(&conf.filename)).unwrap();
+        let line: serde_json::Value = serde_json::from_str(text.trim()).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["fields"]["message"], "this is json");
+        assert_eq!(line["fields"]["password"], "******");
+    }
 }
@@ -6,3 +6,4 @@ pub mod console_costom_print;
 // pub mod console_json;
 pub mod db;
 pub mod file;
+pub mod rolling;
@@ -0,0 +1,407 @@
+//! 日志文件轮转
+//!
+//! 支持按小时、按天或按文件大小轮转, 轮转后的历史文件可使用 gzip 压缩,
+//! 并按数量或总大小清理最早的历史文件.
+//!
+//! 当前日志写入 `{filepath}/{filename}`, 历史文件命名为 `{filename}.{时间}[.序号][.gz]`.
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, NaiveDateTime};
+use flate2::{Compression, write::GzEncoder};
+
+use crate::config::{FileConfig, Rotation};
+
+/// 时钟
+pub trait Clock: Send + 'static {
+    /// 当前本地时间
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// 系统时钟
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// 可轮转的日志文件
+pub struct RollingFileWriter<C: Clock = SystemClock> {
+    dir: PathBuf,
+    filename: String,
+    rotation: Rotation,
+    max_file_size: u64,
+    max_files: usize,
+    max_total_size: u64,
+    compress: bool,
+    clock: C,
+    file: File,
+    /// 当前文件大小
+    size: u64,
+    /// 当前文件所属的时间周期
+    period: String,
+}
+
+impl RollingFileWriter<SystemClock> {
+    /// 从配置创建日志文件
+    pub fn new(config: &FileConfig) -> io::Result<Self> {
+        Self::with_clock(config, SystemClock)
+    }
+}
+
+impl<C: Clock> RollingFileWriter<C> {
+    /// 使用指定的时钟创建日志文件
+    pub fn with_clock(config: &FileConfig, clock: C) -> io::Result<Self> {
+        let dir = PathBuf::from(&config.filepath);
+        fs::create_dir_all(&dir)?;
+        let file = Self::open(&dir.join(&config.filename))?;
+        let size = file.metadata()?.len();
+        let period = Self::period(config.rotation, clock.now());
+
+        Ok(RollingFileWriter {
+            dir,
+            filename: config.filename.clone(),
+            rotation: config.rotation,
+            max_file_size: config.max_file_size * 1024 * 1024,
+            max_files: config.max_files,
+            max_total_size: config.max_total_size * 1024 * 1024,
+            compress: config.compress,
+            clock,
+            file,
+            size,
+            period,
+        })
+    }
+
+    /// 设置单个文件的最大字节数, 用于测试
+    #[cfg(test)]
+    fn with_max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    fn open(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    /// 时间所属的轮转周期
+    fn period(rotation: Rotation, now: NaiveDateTime) -> String {
+        match rotation {
+            Rotation::Hourly => now.format("%Y-%m-%d-%H").to_string(),
+            Rotation::Daily => now.format("%Y-%m-%d").to_string(),
+            Rotation::Never | Rotation::Size => String::new(),
+        }
+    }
+
+    /// 写入前是否需要轮转
+    fn should_rotate(&self, now: NaiveDateTime, len: u64) -> bool {
+        match self.rotation {
+            Rotation::Never => false,
+            Rotation::Hourly | Rotation::Daily => {
+                self.size > 0 && Self::period(self.rotation, now) != self.period
+            }
+            Rotation::Size => self.size > 0 && self.size + len > self.max_file_size,
+        }
+    }
+
+    /// 轮转当前文件
+    fn rotate(&mut self, now: NaiveDateTime) -> io::Result<()> {
+        self.file.flush()?;
+
+        // 按时间轮转时使用文件所属的周期命名, 按大小轮转时使用轮转时间命名
+        let suffix = match self.rotation {
+            Rotation::Size => now.format("%Y-%m-%d-%H%M%S").to_string(),
+            _ => self.period.clone(),
+        };
+        let target = self.rotated_path(&suffix);
+        let current = self.dir.join(&self.filename);
+        fs::rename(&current, &target)?;
+
+        self.file = Self::open(&current)?;
+        self.size = 0;
+        self.period = Self::period(self.rotation, now);
+
+        if self.compress {
+            Self::gzip(&target)?;
+        }
+        self.purge()
+    }
+
+    /// 历史文件路径, 存在同名文件时追加序号
+    fn rotated_path(&self, suffix: &str) -> PathBuf {
+        let base = format!("{}.{}", self.filename, suffix);
+        let mut index = 0;
+        loop {
+            let name = match index {
+                0 => base.clone(),
+                _ => format!("{base}.{index}"),
+            };
+            let path = self.dir.join(&name);
+            if !path.exists() && !self.dir.join(format!("{name}.gz")).exists() {
+                return path;
+            }
+            index += 1;
+        }
+    }
+
+    /// 压缩历史文件并删除原文件
+    fn gzip(path: &Path) -> io::Result<()> {
+        let mut target = path.as_os_str().to_owned();
+        target.push(".gz");
+
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+        io::copy(&mut reader, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+        fs::remove_file(path)
+    }
+
+    /// 历史文件列表, 按时间从早到晚排序
+    fn rotated_files(&self) -> io::Result<Vec<(PathBuf, u64)>> {
+        let prefix = format!("{}.", self.filename);
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(&prefix) || !entry.file_type()?.is_file() {
+                continue;
+            }
+            files.push((entry.path(), entry.metadata()?.len()));
+        }
+        files.sort_by_key(|(path, _)| Self::sort_key(path));
+        Ok(files)
+    }
+
+    /// 排序键, 忽略压缩后缀并按序号排序
+    fn sort_key(path: &Path) -> (String, u32) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let name = name.strip_suffix(".gz").unwrap_or(&name);
+        match name.rsplit_once('.') {
+            Some((base, index)) if index.len() < 4 => match index.parse::<u32>() {
+                Ok(index) => (base.to_string(), index),
+                Err(_) => (name.to_string(), 0),
+            },
+            _ => (name.to_string(), 0),
+        }
+    }
+
+    /// 按数量及总大小清理最早的历史文件
+    fn purge(&self) -> io::Result<()> {
+        if self.max_files == 0 && self.max_total_size == 0 {
+            return Ok(());
+        }
+        let mut files = self.rotated_files()?;
+        let mut total: u64 = files.iter().map(|(_, size)| size).sum();
+        while let Some((path, size)) = files.first().cloned() {
+            let over_count = self.max_files > 0 && files.len() > self.max_files;
+            let over_size = self.max_total_size > 0 && total > self.max_total_size;
+            if !over_count && !over_size {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= size;
+            files.remove(0);
+        }
+        Ok(())
+    }
+}
+
+impl<C: Clock> Write for RollingFileWriter<C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let now = self.clock.now();
+        if self.should_rotate(now, buf.len() as u64)
+            && let Err(err) = self.rotate(now)
+        {
+            // 轮转失败时继续写入当前文件, 避免丢失日志
+            eprintln!("rotate log file failed, err: {err:#?}");
+        }
+        let len = self.file.write(buf)?;
+        self.size += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Read,
+        sync::{Arc, Mutex},
+    };
+
+    use chrono::{Duration, NaiveDate};
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    /// 测试时钟
+    #[derive(Clone)]
+    struct FakeClock(Arc<Mutex<NaiveDateTime>>);
+
+    impl FakeClock {
+        fn new() -> Self {
+            let now = NaiveDate::from_ymd_opt(2024, 1, 1)
+                .and_then(|v| v.and_hms_opt(10, 30, 0))
+                .expect("date time");
+            FakeClock(Arc::new(Mutex::new(now)))
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.0.lock().unwrap() += duration;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> NaiveDateTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn config(dir: &Path, rotation: Rotation) -> FileConfig {
+        FileConfig {
+            filepath: dir.to_string_lossy().to_string(),
+            filename: "app.log".to_owned(),
+            rotation,
+            ..Default::default()
+        }
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut names = fs::read_dir(dir)
+            .unwrap()
+            .map(|v| v.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_rotate_hourly() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::new();
+        let mut writer =
+            RollingFileWriter::with_clock(&config(dir.path(), Rotation::Hourly), clock.clone())
+                .unwrap();
+
+        writer.write_all(b"line 1\n").unwrap();
+        clock.advance(Duration::minutes(20));
+        writer.write_all(b"line 2\n").unwrap();
+        assert_eq!(names(dir.path()), vec!["app.log"]);
+
+        clock.advance(Duration::minutes(20));
+        writer.write_all(b"line 3\n").unwrap();
+        assert_eq!(names(dir.path()), vec!["app.log", "app.log.2024-01-01-10"]);
+        let rotated = fs::read_to_string(dir.path().join("app.log.2024-01-01-10")).unwrap();
+        assert_eq!(rotated, "line 1\nline 2\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app.log")).unwrap(),
+            "line 3\n"
+        );
+    }
+
+    #[test]
+    fn test_rotate_daily_with_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::new();
+        let config = FileConfig {
+            max_files: 2,
+            ..config(dir.path(), Rotation::Daily)
+        };
+        let mut writer = RollingFileWriter::with_clock(&config, clock.clone()).unwrap();
+
+        for i in 0..5 {
+            writer.write_all(format!("day {i}\n").as_bytes()).unwrap();
+            clock.advance(Duration::days(1));
+        }
+        writer.write_all(b"day 5\n").unwrap();
+        assert_eq!(
+            names(dir.path()),
+            vec!["app.log", "app.log.2024-01-04", "app.log.2024-01-05"]
+        );
+    }
+
+    #[test]
+    fn test_rotate_size_with_compress() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::new();
+        let config = FileConfig {
+            compress: true,
+            ..config(dir.path(), Rotation::Size)
+        };
+        let mut writer = RollingFileWriter::with_clock(&config, clock.clone())
+            .unwrap()
+            .with_max_file_bytes(10);
+
+        // 同一秒内多次轮转, 使用序号区分
+        writer.write_all(b"0123456789").unwrap();
+        writer.write_all(b"abcdefghij").unwrap();
+        writer.write_all(b"ABCDEFGHIJ").unwrap();
+        assert_eq!(
+            names(dir.path()),
+            vec![
+                "app.log",
+                "app.log.2024-01-01-103000.1.gz",
+                "app.log.2024-01-01-103000.gz"
+            ]
+        );
+
+        let mut text = String::new();
+        GzDecoder::new(File::open(dir.path().join("app.log.2024-01-01-103000.1.gz")).unwrap())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "abcdefghij");
+    }
+
+    #[test]
+    fn test_max_total_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::new();
+        let mut writer =
+            RollingFileWriter::with_clock(&config(dir.path(), Rotation::Size), clock.clone())
+                .unwrap()
+                .with_max_file_bytes(10);
+        writer.max_total_size = 25;
+
+        for i in 0..5 {
+            writer
+                .write_all(format!("{i}{i}{i}{i}{i}{i}{i}{i}{i}{i}").as_bytes())
+                .unwrap();
+            clock.advance(Duration::seconds(1));
+        }
+        // 历史文件共 4 个, 每个 10 字节, 仅保留最近 2 个
+        assert_eq!(
+            names(dir.path()),
+            vec![
+                "app.log",
+                "app.log.2024-01-01-103003",
+                "app.log.2024-01-01-103004"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("app.log.2024-01-01-103004")).unwrap(),
+            "3333333333"
+        );
+    }
+
+    #[test]
+    fn test_rotate_never() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = FakeClock::new();
+        let mut writer =
+            RollingFileWriter::with_clock(&config(dir.path(), Rotation::Never), clock.clone())
+                .unwrap();
+        writer.write_all(b"line 1\n").unwrap();
+        clock.advance(Duration::days(3));
+        writer.write_all(b"line 2\n").unwrap();
+        assert_eq!(names(dir.path()), vec!["app.log"]);
+    }
+}
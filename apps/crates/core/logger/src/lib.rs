@@ -10,31 +10,62 @@
 //!     https://docs.rs/tracing-subscriber/latest/tracing_subscriber/layer/index.html
 pub mod config;
 pub mod dao;
+pub mod filter;
 mod layer;
+pub mod redact;
+pub mod tail;
 pub mod utils;
 
+pub use layer::db::writer::{DbWriterGuard, DbWriterMonitor, DbWriterStatus};
+
 use tracing::subscriber::SetGlobalDefaultError;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
     Layer, Registry,
+    filter::ParseError,
     layer::{Layered, SubscriberExt},
+    reload,
 };
 
 #[derive(Debug)]
 pub enum Error {
     ColorEyreReport(color_eyre::Report),
     SetGlobalDefaultError(SetGlobalDefaultError),
+    /// 过滤指令为空
+    FilterDirectivesEmpty,
+    /// 过滤指令解析失败
+    FilterParse(ParseError),
+    /// 过滤器重载失败
+    FilterReload(reload::Error),
+    /// 日志输出层未启用
+    FilterLayerNotFound(filter::LayerKind),
+    /// 自动恢复需要在 Tokio 运行时中设置
+    FilterRevertRuntime(tokio::runtime::TryCurrentError),
 }
 
+/// 日志输出守卫, 释放时写入剩余的日志, 需持有至程序退出
+#[derive(Debug)]
+pub enum LoggerGuard {
+    /// 文件日志写入守卫
+    Worker(WorkerGuard),
+    /// 数据库日志写入守卫
+    Db(DbWriterGuard),
+}
+
+/// 日志订阅器
+type RegistrySubscriber = Layered<ErrorLayer<Registry>, Registry>;
+
 /// 日志 Layer
-type RegistryLayer = Box<dyn Layer<Layered<ErrorLayer<Registry>, Registry>> + Send + Sync>;
+type RegistryLayer = Box<dyn Layer<RegistrySubscriber> + Send + Sync>;
 
 /// 日志 Layers 构造器
 struct LoggerLayer<'a> {
     layers: Vec<RegistryLayer>,
-    guards: Vec<WorkerGuard>,
+    guards: Vec<LoggerGuard>,
     config: &'a config::LoggerConfig,
+    redactor: redact::Redactor,
+    filter: filter::LoggerFilter,
 }
 
 impl<'a> LoggerLayer<'a> {
@@ -42,10 +73,13 @@ impl<'a> LoggerLayer<'a> {
     fn form_config(config: &'a config::LoggerConfig) -> Self {
         let layers = Vec::new();
         let guards = Vec::new();
+        let redactor = redact::Redactor::new(&config.redact);
         LoggerLayer {
             layers,
             guards,
             config,
+            redactor,
+            filter: filter::LoggerFilter::default(),
         }
     }
 
@@ -55,8 +89,11 @@ impl<'a> LoggerLayer<'a> {
             return self;
         }
 
-        let layer = layer::console::layer(&self.config.console);
-        self.layers.push(layer);
+        let layer = layer::console::layer(self.redactor.clone()).with_filter(
+            self.filter
+                .layer(filter::LayerKind::Console, &self.config.console.level),
+        );
+        self.layers.push(layer.boxed());
         self
     }
 
@@ -66,8 +103,12 @@ impl<'a> LoggerLayer<'a> {
             return self;
         }
 
-        let layer = layer::console_bunyan::layer(&self.config.console_bunyan);
-        self.layers.push(layer);
+        let layer =
+            layer::console_bunyan::layer(self.redactor.clone()).with_filter(self.filter.layer(
+                filter::LayerKind::ConsoleBunyan,
+                &self.config.console_bunyan.level,
+            ));
+        self.layers.push(layer.boxed());
         self
     }
 
@@ -77,9 +118,14 @@ impl<'a> LoggerLayer<'a> {
             return self;
         }
 
-        let (file_layer, file_guard) = layer::file::non_blocking_layer(&self.config.file);
-        self.layers.push(file_layer);
-        self.guards.push(file_guard);
+        let (file_layer, file_guard) =
+            layer::file::non_blocking_layer(&self.config.file, self.redactor.clone());
+        let file_layer = file_layer.with_filter(
+            self.filter
+                .layer(filter::LayerKind::File, &self.config.file.level),
+        );
+        self.layers.push(file_layer.boxed());
+        self.guards.push(LoggerGuard::Worker(file_guard));
         self
     }
 
@@ -89,14 +135,21 @@ impl<'a> LoggerLayer<'a> {
             return self;
         }
 
-        let (layer, guard) = layer::db::non_blocking_layer(self.config.db.clone());
-        self.layers.push(layer);
-        self.guards.push(guard);
+        let (layer, guard) =
+            layer::db::non_blocking_layer(self.config.db.clone(), self.redactor.clone());
+        let layer = layer.with_filter(
+            self.filter
+                .layer(filter::LayerKind::Db, &self.config.db.level),
+        );
+        self.layers.push(layer.boxed());
+        self.guards.push(LoggerGuard::Db(guard));
         self
     }
 
     /// 构建对象
-    pub fn build(config: &'a config::LoggerConfig) -> (Vec<RegistryLayer>, Vec<WorkerGuard>) {
+    pub fn build(
+        config: &'a config::LoggerConfig,
+    ) -> (Vec<RegistryLayer>, Vec<LoggerGuard>, filter::LoggerFilter) {
         let mut binding = Self::form_config(config);
         let layer = binding
             .set_console()
@@ -107,6 +160,7 @@ impl<'a> LoggerLayer<'a> {
         (
             std::mem::take(&mut layer.layers),
             std::mem::take(&mut layer.guards),
+            layer.filter.clone(),
         )
     }
 }
@@ -158,12 +212,14 @@ impl<'a> Logger<'a> {
     }
 
     /// 构建日志订阅器
-    pub fn build(config: &'a config::LoggerConfig) -> Result<Vec<WorkerGuard>, Error> {
-        let (layers, guards) = LoggerLayer::build(config);
+    pub fn build(config: &'a config::LoggerConfig) -> Result<Vec<LoggerGuard>, Error> {
+        let (layers, guards, filter) = LoggerLayer::build(config);
 
         Self::form_config(config)
             .set_color_eyre()?
             .set_global_default(layers)?;
+        // 注册全局日志过滤器, 用于运行时调整日志级别
+        filter.set_global();
 
         Ok(guards)
     }
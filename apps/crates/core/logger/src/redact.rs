@@ -0,0 +1,357 @@
+//! 敏感信息脱敏
+//!
+//! 用于日志输出、数据库日志及API操作日志中的敏感字段与请求头脱敏
+use std::{borrow::Cow, io, sync::Arc};
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+use tracing::Metadata;
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::config::RedactConfig;
+
+/// 终端颜色控制符, 控制台日志中字段名称与值之间可能存在
+const ANSI: &str = r"(?:\x1b\[[0-9;]*m)*";
+
+/// 脱敏器
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    enable: bool,
+    mask: String,
+    /// 字段名称匹配
+    key: Regex,
+    /// 请求头名称匹配
+    header: Regex,
+    /// 文本中的 `key: value` / `key=value` 匹配
+    pair: Regex,
+    /// 文本中的 Bearer 令牌及 JWT 匹配
+    token: Regex,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Redactor::new(&RedactConfig::default())
+    }
+}
+
+impl Redactor {
+    /// 从配置创建脱敏器
+    pub fn new(config: &RedactConfig) -> Self {
+        let keys = Self::alternation(&config.keys);
+        let headers = Self::alternation(&config.headers);
+        let names = [keys.as_str(), headers.as_str()]
+            .into_iter()
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join("|");
+        let names = Self::or_never(&names);
+
+        // 名称均已转义, 正则表达式总是合法的
+        let build = |pattern: String| Regex::new(&pattern).expect("invalid redact pattern");
+        let inner = Inner {
+            enable: config.enable,
+            mask: config.mask.clone(),
+            key: build(format!(r"(?i)^(?:{})$", Self::or_never(&keys))),
+            header: build(format!(r"(?i)^(?:{})$", Self::or_never(&headers))),
+            pair: build(format!(
+                r#"(?i)(?P<key>(?:^|\x1b\[[0-9;]*m|[^\w-])(?:\\?")?(?:{names})\b(?:\\?")?{ANSI}\s*[:=]{ANSI}\s*)(?P<value>\\"(?:[^"\\]|\\[^"])*\\"|"(?:[^"\\]|\\.)*"|[^\s,&;}}\])"\\]+)"#
+            )),
+            token: build(r"(?i)\bBearer\s+[\w\-.~+/]+=*|\beyJ[\w-]+\.[\w-]+\.[\w-]+".to_owned()),
+        };
+        Redactor {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// 脱敏后的替换值
+    pub fn mask(&self) -> &str {
+        &self.inner.mask
+    }
+
+    /// 字段是否需要脱敏
+    pub fn is_sensitive_key(&self, key: &str) -> bool {
+        self.inner.enable && self.inner.key.is_match(key)
+    }
+
+    /// 请求头是否需要脱敏
+    pub fn is_sensitive_header(&self, name: &str) -> bool {
+        self.inner.enable && self.inner.header.is_match(name)
+    }
+
+    /// JSON 值脱敏
+    pub fn redact_value(&self, value: &mut Value) {
+        if !self.inner.enable {
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.is_sensitive_key(key) {
+                        *value = Value::String(self.inner.mask.clone());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+            Value::Array(list) => list.iter_mut().for_each(|v| self.redact_value(v)),
+            Value::String(text) => {
+                if let Cow::Owned(v) = self.redact_text(text) {
+                    *text = v;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// JSON 字符串脱敏, 非 JSON 时按文本脱敏
+    pub fn redact_json(&self, text: &str) -> String {
+        if !self.inner.enable {
+            return text.to_owned();
+        }
+        match serde_json::from_str::<Value>(text) {
+            Ok(mut value) => {
+                self.redact_value(&mut value);
+                value.to_string()
+            }
+            Err(_) => self.redact_text(text).into_owned(),
+        }
+    }
+
+    /// 表单及查询参数脱敏
+    pub fn redact_form(&self, text: &str) -> String {
+        if !self.inner.enable {
+            return text.to_owned();
+        }
+        text.split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, _)) if self.is_sensitive_key(key) => {
+                    format!("{key}={}", self.inner.mask)
+                }
+                _ => pair.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// 任意文本脱敏, 如格式化后的日志行
+    pub fn redact_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.inner.enable {
+            return Cow::Borrowed(text);
+        }
+        let mask = &self.inner.mask;
+        let text = self.inner.pair.replace_all(text, |caps: &Captures| {
+            let value = &caps["value"];
+            let value = if value.starts_with(r#"\""#) {
+                format!(r#"\"{mask}\""#)
+            } else if value.starts_with('"') {
+                format!(r#""{mask}""#)
+            } else {
+                mask.clone()
+            };
+            format!("{}{}", &caps["key"], value)
+        });
+        let token = match self.inner.token.replace_all(&text, |caps: &Captures| {
+            if caps[0].to_ascii_lowercase().starts_with("bearer") {
+                return format!("Bearer {mask}");
+            }
+            mask.clone()
+        }) {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(v) => Some(v),
+        };
+        match token {
+            Some(v) => Cow::Owned(v),
+            None => text,
+        }
+    }
+
+    /// 将名称列表转换为正则表达式分支
+    fn alternation(names: &[String]) -> String {
+        names
+            .iter()
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                v.split('*')
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(r"[\w-]*")
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    /// 空分支时返回不匹配任何内容的表达式
+    fn or_never(alternation: &str) -> &str {
+        if alternation.is_empty() {
+            return r"[^\s\S]";
+        }
+        alternation
+    }
+}
+
+/// 脱敏输出包装
+///
+/// 格式化后的日志行在写入前进行脱敏
+#[derive(Debug, Clone)]
+pub struct RedactMakeWriter<M> {
+    make_writer: M,
+    redactor: Redactor,
+}
+
+impl<M> RedactMakeWriter<M> {
+    pub fn new(make_writer: M, redactor: Redactor) -> Self {
+        RedactMakeWriter {
+            make_writer,
+            redactor,
+        }
+    }
+}
+
+impl<'a, M> MakeWriter<'a> for RedactMakeWriter<M>
+where
+    M: MakeWriter<'a>,
+{
+    type Writer = RedactWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactWriter {
+            writer: self.make_writer.make_writer(),
+            redactor: self.redactor.clone(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        RedactWriter {
+            writer: self.make_writer.make_writer_for(meta),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+/// 脱敏输出
+pub struct RedactWriter<W> {
+    writer: W,
+    redactor: Redactor,
+}
+
+impl<W: io::Write> io::Write for RedactWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        match self.redactor.redact_text(&text) {
+            Cow::Borrowed(_) => self.writer.write_all(buf)?,
+            Cow::Owned(v) => self.writer.write_all(v.as_bytes())?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_is_sensitive() {
+        let redactor = Redactor::default();
+        assert!(redactor.is_sensitive_key("password"));
+        assert!(redactor.is_sensitive_key("Old_Password"));
+        assert!(redactor.is_sensitive_key("app_secret"));
+        assert!(redactor.is_sensitive_key("access_token"));
+        assert!(!redactor.is_sensitive_key("access_token_status"));
+        assert!(!redactor.is_sensitive_key("username"));
+        assert!(redactor.is_sensitive_header("Authorization"));
+        assert!(!redactor.is_sensitive_header("content-type"));
+
+        let redactor = Redactor::new(&RedactConfig {
+            enable: false,
+            ..Default::default()
+        });
+        assert!(!redactor.is_sensitive_key("password"));
+    }
+
+    #[test]
+    fn test_redact_json() {
+        let redactor = Redactor::default();
+        let text = json!({
+            "username": "user01",
+            "password": "123456",
+            "accounts": [{"app_secret": "s1", "access_token": "t1", "access_token_status": true}],
+            "remark": "Bearer abc.def",
+        })
+        .to_string();
+        let value: Value = serde_json::from_str(&redactor.redact_json(&text)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "username": "user01",
+                "password": "******",
+                "accounts": [{"app_secret": "******", "access_token": "******", "access_token_status": true}],
+                "remark": "Bearer ******",
+            })
+        );
+        assert_eq!(
+            redactor.redact_form("username=user01&password=123456"),
+            "username=user01&password=******"
+        );
+    }
+
+    #[test]
+    fn test_redact_text() {
+        let redactor = Redactor::default();
+        let cases = [
+            (
+                r#"headers={"authorization": "Bearer eyJ.a.b", "accept": "*/*"}"#,
+                r#"headers={"authorization": "******", "accept": "*/*"}"#,
+            ),
+            (
+                r#"body="{\"password\":\"123456\",\"username\":\"user01\"}""#,
+                r#"body="{\"password\":\"******\",\"username\":\"user01\"}""#,
+            ),
+            (
+                r#"LoginReq { username: "user01", password: "123456" }"#,
+                r#"LoginReq { username: "user01", password: "******" }"#,
+            ),
+            (
+                "/login?password=123456&page=1",
+                "/login?password=******&page=1",
+            ),
+            (
+                "token eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOjF9.c2lnbmF0dXJl end",
+                "token ****** end",
+            ),
+            (
+                "\x1b[3mpassword\x1b[0m\x1b[2m=\x1b[0m123456 ok",
+                "\x1b[3mpassword\x1b[0m\x1b[2m=\x1b[0m****** ok",
+            ),
+            ("nothing to hide", "nothing to hide"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(redactor.redact_text(text), expected);
+        }
+        assert!(matches!(
+            redactor.redact_text("nothing to hide"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_redact_writer() {
+        let make_writer = RedactMakeWriter::new(Vec::<u8>::new, Redactor::default());
+        let mut writer = make_writer.make_writer();
+        let line = b"login password=123456\n";
+        assert_eq!(writer.write(line).unwrap(), line.len());
+        assert_eq!(writer.writer, b"login password=******\n");
+    }
+}
@@ -0,0 +1,58 @@
+//! 实时日志订阅
+//!
+//! 数据库日志层在日志写入通道的同时, 将日志广播给订阅者, 用于实时查看日志;
+//! 无订阅者时不进行任何复制.
+use std::sync::LazyLock;
+
+use entity::log::log_system;
+use tokio::sync::broadcast::{self, Receiver, Sender};
+
+/// 广播通道容量, 订阅者处理过慢时将丢弃最早的日志
+pub const TAIL_CAPACITY: usize = 1024;
+
+/// 日志广播通道
+static TAIL: LazyLock<Sender<log_system::Model>> =
+    LazyLock::new(|| broadcast::channel(TAIL_CAPACITY).0);
+
+/// 订阅实时日志
+///
+/// 仅在启用数据库日志时产生数据, 日志级别受数据库日志配置的级别限制
+pub fn subscribe() -> Receiver<log_system::Model> {
+    TAIL.subscribe()
+}
+
+/// 发布日志到订阅者
+pub fn publish(log: &log_system::Model) {
+    if TAIL.receiver_count() == 0 {
+        return;
+    }
+    // 发送失败时表示订阅者已全部退出, 忽略即可
+    let _ = TAIL.send(log.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish() {
+        // 无订阅者时直接忽略
+        publish(&log_system::Model::default());
+
+        let mut rx = subscribe();
+        let log = log_system::Model {
+            target: "tail_test".to_string(),
+            message: Some("hello".to_string()),
+            ..Default::default()
+        };
+        publish(&log);
+        // 其他测试可能同时产生日志
+        loop {
+            let output = rx.recv().await.expect("recv log");
+            if output.target == "tail_test" {
+                assert_eq!(output.message.as_deref(), Some("hello"));
+                break;
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use tracing_subscriber::fmt::time::OffsetTime;
 
 /// 获取本地时间
 pub fn local_time() -> OffsetTime<Vec<FormatItem<'static>>> {
-    let time_format = time::format_description::parse(
+    let time_format = time::format_description::parse_borrowed::<2>(
         "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:3]",
     )
     .expect("format string should be valid!");
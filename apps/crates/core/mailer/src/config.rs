@@ -0,0 +1,95 @@
+//! 邮件配置
+use serde::{Deserialize, Serialize};
+
+/// 邮件配置
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MailerConfig {
+    /// 发送方式
+    #[serde(default)]
+    pub mode: Mode,
+    /// 发件人, 如: "DeBox <noreply@example.com>"
+    #[serde(default)]
+    pub from: String,
+    /// 站点地址, 用于拼接邮件中的链接
+    #[serde(default)]
+    pub base_url: String,
+    /// SMTP 配置
+    #[serde(default)]
+    pub smtp: SmtpConfig,
+    /// 文件配置
+    #[serde(default)]
+    pub file: FileConfig,
+}
+
+/// 发送方式
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    /// SMTP 服务
+    #[serde(rename = "smtp")]
+    Smtp,
+    /// 写入本地文件, 用于开发环境
+    #[serde(rename = "file")]
+    File,
+    /// 保存在内存中, 用于测试
+    #[default]
+    #[serde(rename = "memory")]
+    Memory,
+}
+
+/// SMTP 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    /// 服务地址
+    pub host: String,
+    /// 服务端口
+    pub port: u16,
+    /// 账号
+    pub username: String,
+    /// 密码
+    pub password: String,
+    /// 加密方式
+    #[serde(default)]
+    pub encryption: Encryption,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 465,
+            username: String::new(),
+            password: String::new(),
+            encryption: Encryption::default(),
+        }
+    }
+}
+
+/// SMTP 加密方式
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Encryption {
+    /// 不加密
+    #[serde(rename = "none")]
+    None,
+    /// STARTTLS
+    #[serde(rename = "starttls")]
+    StartTls,
+    /// TLS
+    #[default]
+    #[serde(rename = "tls")]
+    Tls,
+}
+
+/// 文件配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileConfig {
+    /// 邮件存放目录
+    pub dirpath: String,
+}
+
+impl Default for FileConfig {
+    fn default() -> Self {
+        Self {
+            dirpath: "mails".to_string(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! 错误类型
+
+/// 错误种类
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("邮件地址解析失败, {0}")]
+    AddressParse(String),
+    #[error("邮件构建失败, {0}")]
+    MessageBuild(String),
+    #[error("SMTP 服务错误, {0}")]
+    Smtp(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
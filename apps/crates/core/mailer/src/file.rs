@@ -0,0 +1,94 @@
+//! 文件邮件发送, 用于开发环境
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Local;
+use log::info;
+
+use crate::{Mail, Mailer, config::MailerConfig, error::Error};
+
+/// 文件邮件发送器
+///
+/// 每封邮件写入一个 `.eml` 文本文件
+pub struct FileMailer {
+    dirpath: PathBuf,
+    from: String,
+    base_url: String,
+}
+
+impl FileMailer {
+    pub fn new(config: &MailerConfig) -> Self {
+        FileMailer {
+            dirpath: PathBuf::from(&config.file.dirpath),
+            from: config.from.clone(),
+            base_url: config.base_url.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: Mail) -> Result<(), Error> {
+        tokio::fs::create_dir_all(&self.dirpath).await?;
+
+        let now = Local::now();
+        let filename = format!(
+            "{}-{}.eml",
+            now.format("%Y%m%d%H%M%S%6f"),
+            mail.to.replace(['@', '/', '\\'], "_")
+        );
+        let content = format!(
+            "From: {}\r\nTo: {}\r\nDate: {}\r\nSubject: {}\r\n\r\n{}\r\n",
+            self.from,
+            mail.to,
+            now.to_rfc2822(),
+            mail.subject,
+            mail.body
+        );
+
+        let filepath = self.dirpath.join(filename);
+        tokio::fs::write(&filepath, content).await?;
+        info!("邮件已写入文件, path: {}", filepath.display());
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::FileConfig;
+
+    #[tokio::test]
+    async fn test_file_mailer() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let config = MailerConfig {
+            from: "noreply@example.com".to_string(),
+            file: FileConfig {
+                dirpath: dir.path().to_string_lossy().to_string(),
+            },
+            ..Default::default()
+        };
+        let mailer = FileMailer::new(&config);
+        let mail = Mail {
+            to: "user@example.com".to_string(),
+            subject: "hello".to_string(),
+            body: "world".to_string(),
+        };
+        mailer.send(mail).await.expect("send mail");
+
+        let entries: Vec<_> = std::fs::read_dir(dir.path())
+            .expect("read dir")
+            .flatten()
+            .collect();
+        assert_eq!(entries.len(), 1);
+        let content = std::fs::read_to_string(entries[0].path()).expect("read mail");
+        assert!(content.contains("To: user@example.com"));
+        assert!(content.contains("Subject: hello"));
+        assert!(content.ends_with("world\r\n"));
+    }
+}
@@ -0,0 +1,48 @@
+//! 邮件发送
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub mod config;
+pub mod error;
+mod file;
+mod memory;
+mod smtp;
+
+pub use config::{Encryption, FileConfig, MailerConfig, Mode, SmtpConfig};
+pub use error::Error;
+pub use file::FileMailer;
+pub use memory::MemoryMailer;
+pub use smtp::SmtpMailer;
+
+/// 邮件内容
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mail {
+    /// 收件人
+    pub to: String,
+    /// 主题
+    pub subject: String,
+    /// 正文, 纯文本
+    pub body: String,
+}
+
+/// 邮件发送器
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// 发送邮件
+    async fn send(&self, mail: Mail) -> Result<(), Error>;
+
+    /// 站点地址, 用于拼接邮件中的链接
+    fn base_url(&self) -> &str;
+}
+
+/// 根据配置构建邮件发送器
+pub fn build(config: &MailerConfig) -> Result<Arc<dyn Mailer>, Error> {
+    let mailer: Arc<dyn Mailer> = match config.mode {
+        Mode::Smtp => Arc::new(SmtpMailer::new(config)?),
+        Mode::File => Arc::new(FileMailer::new(config)),
+        Mode::Memory => Arc::new(MemoryMailer::new(config)),
+    };
+    Ok(mailer)
+}
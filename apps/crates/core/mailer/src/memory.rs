@@ -0,0 +1,81 @@
+//! 内存邮件发送, 用于测试
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::info;
+use tokio::sync::Mutex;
+
+use crate::{Mail, Mailer, config::MailerConfig, error::Error};
+
+/// 内存邮件发送器
+///
+/// 邮件不会真正发出, 仅保存在内存中, 可通过 [`MemoryMailer::mails`] 读取
+#[derive(Debug, Default, Clone)]
+pub struct MemoryMailer {
+    mails: Arc<Mutex<Vec<Mail>>>,
+    base_url: String,
+}
+
+impl MemoryMailer {
+    pub fn new(config: &MailerConfig) -> Self {
+        MemoryMailer {
+            mails: Arc::new(Mutex::new(Vec::new())),
+            base_url: config.base_url.clone(),
+        }
+    }
+
+    /// 获取已发送的邮件
+    pub async fn mails(&self) -> Vec<Mail> {
+        self.mails.lock().await.clone()
+    }
+
+    /// 获取最后一封发送给指定收件人的邮件
+    pub async fn last_mail_to(&self, to: &str) -> Option<Mail> {
+        self.mails
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|mail| mail.to == to)
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, mail: Mail) -> Result<(), Error> {
+        info!(
+            "邮件已保存到内存, to: {}, subject: {}",
+            mail.to, mail.subject
+        );
+        self.mails.lock().await.push(mail);
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_mailer() {
+        let mailer = MemoryMailer::default();
+        for i in 0..2 {
+            let mail = Mail {
+                to: "user@example.com".to_string(),
+                subject: format!("subject-{i}"),
+                body: "body".to_string(),
+            };
+            mailer.send(mail).await.expect("send mail");
+        }
+
+        assert_eq!(mailer.mails().await.len(), 2);
+        let last = mailer.last_mail_to("user@example.com").await;
+        assert_eq!(last.map(|v| v.subject), Some("subject-1".to_string()));
+        assert!(mailer.last_mail_to("other@example.com").await.is_none());
+    }
+}
@@ -0,0 +1,84 @@
+//! SMTP 邮件发送
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use log::info;
+
+use crate::{
+    Mail, Mailer,
+    config::{Encryption, MailerConfig},
+    error::Error,
+};
+
+/// SMTP 邮件发送器
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    base_url: String,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &MailerConfig) -> Result<Self, Error> {
+        let from = config
+            .from
+            .parse::<Mailbox>()
+            .map_err(|err| Error::AddressParse(err.to_string()))?;
+
+        let smtp = &config.smtp;
+        let builder = match smtp.encryption {
+            Encryption::None => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp.host.as_str())
+            }
+            Encryption::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp.host.as_str())
+                    .map_err(|err| Error::Smtp(err.to_string()))?
+            }
+            Encryption::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(smtp.host.as_str())
+                .map_err(|err| Error::Smtp(err.to_string()))?,
+        };
+        let transport = builder
+            .port(smtp.port)
+            .credentials(Credentials::new(
+                smtp.username.clone(),
+                smtp.password.clone(),
+            ))
+            .build();
+
+        Ok(SmtpMailer {
+            transport,
+            from,
+            base_url: config.base_url.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: Mail) -> Result<(), Error> {
+        let to = mail
+            .to
+            .parse::<Mailbox>()
+            .map_err(|err| Error::AddressParse(err.to_string()))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(mail.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(mail.body)
+            .map_err(|err| Error::MessageBuild(err.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|err| Error::Smtp(err.to_string()))?;
+        info!("邮件已发送, to: {}", mail.to);
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
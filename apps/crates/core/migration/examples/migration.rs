@@ -26,6 +26,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         console_bunyan: Default::default(),
         file: Default::default(),
         db: Default::default(),
+        redact: Default::default(),
+        retention: Default::default(),
     };
     // 初始化日志
     let _guards = logger::Logger::build(&conf).expect("初始化日志失败");
@@ -0,0 +1,236 @@
+//! DeBox群组表主键调整
+//!
+//! 群组ID改为自增整型, 并取消账号ID的唯一约束, 一个账号可关联多个群组.
+//! 主键类型无法直接修改, 需重建数据表并迁移已有数据, 原群组ID不再保留.
+use sea_orm::{
+    DatabaseBackend, DeriveIden, DeriveMigrationName,
+    sea_query::{ColumnDef, Expr, ForeignKey, ForeignKeyAction, Index, Query, Table},
+};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+use crate::debox::{debox_account::DeboxAccount, debox_group::DeboxGroup};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        rebuild(manager, true).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        rebuild(manager, false).await
+    }
+}
+
+/// 重建群组表
+///
+/// `integer_id` 为 `true` 时使用自增整型主键, 否则恢复为字符串主键且账号ID唯一.
+async fn rebuild(manager: &SchemaManager<'_>, integer_id: bool) -> Result<(), DbErr> {
+    manager
+        .rename_table(
+            Table::rename()
+                .table(DeboxGroup::Table, DeboxGroupOld::Table)
+                .to_owned(),
+        )
+        .await?;
+
+    let mut id = ColumnDef::new(DeboxGroup::Id);
+    let mut account_id = ColumnDef::new(DeboxGroup::AccountId);
+    if integer_id {
+        id.integer().auto_increment();
+    } else {
+        id.string().string_len(20);
+        account_id.unique_key();
+    }
+    manager
+        .create_table(
+            Table::create()
+                .table(DeboxGroup::Table)
+                .comment("DeBox群组表")
+                .col(id.primary_key().not_null().comment("群组ID"))
+                .col(account_id.integer().not_null().comment("账号ID"))
+                .col(
+                    ColumnDef::new(DeboxGroup::Url)
+                        .string()
+                        .string_len(60)
+                        .not_null()
+                        .comment("群组分享链接"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::GroupName)
+                        .string()
+                        .string_len(50)
+                        .not_null()
+                        .comment("群组名称"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::GroupCode)
+                        .string()
+                        .string_len(250)
+                        .default("")
+                        .comment("群组邀请码"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::Desc)
+                        .string()
+                        .string_len(200)
+                        .null()
+                        .default("")
+                        .comment("描述信息"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::Status)
+                        .boolean()
+                        .not_null()
+                        .default(false)
+                        .comment("状态(0:停用,1:正常)"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::CreatedAt)
+                        .date_time()
+                        .not_null()
+                        .default(Expr::current_timestamp())
+                        .comment("创建时间"),
+                )
+                .col(
+                    ColumnDef::new(DeboxGroup::UpdatedAt)
+                        .date_time()
+                        .not_null()
+                        .extra({
+                            match manager.get_database_backend() {
+                                DatabaseBackend::Sqlite => "DEFAULT CURRENT_TIMESTAMP",
+                                _ => "DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP",
+                            }
+                        })
+                        .comment("更新时间"),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+    // 整型主键由数据库生成, 字符串主键沿用原ID
+    let columns = || {
+        let mut columns = vec![
+            DeboxGroup::AccountId,
+            DeboxGroup::Url,
+            DeboxGroup::GroupName,
+            DeboxGroup::GroupCode,
+            DeboxGroup::Desc,
+            DeboxGroup::Status,
+            DeboxGroup::CreatedAt,
+            DeboxGroup::UpdatedAt,
+        ];
+        if !integer_id {
+            columns.insert(0, DeboxGroup::Id);
+        }
+        columns
+    };
+    let select = Query::select()
+        .columns(columns())
+        .from(DeboxGroupOld::Table)
+        .to_owned();
+    let insert = Query::insert()
+        .into_table(DeboxGroup::Table)
+        .columns(columns())
+        .select_from(select)
+        .map_err(|err| DbErr::Migration(err.to_string()))?
+        .to_owned();
+    manager.exec_stmt(insert).await?;
+
+    // 删除旧表时一并删除其索引及外键, 之后才能以相同名称重建
+    manager
+        .drop_table(Table::drop().table(DeboxGroupOld::Table).to_owned())
+        .await?;
+
+    manager
+        .create_index(
+            Index::create()
+                .if_not_exists()
+                .name("idx_account_id")
+                .table(DeboxGroup::Table)
+                .col(DeboxGroup::AccountId)
+                .to_owned(),
+        )
+        .await?;
+
+    // Sqlite 不支持外键
+    if manager.get_database_backend() == DatabaseBackend::Sqlite {
+        return Ok(());
+    }
+
+    manager
+        .create_foreign_key(
+            ForeignKey::create()
+                .name("fk_user_debox_group_account_id")
+                .from(DeboxGroup::Table, DeboxGroup::AccountId)
+                .to(DeboxAccount::Table, DeboxAccount::Id)
+                .on_update(ForeignKeyAction::Cascade)
+                .on_delete(ForeignKeyAction::Cascade)
+                .to_owned(),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// 重建期间的旧群组表
+#[derive(DeriveIden)]
+enum DeboxGroupOld {
+    #[sea_orm(iden = "t_debox_group_old")]
+    Table,
+}
+
+#[cfg(test)]
+mod tests {
+    use database::mock::Mock;
+    use entity::debox::debox_group;
+    use sea_orm::{ActiveModelTrait, ConnectionTrait, EntityTrait, Set};
+    use sea_orm_migration::{MigrationName, MigratorTrait};
+
+    use super::Migration;
+    use crate::Migrator;
+
+    #[tokio::test]
+    async fn test_rebuild() {
+        let db = Mock::builder().await.expect("connect db").build();
+        // 执行至重建群组表之前
+        let steps = Migrator::migrations()
+            .iter()
+            .position(|m| m.name() == Migration.name())
+            .expect("migration registered");
+        Migrator::up(db.db(), Some(steps as u32))
+            .await
+            .expect("migration");
+        db.db()
+            .execute_unprepared(
+                "INSERT INTO t_debox_group (id, account_id, url, group_name, status)
+                VALUES ('l3izdfzd', 1, 'https://m.debox.pro/group?id=l3izdfzd', 'group', 1)",
+            )
+            .await
+            .expect("insert group");
+
+        Migrator::up(db.db(), None).await.expect("migration");
+        let groups = debox_group::Entity::find()
+            .all(db.db())
+            .await
+            .expect("query groups");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].group_name, "group");
+
+        // 同一账号可关联多个群组
+        let group = debox_group::ActiveModel {
+            account_id: Set(1),
+            url: Set("https://m.debox.pro/group?id=2y9u8fkw".to_string()),
+            group_name: Set("group2".to_string()),
+            group_code: Set(String::new()),
+            status: Set(true),
+            ..Default::default()
+        }
+        .insert(db.db())
+        .await
+        .expect("insert group");
+        assert!(group.id > groups[0].id);
+    }
+}
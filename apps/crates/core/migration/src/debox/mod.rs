@@ -1,3 +1,4 @@
 //! DeBox 相关实体定义
 pub mod debox_account;
 pub mod debox_group;
+pub mod debox_group_id;
@@ -2,6 +2,12 @@ use sea_orm_migration::{MigrationTrait, MigratorTrait, async_trait};
 
 mod utils;
 
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+pub mod seed;
+pub use seed::{SeedReport, Seeder};
+
 mod debox;
 mod log;
 mod system;
@@ -19,6 +25,8 @@ impl MigratorTrait for Migrator {
             Box::new(user::email::Migration),
             Box::new(user::role::Migration),
             Box::new(user::user_role_rel::Migration),
+            Box::new(user::verify_token::Migration),
+            Box::new(user::phone_code::Migration),
             // DeBox 管理
             Box::new(debox::debox_account::Migration),
             Box::new(debox::debox_group::Migration),
@@ -28,10 +36,15 @@ impl MigratorTrait for Migrator {
             Box::new(system::dict_data::Migration),
             Box::new(system::image_captcha::Migration),
             Box::new(system::file_resource::Migration),
+            Box::new(system::file_resource_storage::Migration),
             // 日志管理
             Box::new(log::log_api_operation::Migration),
             Box::new(log::log_system::Migration),
+            Box::new(log::log_system_fts::Migration),
             Box::new(log::log_web::Migration),
+            // 数据表调整
+            Box::new(debox::debox_group_id::Migration),
+            Box::new(log::log_system_request_id::Migration),
         ]
     }
 }
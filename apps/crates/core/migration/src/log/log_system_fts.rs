@@ -0,0 +1,76 @@
+//! 系统日志全文索引表
+//!
+//! 仅 Sqlite 使用 FTS5 虚拟表对日志信息建立索引, 其他数据库使用 LIKE 查询
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DeriveMigrationName};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        // trigram 分词器支持任意子串匹配, 包括中文
+        db.execute_unprepared(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS t_log_system_fts USING fts5(
+                message,
+                content = 't_log_system',
+                content_rowid = 'id',
+                tokenize = 'trigram'
+            )",
+        )
+        .await?;
+
+        // 同步触发器
+        db.execute_unprepared(
+            "CREATE TRIGGER IF NOT EXISTS t_log_system_fts_ai AFTER INSERT ON t_log_system BEGIN
+                INSERT INTO t_log_system_fts(rowid, message) VALUES (new.id, new.message);
+            END",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE TRIGGER IF NOT EXISTS t_log_system_fts_ad AFTER DELETE ON t_log_system BEGIN
+                INSERT INTO t_log_system_fts(t_log_system_fts, rowid, message)
+                VALUES ('delete', old.id, old.message);
+            END",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE TRIGGER IF NOT EXISTS t_log_system_fts_au AFTER UPDATE ON t_log_system BEGIN
+                INSERT INTO t_log_system_fts(t_log_system_fts, rowid, message)
+                VALUES ('delete', old.id, old.message);
+                INSERT INTO t_log_system_fts(rowid, message) VALUES (new.id, new.message);
+            END",
+        )
+        .await?;
+
+        // 为已有日志建立索引
+        db.execute_unprepared("INSERT INTO t_log_system_fts(t_log_system_fts) VALUES ('rebuild')")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TRIGGER IF EXISTS t_log_system_fts_ai")
+            .await?;
+        db.execute_unprepared("DROP TRIGGER IF EXISTS t_log_system_fts_ad")
+            .await?;
+        db.execute_unprepared("DROP TRIGGER IF EXISTS t_log_system_fts_au")
+            .await?;
+        db.execute_unprepared("DROP TABLE IF EXISTS t_log_system_fts")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! 系统日志表请求ID
+//!
+//! 增加请求ID字段及索引, 用于关联同一请求的系统日志与API操作日志.
+use sea_orm::{
+    DeriveIden, DeriveMigrationName, Iden,
+    sea_query::{ColumnDef, Index, Table},
+};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LogSystem::Table)
+                    .add_column(
+                        ColumnDef::new(LogSystem::RequestId)
+                            .string()
+                            .string_len(36)
+                            .null()
+                            .default("")
+                            .comment("请求ID"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name(index_name())
+                    .table(LogSystem::Table)
+                    .col(LogSystem::RequestId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .if_exists()
+                    .name(index_name())
+                    .table(LogSystem::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LogSystem::Table)
+                    .drop_column(LogSystem::RequestId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// 请求ID索引名称
+fn index_name() -> String {
+    format!(
+        "idx_{}_{}",
+        LogSystem::Table.to_string(),
+        LogSystem::RequestId.to_string()
+    )
+}
+
+#[derive(DeriveIden)]
+enum LogSystem {
+    #[sea_orm(iden = "t_log_system")]
+    Table,
+    RequestId,
+}
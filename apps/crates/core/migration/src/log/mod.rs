@@ -1,4 +1,6 @@
 //! 日志
 pub mod log_api_operation;
 pub mod log_system;
+pub mod log_system_fts;
+pub mod log_system_request_id;
 pub mod log_web;
@@ -0,0 +1,15 @@
+//! Mock 模拟测试
+
+use std::sync::Arc;
+
+use database::{PoolTrait, mock::Mock};
+use sea_orm_migration::MigratorTrait;
+
+use crate::Migrator;
+
+/// 创建内存数据库并执行所有迁移
+pub async fn mock_db() -> Arc<dyn PoolTrait> {
+    let db = Mock::builder().await.expect("connect db").build();
+    Migrator::up(db.db(), None).await.expect("migration");
+    db
+}
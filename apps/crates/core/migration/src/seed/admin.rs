@@ -0,0 +1,102 @@
+//! 初始管理员
+
+use entity::user::{self, RoleEntity, UserBaseEntity, UserRoleRelEntity};
+use log::warn;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, JoinType, PaginatorTrait,
+    QueryFilter, QuerySelect, RelationTrait, Set,
+};
+use uuid::Uuid;
+
+use utils::crypto::sha2_256;
+
+use super::role::ADMIN_ROLE_NAME;
+
+/// 初始管理员账号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminSeed {
+    /// 用户名
+    pub username: String,
+    /// 密码, 为空时随机生成
+    pub password: Option<String>,
+}
+
+/// 已创建的管理员账号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminCredential {
+    /// 用户ID
+    pub user_id: i32,
+    /// 用户名
+    pub username: String,
+    /// 密码
+    pub password: String,
+    /// 密码是否随机生成
+    pub generated: bool,
+}
+
+/// 不存在有效的管理员时创建初始管理员
+///
+/// 用户名已被非管理员使用时跳过创建, 需通过环境变量指定其他用户名.
+pub async fn seed<C: ConnectionTrait>(
+    db: &C,
+    admin: &AdminSeed,
+) -> Result<Option<AdminCredential>, DbErr> {
+    let role = RoleEntity::find()
+        .filter(user::role::Column::Name.eq(ADMIN_ROLE_NAME))
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("角色不存在, name: {ADMIN_ROLE_NAME}")))?;
+
+    let admins = UserRoleRelEntity::find()
+        .join(
+            JoinType::InnerJoin,
+            user::user_role_rel::Relation::Base.def(),
+        )
+        .filter(user::user_role_rel::Column::RoleId.eq(role.id))
+        .filter(user::user_base::Column::Status.eq(true))
+        .count(db)
+        .await?;
+    if admins > 0 {
+        return Ok(None);
+    }
+
+    let exists = UserBaseEntity::find()
+        .filter(user::user_base::Column::Username.eq(&admin.username))
+        .one(db)
+        .await?;
+    if exists.is_some() {
+        warn!(
+            "用户名已被非管理员使用, 跳过创建初始管理员, username: {}",
+            admin.username
+        );
+        return Ok(None);
+    }
+
+    let (password, generated) = match admin.password.clone() {
+        Some(v) if !v.is_empty() => (v, false),
+        _ => (Uuid::new_v4().simple().to_string()[..16].to_string(), true),
+    };
+    let model = user::user_base::ActiveModel {
+        username: Set(admin.username.clone()),
+        gender: Set(0),
+        password: Set(sha2_256(&password)),
+        status: Set(true),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    user::user_role_rel::ActiveModel {
+        user_id: Set(model.id),
+        role_id: Set(role.id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(Some(AdminCredential {
+        user_id: model.id,
+        username: model.username,
+        password,
+        generated,
+    }))
+}
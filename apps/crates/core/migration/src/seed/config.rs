@@ -0,0 +1,45 @@
+//! 预设系统配置
+//!
+//! 与 `system` 服务中声明的系统配置保持一致.
+
+use entity::system::{self, ConfigEntity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+
+/// 预设系统配置: (编码, 名称, 值)
+pub const DEFAULT_CONFIGS: &[(&str, &str, &str)] = &[
+    ("site_name", "站点名称", "Debox Pro Tools"),
+    ("user_register_enabled", "开放用户注册", "true"),
+    ("login_captcha_enabled", "登录验证码", "true"),
+    ("login_max_failures", "登录失败锁定次数", "5"),
+    ("notice_banner", "公告横幅", "{}"),
+];
+
+/// 添加缺失的预设系统配置, 已存在的配置保持不变, 返回添加的数量
+pub async fn seed<C: ConnectionTrait>(db: &C) -> Result<usize, DbErr> {
+    let mut total = 0;
+    for (sort, (code, name, value)) in DEFAULT_CONFIGS.iter().enumerate() {
+        let exists = ConfigEntity::find()
+            .filter(system::config::Column::Code.eq(*code))
+            .one(db)
+            .await?;
+        if exists.is_some() {
+            continue;
+        }
+
+        system::config::ActiveModel {
+            name: Set(name.to_string()),
+            code: Set(code.to_string()),
+            value: Set(Some(value.to_string())),
+            sort: Set(Some(sort as i32 + 1)),
+            status: Set(true),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        total += 1;
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,70 @@
+//! 预设字典维度
+
+use entity::system::{self, DictDimensionEntity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+
+/// 预设字典维度
+pub struct DictDimensionSeed {
+    /// 字典维度名称
+    pub name: &'static str,
+    /// 字典维度编码
+    pub code: &'static str,
+    /// 字典数据: (标签, 值)
+    pub data: &'static [(&'static str, &'static str)],
+}
+
+/// 预设字典维度列表
+pub const DEFAULT_DICT_DIMENSIONS: &[DictDimensionSeed] = &[
+    DictDimensionSeed {
+        name: "性别",
+        code: "gender",
+        data: &[("保密", "0"), ("女", "1"), ("男", "2")],
+    },
+    DictDimensionSeed {
+        name: "用户类型",
+        code: "user_type",
+        data: &[("用户名", "base"), ("手机号码", "phone"), ("邮箱", "email")],
+    },
+];
+
+/// 添加缺失的预设字典维度及其字典数据, 已存在的维度保持不变, 返回添加的维度数量
+pub async fn seed<C: ConnectionTrait>(db: &C) -> Result<usize, DbErr> {
+    let mut total = 0;
+    for (sort, dimension) in DEFAULT_DICT_DIMENSIONS.iter().enumerate() {
+        let exists = DictDimensionEntity::find()
+            .filter(system::dict_dimension::Column::Code.eq(dimension.code))
+            .one(db)
+            .await?;
+        if exists.is_some() {
+            continue;
+        }
+
+        let model = system::dict_dimension::ActiveModel {
+            name: Set(dimension.name.to_string()),
+            code: Set(dimension.code.to_string()),
+            sort: Set(Some(sort as i32 + 1)),
+            status: Set(true),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        for (sort, (label, value)) in dimension.data.iter().enumerate() {
+            system::dict_data::ActiveModel {
+                dim_id: Set(model.id),
+                label: Set(label.to_string()),
+                value: Set(value.to_string()),
+                sort: Set(Some(sort as i32 + 1)),
+                status: Set(true),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        total += 1;
+    }
+
+    Ok(total)
+}
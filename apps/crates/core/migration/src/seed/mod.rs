@@ -0,0 +1,148 @@
+//! 预设数据
+//!
+//! 创建预设角色、系统配置、字典维度及初始管理员, 已存在的数据保持不变, 可重复执行.
+//!
+//! 初始管理员仅在不存在有效的管理员时创建, 用户名及密码分别通过环境变量
+//! [`ADMIN_USERNAME_ENV`] 及 [`ADMIN_PASSWORD_ENV`] 指定, 未指定密码时随机生成,
+//! 由调用方输出. 用户名已被非管理员使用时跳过创建.
+use sea_orm::{DatabaseConnection, DbErr, TransactionTrait};
+
+mod admin;
+mod config;
+mod dict_dimension;
+mod role;
+
+pub use admin::{AdminCredential, AdminSeed};
+pub use config::DEFAULT_CONFIGS;
+pub use dict_dimension::{DEFAULT_DICT_DIMENSIONS, DictDimensionSeed};
+pub use role::{ADMIN_ROLE_NAME, DEFAULT_ROLES};
+
+/// 初始管理员用户名环境变量
+pub const ADMIN_USERNAME_ENV: &str = "DEBOX_ADMIN_USERNAME";
+/// 初始管理员密码环境变量
+pub const ADMIN_PASSWORD_ENV: &str = "DEBOX_ADMIN_PASSWORD";
+/// 初始管理员默认用户名
+pub const DEFAULT_ADMIN_USERNAME: &str = "admin";
+
+impl AdminSeed {
+    /// 从环境变量读取初始管理员账号
+    pub fn from_env() -> Self {
+        let username = std::env::var(ADMIN_USERNAME_ENV)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_ADMIN_USERNAME.to_string());
+        AdminSeed {
+            username,
+            password: std::env::var(ADMIN_PASSWORD_ENV).ok(),
+        }
+    }
+}
+
+/// 预设数据执行结果
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SeedReport {
+    /// 添加的角色数量
+    pub roles: usize,
+    /// 添加的系统配置数量
+    pub configs: usize,
+    /// 添加的字典维度数量
+    pub dict_dimensions: usize,
+    /// 创建的管理员账号
+    pub admin: Option<AdminCredential>,
+}
+
+/// 预设数据
+pub struct Seeder;
+
+impl Seeder {
+    /// 写入预设数据, 初始管理员账号从环境变量读取
+    pub async fn run(db: &DatabaseConnection) -> Result<SeedReport, DbErr> {
+        Self::run_with(db, &AdminSeed::from_env()).await
+    }
+
+    /// 写入预设数据
+    pub async fn run_with(db: &DatabaseConnection, admin: &AdminSeed) -> Result<SeedReport, DbErr> {
+        let txn = db.begin().await?;
+        let report = SeedReport {
+            roles: role::seed(&txn).await?,
+            configs: config::seed(&txn).await?,
+            dict_dimensions: dict_dimension::seed(&txn).await?,
+            admin: admin::seed(&txn, admin).await?,
+        };
+        txn.commit().await?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use entity::{
+        system::{ConfigEntity, DictDataEntity},
+        user::UserBaseEntity,
+    };
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    use super::*;
+    use crate::mock::mock_db;
+
+    #[tokio::test]
+    async fn test_seed() {
+        let db = mock_db().await;
+        let admin = AdminSeed {
+            username: "root".to_string(),
+            password: None,
+        };
+
+        let report = Seeder::run_with(db.db(), &admin).await.expect("seed");
+        // 预设角色已由迁移写入
+        assert_eq!(report.roles, 0);
+        assert_eq!(report.configs, DEFAULT_CONFIGS.len());
+        assert_eq!(report.dict_dimensions, DEFAULT_DICT_DIMENSIONS.len());
+        let credential = report.admin.expect("admin created");
+        assert_eq!(credential.username, "root");
+        assert!(credential.generated);
+        assert_eq!(credential.password.len(), 16);
+
+        let user = UserBaseEntity::find_by_id(credential.user_id)
+            .one(db.db())
+            .await
+            .expect("query user")
+            .expect("user exists");
+        assert_eq!(user.password, utils::crypto::sha2_256(&credential.password));
+
+        // 重复执行不再写入数据
+        let report = Seeder::run_with(db.db(), &admin).await.expect("seed again");
+        assert_eq!(report, SeedReport::default());
+        let configs = ConfigEntity::find().all(db.db()).await.expect("query");
+        assert_eq!(configs.len(), DEFAULT_CONFIGS.len());
+        let dict_data = DictDataEntity::find().all(db.db()).await.expect("query");
+        assert_eq!(dict_data.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_seed_username_taken() {
+        let db = mock_db().await;
+        entity::user::user_base::ActiveModel {
+            username: Set("admin".to_string()),
+            gender: Set(0),
+            password: Set("x".to_string()),
+            status: Set(true),
+            ..Default::default()
+        }
+        .insert(db.db())
+        .await
+        .expect("insert user");
+
+        let admin = AdminSeed {
+            username: "admin".to_string(),
+            password: Some("secret".to_string()),
+        };
+        let report = Seeder::run_with(db.db(), &admin).await.expect("seed");
+        assert!(report.admin.is_none());
+        // 其余预设数据正常写入
+        let configs = ConfigEntity::find().all(db.db()).await.expect("query");
+        assert_eq!(configs.len(), report.configs);
+        assert!(!configs.is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+//! 预设角色
+
+pub use entity::user::role::ADMIN_ROLE_NAME;
+use entity::user::{self, RoleEntity};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+
+/// 预设角色名称
+pub const DEFAULT_ROLES: &[&str] = &[
+    ADMIN_ROLE_NAME,
+    "普通用户",
+    "开发工程师",
+    "设计师",
+    "客服人员",
+];
+
+/// 添加缺失的预设角色, 返回添加的数量
+pub async fn seed<C: ConnectionTrait>(db: &C) -> Result<usize, DbErr> {
+    let mut total = 0;
+    for name in DEFAULT_ROLES {
+        let exists = RoleEntity::find()
+            .filter(user::role::Column::Name.eq(*name))
+            .one(db)
+            .await?;
+        if exists.is_some() {
+            continue;
+        }
+
+        user::role::ActiveModel {
+            name: Set(name.to_string()),
+            sort: Set(Some(1)),
+            status: Set(true),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        total += 1;
+    }
+
+    Ok(total)
+}
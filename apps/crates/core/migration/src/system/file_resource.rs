@@ -115,6 +115,8 @@ pub enum FileResource {
     Extension,
     ContentType,
     Size,
+    Storage,
+    Path,
     Desc,
     CreatedAt,
 }
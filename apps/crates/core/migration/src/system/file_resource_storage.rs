@@ -0,0 +1,130 @@
+//! 文件资源表存储方式
+//!
+//! 增加存储方式及本地文件路径, 扩展文件大小、HASH值及内容类型的长度.
+//! Sqlite 不限制字符串长度且整型为 64 位, 仅需增加字段.
+use sea_orm::{
+    DatabaseBackend, DeriveMigrationName,
+    sea_query::{ColumnDef, Table},
+};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+use super::file_resource::FileResource;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .add_column(
+                        ColumnDef::new(FileResource::Storage)
+                            .string()
+                            .string_len(16)
+                            .not_null()
+                            .default("db")
+                            .comment("存储方式(db:数据库,local:本地文件)"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .add_column(
+                        ColumnDef::new(FileResource::Path)
+                            .string()
+                            .string_len(255)
+                            .null()
+                            .comment("本地文件相对路径"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .modify_column(
+                        ColumnDef::new(FileResource::Hash)
+                            .string()
+                            .string_len(64)
+                            .not_null()
+                            .comment("文件HASH值, SHA-256"),
+                    )
+                    .modify_column(
+                        ColumnDef::new(FileResource::ContentType)
+                            .string()
+                            .string_len(100)
+                            .not_null()
+                            .comment("内容类型, text/html"),
+                    )
+                    .modify_column(
+                        ColumnDef::new(FileResource::Size)
+                            .big_integer()
+                            .not_null()
+                            .comment("文件文件大小，单位为字节"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .drop_column(FileResource::Path)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .drop_column(FileResource::Storage)
+                    .to_owned(),
+            )
+            .await?;
+
+        if manager.get_database_backend() == DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FileResource::Table)
+                    .modify_column(
+                        ColumnDef::new(FileResource::Hash)
+                            .string()
+                            .string_len(32)
+                            .not_null()
+                            .comment("文件HASH值"),
+                    )
+                    .modify_column(
+                        ColumnDef::new(FileResource::ContentType)
+                            .string()
+                            .string_len(20)
+                            .not_null()
+                            .comment("内容类型, text/html"),
+                    )
+                    .modify_column(
+                        ColumnDef::new(FileResource::Size)
+                            .integer()
+                            .not_null()
+                            .comment("文件文件大小，单位为字节"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -3,4 +3,5 @@ pub mod config;
 pub mod dict_data;
 pub mod dict_dimension;
 pub mod file_resource;
+pub mod file_resource_storage;
 pub mod image_captcha;
@@ -1,6 +1,8 @@
 //! 用户相关
 pub mod email;
 pub mod phone;
+pub mod phone_code;
 pub mod role;
 pub mod user_base;
 pub mod user_role_rel;
+pub mod verify_token;
@@ -0,0 +1,121 @@
+//! 用户短信验证码表
+//! Entity: [`entity::user::PhoneCode`]
+
+use sea_orm::{
+    DeriveIden, DeriveMigrationName,
+    sea_query::{ColumnDef, Expr, Table},
+};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+use crate::utils::if_not_exists_create_index;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Replace the sample below with your own migration scripts
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserPhoneCode::Table)
+                    .comment("用户短信验证码表")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserPhoneCode::Id)
+                            .integer()
+                            .primary_key()
+                            .auto_increment()
+                            .not_null()
+                            .comment("ID"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::Phone)
+                            .string()
+                            .string_len(20)
+                            .not_null()
+                            .comment("手机号码"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::Scene)
+                            .tiny_integer()
+                            .not_null()
+                            .comment("使用场景(0:注册,1:登录,2:绑定手机号)"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::CodeHash)
+                            .string()
+                            .string_len(64)
+                            .not_null()
+                            .comment("验证码摘要"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0)
+                            .comment("校验失败次数"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::ExpiredAt)
+                            .date_time()
+                            .not_null()
+                            .comment("过期时间"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::Status)
+                            .boolean()
+                            .not_null()
+                            .default(true)
+                            .comment("状态(false:失效,true:有效)"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                            .comment("创建时间"),
+                    )
+                    .col(
+                        ColumnDef::new(UserPhoneCode::UpdatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                            .comment("更新时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if_not_exists_create_index(
+            manager,
+            UserPhoneCode::Table,
+            vec![UserPhoneCode::Phone, UserPhoneCode::Scene],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Replace the sample below with your own migration scripts
+        manager
+            .drop_table(Table::drop().table(UserPhoneCode::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum UserPhoneCode {
+    #[sea_orm(iden = "t_user_phone_code")]
+    Table,
+    Id,
+    Phone,
+    Scene,
+    CodeHash,
+    Attempts,
+    ExpiredAt,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,120 @@
+//! 用户验证令牌表
+//! Entity: [`entity::user::VerifyToken`]
+
+use sea_orm::{
+    DeriveIden, DeriveMigrationName,
+    sea_query::{ColumnDef, Expr, Table},
+};
+use sea_orm_migration::{DbErr, MigrationTrait, SchemaManager, async_trait};
+
+use crate::utils::if_not_exists_create_index;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Replace the sample below with your own migration scripts
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserVerifyToken::Table)
+                    .comment("用户验证令牌表")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserVerifyToken::Id)
+                            .integer()
+                            .primary_key()
+                            .auto_increment()
+                            .not_null()
+                            .comment("令牌ID"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::UserId)
+                            .integer()
+                            .not_null()
+                            .comment("用户ID"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::TokenType)
+                            .tiny_integer()
+                            .not_null()
+                            .comment("令牌类型(0:邮箱验证,1:重置密码,2:绑定邮箱)"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::TokenHash)
+                            .string()
+                            .string_len(64)
+                            .unique_key()
+                            .not_null()
+                            .comment("令牌摘要"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::Payload)
+                            .string()
+                            .string_len(100)
+                            .null()
+                            .comment("附加数据, 如待绑定的邮箱"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::ExpiredAt)
+                            .date_time()
+                            .not_null()
+                            .comment("过期时间"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::UsedAt)
+                            .date_time()
+                            .null()
+                            .comment("使用时间"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                            .comment("创建时间"),
+                    )
+                    .col(
+                        ColumnDef::new(UserVerifyToken::UpdatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp())
+                            .comment("更新时间"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        if_not_exists_create_index(
+            manager,
+            UserVerifyToken::Table,
+            vec![UserVerifyToken::UserId, UserVerifyToken::TokenType],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Replace the sample below with your own migration scripts
+        manager
+            .drop_table(Table::drop().table(UserVerifyToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum UserVerifyToken {
+    #[sea_orm(iden = "t_user_verify_token")]
+    Table,
+    Id,
+    UserId,
+    TokenType,
+    TokenHash,
+    Payload,
+    ExpiredAt,
+    UsedAt,
+    CreatedAt,
+    UpdatedAt,
+}
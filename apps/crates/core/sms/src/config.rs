@@ -0,0 +1,57 @@
+//! 短信配置
+use serde::{Deserialize, Serialize};
+
+/// 短信配置
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SmsConfig {
+    /// 发送方式
+    #[serde(default)]
+    pub mode: Mode,
+    /// 短信服务商配置
+    #[serde(default)]
+    pub provider: ProviderConfig,
+}
+
+/// 发送方式
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    /// 短信服务商
+    #[serde(rename = "provider")]
+    Provider,
+    /// 仅输出日志, 用于开发环境
+    #[default]
+    #[serde(rename = "log")]
+    Log,
+}
+
+/// 短信服务商配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// 短信网关地址
+    pub url: String,
+    /// 访问密钥
+    pub access_key: String,
+    /// 短信签名
+    pub sign_name: String,
+    /// 验证码短信模板
+    pub template_code: String,
+    /// 请求超时时间, 秒
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_timeout() -> u64 {
+    5
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            access_key: String::new(),
+            sign_name: String::new(),
+            template_code: String::new(),
+            timeout: default_timeout(),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! 错误类型
+
+/// 错误种类
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("短信网关请求失败, {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("短信网关返回错误, status: {0}, body: {1}")]
+    Provider(u16, String),
+}
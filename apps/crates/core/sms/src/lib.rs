@@ -0,0 +1,30 @@
+//! 短信发送
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+pub mod config;
+pub mod error;
+mod log_sender;
+mod provider;
+
+pub use config::{Mode, ProviderConfig, SmsConfig};
+pub use error::Error;
+pub use log_sender::LogSender;
+pub use provider::ProviderSender;
+
+/// 短信发送器
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    /// 发送验证码短信
+    async fn send_code(&self, phone: &str, code: &str) -> Result<(), Error>;
+}
+
+/// 根据配置构建短信发送器
+pub fn build(config: &SmsConfig) -> Result<Arc<dyn SmsSender>, Error> {
+    let sender: Arc<dyn SmsSender> = match config.mode {
+        Mode::Provider => Arc::new(ProviderSender::new(&config.provider)?),
+        Mode::Log => Arc::new(LogSender::default()),
+    };
+    Ok(sender)
+}
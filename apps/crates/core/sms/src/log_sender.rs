@@ -0,0 +1,57 @@
+//! 日志短信发送, 用于开发环境
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::info;
+use tokio::sync::Mutex;
+
+use crate::{SmsSender, error::Error};
+
+/// 日志短信发送器
+///
+/// 短信不会真正发出, 仅输出到日志并保存在内存中, 可通过 [`LogSender::last_code_to`] 读取
+#[derive(Debug, Default, Clone)]
+pub struct LogSender {
+    codes: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl LogSender {
+    /// 获取最后一条发送给指定手机号的验证码
+    pub async fn last_code_to(&self, phone: &str) -> Option<String> {
+        self.codes
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|(to, _)| to == phone)
+            .map(|(_, code)| code.clone())
+    }
+}
+
+#[async_trait]
+impl SmsSender for LogSender {
+    async fn send_code(&self, phone: &str, code: &str) -> Result<(), Error> {
+        info!("短信验证码, phone: {}, code: {}", phone, code);
+        self.codes
+            .lock()
+            .await
+            .push((phone.to_string(), code.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_log_sender() {
+        let sender = LogSender::default();
+        sender.send_code("13800000000", "1234").await.expect("send");
+        sender.send_code("13800000000", "5678").await.expect("send");
+
+        let code = sender.last_code_to("13800000000").await;
+        assert_eq!(code, Some("5678".to_string()));
+        assert!(sender.last_code_to("13900000000").await.is_none());
+    }
+}
@@ -0,0 +1,56 @@
+//! 短信服务商
+use std::time::Duration;
+
+use async_trait::async_trait;
+use log::info;
+use serde_json::json;
+
+use crate::{SmsSender, config::ProviderConfig, error::Error};
+
+/// 短信服务商发送器
+///
+/// 以 JSON 格式请求通用的 HTTP 短信网关:
+/// `{"phone", "sign_name", "template_code", "template_param": {"code"}}`
+pub struct ProviderSender {
+    client: reqwest::Client,
+    config: ProviderConfig,
+}
+
+impl ProviderSender {
+    pub fn new(config: &ProviderConfig) -> Result<Self, Error> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .build()?;
+        Ok(ProviderSender {
+            client,
+            config: config.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl SmsSender for ProviderSender {
+    async fn send_code(&self, phone: &str, code: &str) -> Result<(), Error> {
+        let payload = json!({
+            "phone": phone,
+            "sign_name": self.config.sign_name,
+            "template_code": self.config.template_code,
+            "template_param": { "code": code },
+        });
+        let resp = self
+            .client
+            .post(&self.config.url)
+            .bearer_auth(&self.config.access_key)
+            .json(&payload)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(Error::Provider(status.as_u16(), body));
+        }
+        info!("验证码短信已发送, phone: {}", phone);
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 //! 加密解密工具集
+use std::io::{self, Read};
+
+use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha256};
 
 const SECRET: &str = "secret";
@@ -14,6 +17,39 @@ pub fn sha2_256(text: &str) -> String {
     format!("{:x}", hash)
 }
 
+/// HMAC-SHA256 签名
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 校验 HMAC-SHA256 签名, 以常量时间比较
+pub fn verify_hmac_sha256(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.verify_slice(tag).is_ok()
+}
+
+/// Sha2 256 摘要, 不加盐, 用于计算文件等数据的摘要
+pub fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Sha2 256 摘要, 分块读取数据, 用于计算大文件的摘要
+pub fn sha256_reader<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,4 +59,30 @@ mod tests {
         let key = sha2_256("123456xwedc");
         assert!(key == "7069cbbdd07d12dbf12dc9c858f6d11f18e4ecda89bfd8af92453b10667d3d35");
     }
+
+    #[test]
+    fn test_hmac_sha256() {
+        let tag = hmac_sha256(b"key", b"data");
+        assert_eq!(tag.len(), 32);
+        assert!(verify_hmac_sha256(b"key", b"data", &tag));
+        assert!(!verify_hmac_sha256(b"other", b"data", &tag));
+        assert!(!verify_hmac_sha256(b"key", b"data2", &tag));
+        assert!(!verify_hmac_sha256(b"key", b"data", &tag[..31]));
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        let key = sha256_hex(b"abc");
+        assert_eq!(
+            key,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sha256_reader() {
+        let data = vec![7u8; 20000];
+        let key = sha256_reader(data.as_slice()).unwrap();
+        assert_eq!(key, sha256_hex(&data));
+    }
 }
@@ -0,0 +1,152 @@
+//! 个人账户
+
+use axum_context::Context;
+use axum_response::{Responder, Response};
+use axum_typed_multipart::TypedMultipart;
+use axum_validator::{Extension, Json};
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::account::{
+        AccountProfileResp, BindEmailReq, BindEmailResp, BindPhoneReq, BindPhoneResp,
+        ChangePasswordReq, ChangePasswordResp, ConfirmBindEmailReq, ConfirmBindEmailResp,
+        UnbindEmailReq, UnbindEmailResp, UnbindPhoneReq, UnbindPhoneResp, UpdateAccountProfileReq,
+        UpdateAccountProfileResp, UploadAvatarReq, UploadAvatarResp,
+    },
+    service::account::AccountService,
+};
+
+/// 控制器
+pub struct AccountController;
+
+impl AccountController {
+    /// 获取个人信息
+    pub async fn profile(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+    ) -> Responder<AccountProfileResp> {
+        let account_service: AccountService = provider.provide();
+        let result = account_service.profile(ctx.get_user_id()).await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+
+    /// 更新个人信息
+    pub async fn update_profile(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<UpdateAccountProfileReq>,
+    ) -> Responder<UpdateAccountProfileResp> {
+        let account_service: AccountService = provider.provide();
+        account_service
+            .update_profile(ctx.get_user_id(), req)
+            .await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+
+    /// 修改密码
+    pub async fn change_password(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<ChangePasswordReq>,
+    ) -> Responder<ChangePasswordResp> {
+        let account_service: AccountService = provider.provide();
+        account_service
+            .change_password(ctx.get_user_id(), req)
+            .await?;
+
+        let resp = Response::<()>::ok().with_msg("密码修改成功").to_json()?;
+        Ok(resp)
+    }
+
+    /// 上传头像
+    pub async fn upload_avatar(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        TypedMultipart(req): TypedMultipart<UploadAvatarReq>,
+    ) -> Responder<UploadAvatarResp> {
+        let file_name = req.file.metadata.file_name.unwrap_or_default();
+        let account_service: AccountService = provider.provide();
+        let avatar = account_service
+            .upload_avatar(ctx.get_user_id(), file_name, req.file.contents.to_vec())
+            .await?;
+
+        let resp = Response::data(UploadAvatarResp { avatar }).to_json()?;
+        Ok(resp)
+    }
+}
+
+impl AccountController {
+    /// 绑定手机号
+    pub async fn bind_phone(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<BindPhoneReq>,
+    ) -> Responder<BindPhoneResp> {
+        let account_service: AccountService = provider.provide();
+        account_service.bind_phone(ctx.get_user_id(), req).await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+
+    /// 解绑手机号
+    pub async fn unbind_phone(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<UnbindPhoneReq>,
+    ) -> Responder<UnbindPhoneResp> {
+        let account_service: AccountService = provider.provide();
+        account_service.unbind_phone(ctx.get_user_id(), req).await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+
+    /// 绑定邮箱, 发送确认邮件
+    pub async fn bind_email(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<BindEmailReq>,
+    ) -> Responder<BindEmailResp> {
+        let account_service: AccountService = provider.provide();
+        account_service.bind_email(ctx.get_user_id(), req).await?;
+
+        let resp = Response::<()>::ok()
+            .with_msg("确认邮件已发送, 请前往邮箱完成绑定")
+            .to_json()?;
+        Ok(resp)
+    }
+
+    /// 确认绑定邮箱
+    pub async fn confirm_bind_email(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<ConfirmBindEmailReq>,
+    ) -> Responder<ConfirmBindEmailResp> {
+        let account_service: AccountService = provider.provide();
+        account_service
+            .confirm_bind_email(ctx.get_user_id(), req)
+            .await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+
+    /// 解绑邮箱
+    pub async fn unbind_email(
+        Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
+        Json(req): Json<UnbindEmailReq>,
+    ) -> Responder<UnbindEmailResp> {
+        let account_service: AccountService = provider.provide();
+        account_service.unbind_email(ctx.get_user_id(), req).await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+}
@@ -0,0 +1,24 @@
+//! 图片验证码
+
+use axum_response::{Responder, Response};
+use axum_validator::Extension;
+
+use inject::AInjectProvider;
+
+use crate::{dto::captcha::CaptchaResp, service::captcha::CaptchaService};
+
+/// 控制器
+pub struct CaptchaController;
+
+impl CaptchaController {
+    /// 获取图片验证码
+    pub async fn captcha(
+        Extension(provider): Extension<AInjectProvider>,
+    ) -> Responder<CaptchaResp> {
+        let captcha_service: CaptchaService = provider.provide();
+        let result = captcha_service.generate().await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+}
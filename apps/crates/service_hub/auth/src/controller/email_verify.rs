@@ -0,0 +1,42 @@
+//! 邮箱验证
+
+use axum_response::{Responder, Response};
+use axum_validator::{Extension, Json, Query};
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::email_verify::{
+        ResendVerifyEmailReq, ResendVerifyEmailResp, VerifyEmailReq, VerifyEmailResp,
+    },
+    service::email_verify::EmailVerifyService,
+};
+
+/// 控制器
+pub struct EmailVerifyController;
+
+impl EmailVerifyController {
+    /// 验证邮箱
+    pub async fn verify(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<VerifyEmailReq>,
+    ) -> Responder<VerifyEmailResp> {
+        let email_verify_service: EmailVerifyService = provider.provide();
+        email_verify_service.verify(req).await?;
+
+        let resp = Response::<()>::ok().with_msg("邮箱验证成功").to_json()?;
+        Ok(resp)
+    }
+
+    /// 重新发送邮箱验证邮件
+    pub async fn resend(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<ResendVerifyEmailReq>,
+    ) -> Responder<ResendVerifyEmailResp> {
+        let email_verify_service: EmailVerifyService = provider.provide();
+        email_verify_service.resend(req).await?;
+
+        let resp = Response::<()>::ok().to_json()?;
+        Ok(resp)
+    }
+}
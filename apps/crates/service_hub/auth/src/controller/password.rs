@@ -0,0 +1,42 @@
+//! 找回密码
+
+use axum_response::{Responder, Response};
+use axum_validator::{Extension, Json};
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::password::{ForgotPasswordReq, ForgotPasswordResp, ResetPasswordReq, ResetPasswordResp},
+    service::password::PasswordService,
+};
+
+/// 控制器
+pub struct PasswordController;
+
+impl PasswordController {
+    /// 忘记密码
+    pub async fn forgot_password(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<ForgotPasswordReq>,
+    ) -> Responder<ForgotPasswordResp> {
+        let password_service: PasswordService = provider.provide();
+        password_service.forgot_password(req).await?;
+
+        let resp = Response::<()>::ok()
+            .with_msg("如果该邮箱已注册, 重置密码邮件将发送至该邮箱")
+            .to_json()?;
+        Ok(resp)
+    }
+
+    /// 重置密码
+    pub async fn reset_password(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<ResetPasswordReq>,
+    ) -> Responder<ResetPasswordResp> {
+        let password_service: PasswordService = provider.provide();
+        password_service.reset_password(req).await?;
+
+        let resp = Response::<()>::ok().with_msg("密码重置成功").to_json()?;
+        Ok(resp)
+    }
+}
@@ -0,0 +1,30 @@
+//! 短信验证码
+
+use axum_response::{Responder, Response};
+use axum_validator::{Extension, Json};
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::phone_code::{SendSmsCodeReq, SendSmsCodeResp},
+    service::phone_code::PhoneCodeService,
+};
+
+/// 控制器
+pub struct PhoneCodeController;
+
+impl PhoneCodeController {
+    /// 发送短信验证码
+    pub async fn send(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<SendSmsCodeReq>,
+    ) -> Responder<SendSmsCodeResp> {
+        let phone_code_service: PhoneCodeService = provider.provide();
+        phone_code_service.send(req).await?;
+
+        let resp = Response::<()>::ok()
+            .with_msg("短信验证码已发送")
+            .to_json()?;
+        Ok(resp)
+    }
+}
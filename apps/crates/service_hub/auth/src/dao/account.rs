@@ -0,0 +1,78 @@
+//! 个人账户
+
+use std::sync::Arc;
+
+use nject::injectable;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+
+use database::PoolTrait;
+use entity::user::{EmailEntity, PhoneEntity, email, phone};
+
+/// 数据访问
+#[injectable]
+pub struct AccountDao {
+    db: Arc<dyn PoolTrait>,
+}
+
+impl AccountDao {
+    /// 绑定手机号, 替换用户已绑定的手机号
+    pub async fn bind_phone(&self, user_id: i32, phone: String) -> Result<phone::Model, DbErr> {
+        let txn = self.db.db().begin().await?;
+
+        PhoneEntity::delete_many()
+            .filter(phone::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await?;
+        let model = phone::ActiveModel {
+            user_id: Set(user_id),
+            phone: Set(phone),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(model)
+    }
+
+    /// 解绑手机号
+    pub async fn unbind_phone(&self, user_id: i32) -> Result<u64, DbErr> {
+        let result = PhoneEntity::delete_many()
+            .filter(phone::Column::UserId.eq(user_id))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// 解绑邮箱
+    pub async fn unbind_email(&self, user_id: i32) -> Result<u64, DbErr> {
+        let result = EmailEntity::delete_many()
+            .filter(email::Column::UserId.eq(user_id))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// 在事务中绑定邮箱, 替换用户已绑定的邮箱
+    pub async fn txn_bind_email(
+        &self,
+        txn: &DatabaseTransaction,
+        user_id: i32,
+        email: String,
+    ) -> Result<email::Model, DbErr> {
+        EmailEntity::delete_many()
+            .filter(email::Column::UserId.eq(user_id))
+            .exec(txn)
+            .await?;
+        email::ActiveModel {
+            user_id: Set(user_id),
+            email: Set(email),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await
+    }
+}
@@ -0,0 +1,47 @@
+//! 图片验证码
+
+use std::sync::Arc;
+
+use nject::injectable;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, sea_query::Expr};
+
+use database::PoolTrait;
+use entity::system::{ImageCaptchaEntity, image_captcha};
+
+/// 数据访问
+#[injectable]
+pub struct CaptchaDao {
+    db: Arc<dyn PoolTrait>,
+}
+
+impl CaptchaDao {
+    /// 添加验证码
+    pub async fn create(
+        &self,
+        active_model: image_captcha::ActiveModel,
+    ) -> Result<image_captcha::Model, DbErr> {
+        active_model.insert(self.db.db()).await
+    }
+
+    /// 根据验证码ID获取验证码信息
+    pub async fn info_by_captcha_id(
+        &self,
+        captcha_id: String,
+    ) -> Result<Option<image_captcha::Model>, DbErr> {
+        ImageCaptchaEntity::find()
+            .filter(image_captcha::Column::CaptchaId.eq(captcha_id))
+            .one(self.db.db())
+            .await
+    }
+
+    /// 作废验证码, 返回是否作废成功
+    pub async fn invalidate(&self, id: i32) -> Result<bool, DbErr> {
+        let result = ImageCaptchaEntity::update_many()
+            .col_expr(image_captcha::Column::Status, Expr::value(false))
+            .filter(image_captcha::Column::Id.eq(id))
+            .filter(image_captcha::Column::Status.eq(true))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -1,2 +1,6 @@
 //! 数据层
+pub mod account;
+pub mod captcha;
+pub mod phone_code;
 pub mod register;
+pub mod verify_token;
@@ -0,0 +1,81 @@
+//! 短信验证码
+
+use std::sync::Arc;
+
+use nject::injectable;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, sea_query::Expr,
+};
+
+use database::PoolTrait;
+use entity::user::{PhoneCodeEntity, phone_code};
+
+/// 数据访问
+#[injectable]
+pub struct PhoneCodeDao {
+    db: Arc<dyn PoolTrait>,
+}
+
+impl PhoneCodeDao {
+    /// 添加验证码
+    pub async fn create(
+        &self,
+        active_model: phone_code::ActiveModel,
+    ) -> Result<phone_code::Model, DbErr> {
+        active_model.insert(self.db.db()).await
+    }
+
+    /// 获取手机号指定场景下最近一次发送的验证码
+    pub async fn latest(
+        &self,
+        phone: String,
+        scene: i8,
+    ) -> Result<Option<phone_code::Model>, DbErr> {
+        PhoneCodeEntity::find()
+            .filter(phone_code::Column::Phone.eq(phone))
+            .filter(phone_code::Column::Scene.eq(scene))
+            .order_by_desc(phone_code::Column::Id)
+            .one(self.db.db())
+            .await
+    }
+
+    /// 作废手机号指定场景下的有效验证码
+    pub async fn invalidate(&self, phone: String, scene: i8) -> Result<u64, DbErr> {
+        let result = PhoneCodeEntity::update_many()
+            .col_expr(phone_code::Column::Status, Expr::value(false))
+            .filter(phone_code::Column::Phone.eq(phone))
+            .filter(phone_code::Column::Scene.eq(scene))
+            .filter(phone_code::Column::Status.eq(true))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// 使用验证码, 返回是否使用成功
+    pub async fn consume(&self, id: i32) -> Result<bool, DbErr> {
+        let result = PhoneCodeEntity::update_many()
+            .col_expr(phone_code::Column::Status, Expr::value(false))
+            .filter(phone_code::Column::Id.eq(id))
+            .filter(phone_code::Column::Status.eq(true))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// 占用一次校验次数, 返回是否占用成功
+    ///
+    /// 在同一条语句中判断并增加校验次数, 并发校验时总次数不会超过上限.
+    pub async fn acquire_attempt(&self, id: i32, max_attempts: i32) -> Result<bool, DbErr> {
+        let result = PhoneCodeEntity::update_many()
+            .col_expr(
+                phone_code::Column::Attempts,
+                Expr::col(phone_code::Column::Attempts).add(1),
+            )
+            .filter(phone_code::Column::Id.eq(id))
+            .filter(phone_code::Column::Status.eq(true))
+            .filter(phone_code::Column::Attempts.lt(max_attempts))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -7,6 +7,7 @@ use sea_orm::{ActiveModelTrait, DatabaseTransaction, DbErr, Set, TransactionTrai
 
 use database::PoolTrait;
 use entity::user::{email, phone, user_base};
+use user::enums::user_base::UserType;
 
 use crate::dto::register::RegisterReq;
 
@@ -50,7 +51,8 @@ impl RegisterDao {
             date_birth: Set(req.date_birth),
             avatar: Set(req.avatar),
             password: Set(req.password),
-            status: Set(true),
+            // 邮箱注册的用户在完成邮箱验证后启用
+            status: Set(req.register_type != UserType::Email),
             ..Default::default()
         };
         active_model.insert(txn).await
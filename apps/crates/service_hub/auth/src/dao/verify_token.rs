@@ -0,0 +1,154 @@
+//! 验证令牌
+
+use std::sync::Arc;
+
+use chrono::Local;
+use nject::injectable;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseTransaction, DbErr, EntityTrait, QueryFilter, Set,
+    TransactionTrait, sea_query::Expr,
+};
+
+use database::PoolTrait;
+use entity::user::{UserBaseEntity, VerifyTokenEntity, user_base, verify_token};
+
+use crate::dao::account::AccountDao;
+
+/// 数据访问
+#[injectable]
+pub struct VerifyTokenDao {
+    db: Arc<dyn PoolTrait>,
+    account_dao: AccountDao,
+}
+
+impl VerifyTokenDao {
+    /// 添加令牌
+    pub async fn create(
+        &self,
+        active_model: verify_token::ActiveModel,
+    ) -> Result<verify_token::Model, DbErr> {
+        active_model.insert(self.db.db()).await
+    }
+
+    /// 根据令牌摘要获取令牌信息
+    pub async fn info_by_hash(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<verify_token::Model>, DbErr> {
+        VerifyTokenEntity::find()
+            .filter(verify_token::Column::TokenHash.eq(token_hash))
+            .one(self.db.db())
+            .await
+    }
+
+    /// 作废用户指定类型的未使用令牌
+    pub async fn invalidate(&self, user_id: i32, token_type: i8) -> Result<u64, DbErr> {
+        let result = VerifyTokenEntity::update_many()
+            .col_expr(
+                verify_token::Column::UsedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .filter(verify_token::Column::UserId.eq(user_id))
+            .filter(verify_token::Column::TokenType.eq(token_type))
+            .filter(verify_token::Column::UsedAt.is_null())
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// 使用邮箱验证令牌并启用用户
+    pub async fn consume_and_enable_user(&self, id: i32, user_id: i32) -> Result<(), DbErr> {
+        let txn = self.db.db().begin().await?;
+
+        self.txn_consume(&txn, id).await?;
+        self.txn_update_user(
+            &txn,
+            user_base::ActiveModel {
+                id: Set(user_id),
+                status: Set(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 使用重置密码令牌并更新密码
+    pub async fn consume_and_reset_password(
+        &self,
+        id: i32,
+        user_id: i32,
+        password: String,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.db().begin().await?;
+
+        self.txn_consume(&txn, id).await?;
+        self.txn_update_user(
+            &txn,
+            user_base::ActiveModel {
+                id: Set(user_id),
+                password: Set(password),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 使用绑定邮箱令牌并绑定邮箱
+    pub async fn consume_and_bind_email(
+        &self,
+        id: i32,
+        user_id: i32,
+        email: String,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.db().begin().await?;
+
+        self.txn_consume(&txn, id).await?;
+        self.account_dao
+            .txn_bind_email(&txn, user_id, email)
+            .await?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// 标记令牌已使用, 已使用的令牌返回 [`DbErr::RecordNotUpdated`]
+    async fn txn_consume(&self, txn: &DatabaseTransaction, id: i32) -> Result<(), DbErr> {
+        let result = VerifyTokenEntity::update_many()
+            .col_expr(
+                verify_token::Column::UsedAt,
+                Expr::value(Local::now().naive_local()),
+            )
+            .filter(verify_token::Column::Id.eq(id))
+            .filter(verify_token::Column::UsedAt.is_null())
+            .exec(txn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotUpdated);
+        }
+        Ok(())
+    }
+
+    /// 更新用户信息
+    async fn txn_update_user(
+        &self,
+        txn: &DatabaseTransaction,
+        active_model: user_base::ActiveModel,
+    ) -> Result<(), DbErr> {
+        let id: i32 = *(active_model.id.clone().as_ref());
+        let result = UserBaseEntity::update_many()
+            .set(active_model)
+            .filter(user_base::Column::Id.eq(id))
+            .exec(txn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotUpdated);
+        }
+        Ok(())
+    }
+}
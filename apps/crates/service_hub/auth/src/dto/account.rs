@@ -0,0 +1,152 @@
+//! 个人账户
+
+use axum::body::Bytes;
+use axum_typed_multipart::{FieldData, TryFromMultipart};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use sea_orm::prelude::DateTime;
+use user::enums::user_base::Gender;
+
+/// 个人信息 响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountProfileResp {
+    /// 用户ID
+    pub id: i32,
+    /// 用户名称
+    pub username: String,
+    /// 真实姓名
+    pub real_name: Option<String>,
+    /// 性别(0:保密,1:女,2:男)
+    pub gender: i8,
+    /// 年龄
+    pub age: Option<i32>,
+    /// 出生日期
+    pub date_birth: Option<String>,
+    /// 头像URL
+    pub avatar: Option<String>,
+    /// 用户个人介绍
+    pub intro: Option<String>,
+    /// 用户描述
+    pub desc: Option<String>,
+    /// 绑定的手机号码
+    pub phone: Option<String>,
+    /// 绑定的邮箱
+    pub email: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime,
+}
+
+/// 更新个人信息 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateAccountProfileReq {
+    /// 真实姓名
+    #[validate(length(max = 32, message = "真实姓名不能超过32个字符"))]
+    pub real_name: Option<String>,
+    /// 性别(0:保密,1:女,2:男)
+    pub gender: Gender,
+    /// 年龄
+    #[validate(range(min = 18, max = 100, message = "年龄必须在18到100岁之间"))]
+    pub age: Option<i32>,
+    /// 出生日期
+    pub date_birth: Option<String>,
+    /// 头像URL
+    #[validate(length(max = 200, message = "头像URL不能超过200个字符"))]
+    pub avatar: Option<String>,
+    /// 用户个人介绍
+    #[validate(length(max = 200, message = "个人介绍不能超过200个字符"))]
+    pub intro: Option<String>,
+    /// 用户描述
+    #[validate(length(max = 200, message = "用户描述不能超过200个字符"))]
+    pub desc: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateAccountProfileResp {}
+
+/// 修改密码 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ChangePasswordReq {
+    /// 原密码
+    pub old_password: String,
+    /// 新密码
+    #[validate(length(min = 6, message = "密码至少需要6个字符"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangePasswordResp {}
+
+/// 绑定手机号 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct BindPhoneReq {
+    /// 手机号码
+    #[validate(length(min = 5, max = 16, message = "手机号码格式错误"))]
+    pub phone: String,
+    /// 短信验证码
+    pub sms_code: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BindPhoneResp {}
+
+/// 解绑手机号 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UnbindPhoneReq {
+    /// 登录密码
+    pub password: String,
+}
+
+/// 解绑手机号 响应体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnbindPhoneResp {}
+
+/// 绑定邮箱 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct BindEmailReq {
+    /// 邮箱
+    #[validate(
+        email(message = "邮箱格式错误"),
+        length(max = 50, message = "邮箱不能超过50个字符")
+    )]
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BindEmailResp {}
+
+/// 确认绑定邮箱 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ConfirmBindEmailReq {
+    /// 验证令牌
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfirmBindEmailResp {}
+
+/// 解绑邮箱 请求体
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UnbindEmailReq {
+    /// 登录密码
+    pub password: String,
+}
+
+/// 解绑邮箱 响应体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnbindEmailResp {}
+
+/// 上传头像 请求体
+#[derive(TryFromMultipart)]
+pub struct UploadAvatarReq {
+    /// 头像文件
+    #[form_data(limit = "2MiB")]
+    pub file: FieldData<Bytes>,
+}
+
+/// 上传头像 响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadAvatarResp {
+    /// 头像URL
+    pub avatar: String,
+}
@@ -0,0 +1,14 @@
+//! 图片验证码
+
+use serde::{Deserialize, Serialize};
+
+/// 图片验证码 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct CaptchaResp {
+    /// 验证码ID
+    pub captcha_id: String,
+    /// 图片数据, Base64编码
+    pub base_img: String,
+    /// 过期时间, 秒
+    pub expire: i16,
+}
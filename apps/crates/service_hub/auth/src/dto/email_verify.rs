@@ -0,0 +1,28 @@
+//! 邮箱验证
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 邮箱验证 请求体
+#[derive(Default, Clone, Deserialize, Validate)]
+pub struct VerifyEmailReq {
+    /// 验证令牌
+    #[validate(length(min = 1, message = "验证令牌不能为空"))]
+    pub token: String,
+}
+
+/// 邮箱验证 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct VerifyEmailResp {}
+
+/// 重新发送邮箱验证邮件 请求体
+#[derive(Default, Clone, Deserialize, Validate)]
+pub struct ResendVerifyEmailReq {
+    /// 邮箱
+    #[validate(email(message = "邮箱格式错误"))]
+    pub email: String,
+}
+
+/// 重新发送邮箱验证邮件 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct ResendVerifyEmailResp {}
@@ -20,7 +20,10 @@ pub struct LoginReq {
     pub blockchain_wallet: Option<String>,
 
     /// 登陆密码
+    #[serde(default)]
     pub password: String,
+    /// 短信验证码, 手机号用户可使用验证码代替密码登陆
+    pub sms_code: Option<String>,
     /// 验证码ID
     pub captcha_id: String,
     /// 验证码
@@ -0,0 +1,31 @@
+//! 找回密码
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 忘记密码 请求体
+#[derive(Default, Clone, Deserialize, Validate)]
+pub struct ForgotPasswordReq {
+    /// 邮箱
+    #[validate(email(message = "邮箱格式错误"))]
+    pub email: String,
+}
+
+/// 忘记密码 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct ForgotPasswordResp {}
+
+/// 重置密码 请求体
+#[derive(Default, Clone, Deserialize, Validate)]
+pub struct ResetPasswordReq {
+    /// 重置密码令牌
+    #[validate(length(min = 1, message = "重置密码令牌不能为空"))]
+    pub token: String,
+    /// 新密码
+    #[validate(length(min = 6, message = "密码至少需要6个字符"))]
+    pub password: String,
+}
+
+/// 重置密码 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct ResetPasswordResp {}
@@ -0,0 +1,26 @@
+//! 短信验证码
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use entity::user::phone_code::enums::CodeScene;
+
+/// 发送短信验证码 请求体
+#[derive(Clone, Deserialize, Validate)]
+pub struct SendSmsCodeReq {
+    /// 手机号码
+    #[validate(length(min = 5, max = 20, message = "手机号码格式错误"))]
+    pub phone: String,
+    /// 使用场景(0:注册,1:登录)
+    pub scene: CodeScene,
+    /// 验证码ID
+    #[validate(length(min = 1, message = "验证码ID不能为空"))]
+    pub captcha_id: String,
+    /// 验证码
+    #[validate(length(min = 1, message = "验证码不能为空"))]
+    pub captcha: String,
+}
+
+/// 发送短信验证码 响应体
+#[derive(Default, Deserialize, Serialize)]
+pub struct SendSmsCodeResp {}
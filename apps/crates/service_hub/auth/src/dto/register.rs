@@ -40,6 +40,9 @@ pub struct RegisterReq {
     /// 头像URL
     pub avatar: Option<String>,
 
+    /// 短信验证码, 手机号注册时必填
+    pub sms_code: Option<String>,
+
     // ==== 防止恶意注册 ====
     /// 验证码ID
     #[serde(default)]
@@ -4,10 +4,22 @@ pub mod dto;
 pub mod enums;
 
 pub(crate) mod service;
-pub use service::{login::LoginService, register::RegisterService};
+pub use service::{
+    account::AccountService, captcha::CaptchaService, email_verify::EmailVerifyService,
+    login::LoginService, password::PasswordService, phone_code::PhoneCodeService,
+    register::RegisterService, verify_token::VerifyTokenService,
+};
 
 pub(crate) mod controller;
-pub use controller::{login::LoginController, register::RegisterController};
+pub use controller::{
+    account::AccountController, captcha::CaptchaController, email_verify::EmailVerifyController,
+    login::LoginController, password::PasswordController, phone_code::PhoneCodeController,
+    register::RegisterController,
+};
 
 pub(crate) mod router;
-pub use router::{AuthRouter, login::LoginRouter, register::RegisterRouter};
+pub use router::{
+    AuthRouter, account::AccountRouter, captcha::CaptchaRouter, email_verify::EmailVerifyRouter,
+    login::LoginRouter, password::PasswordRouter, phone_code::PhoneCodeRouter,
+    register::RegisterRouter,
+};
@@ -0,0 +1,41 @@
+//! 个人账户
+
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+
+use crate::controller::account::AccountController;
+
+/// 路由器
+pub struct AccountRouter;
+
+impl AccountRouter {
+    /// 注册`个人账户`路由
+    ///
+    /// 仅操作当前登录用户的数据, 用户ID取自鉴权上下文
+    pub fn register() -> Router {
+        Router::new().nest(
+            "/user/me",
+            Router::new()
+                .route(
+                    "/",
+                    get(AccountController::profile).put(AccountController::update_profile),
+                )
+                .route("/password", put(AccountController::change_password))
+                .route("/avatar", post(AccountController::upload_avatar))
+                .route(
+                    "/phone",
+                    post(AccountController::bind_phone).delete(AccountController::unbind_phone),
+                )
+                .route(
+                    "/email",
+                    post(AccountController::bind_email).delete(AccountController::unbind_email),
+                )
+                .route(
+                    "/email/confirm",
+                    post(AccountController::confirm_bind_email),
+                ),
+        )
+    }
+}
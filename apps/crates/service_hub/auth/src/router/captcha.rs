@@ -0,0 +1,15 @@
+//! 图片验证码
+
+use axum::{Router, routing::get};
+
+use crate::controller::captcha::CaptchaController;
+
+/// 路由器
+pub struct CaptchaRouter;
+
+impl CaptchaRouter {
+    /// 注册`图片验证码`路由
+    pub fn register() -> Router {
+        Router::new().route("/captcha", get(CaptchaController::captcha))
+    }
+}
@@ -0,0 +1,20 @@
+//! 邮箱验证
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::controller::email_verify::EmailVerifyController;
+
+/// 路由器
+pub struct EmailVerifyRouter;
+
+impl EmailVerifyRouter {
+    /// 注册`邮箱验证`路由
+    pub fn register() -> Router {
+        Router::new()
+            .route("/verify-email", get(EmailVerifyController::verify))
+            .route("/verify-email/resend", post(EmailVerifyController::resend))
+    }
+}
@@ -1,8 +1,13 @@
 //! 路由层
 
 use axum::Router;
+pub mod account;
+pub mod captcha;
+pub mod email_verify;
 pub mod login;
 pub mod logout;
+pub mod password;
+pub mod phone_code;
 pub mod register;
 
 /// 路由器
@@ -14,9 +19,13 @@ impl AuthRouter {
         Router::new().nest(
             "/auth",
             Router::new()
+                .merge(captcha::CaptchaRouter::register()) // 图片验证码
+                .merge(phone_code::PhoneCodeRouter::register()) // 短信验证码
                 .merge(login::LoginRouter::register()) // 登陆
                 .merge(logout::LogoutRouter::register()) // 登出
-                .merge(register::RegisterRouter::register()), // 注册用户
+                .merge(register::RegisterRouter::register()) // 注册用户
+                .merge(email_verify::EmailVerifyRouter::register()) // 邮箱验证
+                .merge(password::PasswordRouter::register()), // 找回密码
         )
     }
 }
@@ -0,0 +1,20 @@
+//! 找回密码
+
+use axum::{Router, routing::post};
+
+use crate::controller::password::PasswordController;
+
+/// 路由器
+pub struct PasswordRouter;
+
+impl PasswordRouter {
+    /// 注册`找回密码`路由
+    pub fn register() -> Router {
+        Router::new()
+            .route(
+                "/forgot-password",
+                post(PasswordController::forgot_password),
+            )
+            .route("/reset-password", post(PasswordController::reset_password))
+    }
+}
@@ -0,0 +1,15 @@
+//! 短信验证码
+
+use axum::{Router, routing::post};
+
+use crate::controller::phone_code::PhoneCodeController;
+
+/// 路由器
+pub struct PhoneCodeRouter;
+
+impl PhoneCodeRouter {
+    /// 注册`短信验证码`路由
+    pub fn register() -> Router {
+        Router::new().route("/sms-code", post(PhoneCodeController::send))
+    }
+}
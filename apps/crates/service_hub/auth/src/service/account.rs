@@ -0,0 +1,369 @@
+//! 个人账户
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use log::error;
+use mailer::{Mail, Mailer};
+use nject::injectable;
+use sea_orm::{DbErr::RecordNotUpdated, Set};
+
+use entity::user::{phone_code::enums::CodeScene, user_base, verify_token::enums::TokenType};
+use err_code::{Error, ErrorMsg};
+use system::{FileResourceService, constant::FILE_RESOURCE_URL};
+use user::{EmailDao, PhoneDao, UserBaseDao};
+use utils::crypto::sha2_256;
+
+use crate::{
+    dao::{account::AccountDao, verify_token::VerifyTokenDao},
+    dto::account::{
+        AccountProfileResp, BindEmailReq, BindPhoneReq, ChangePasswordReq, ConfirmBindEmailReq,
+        UnbindEmailReq, UnbindPhoneReq, UpdateAccountProfileReq,
+    },
+    service::{phone_code::PhoneCodeService, verify_token::VerifyTokenService},
+};
+
+/// 绑定邮箱令牌有效期, 分钟
+const EMAIL_BIND_EXPIRE_MINUTES: i64 = 30;
+/// 头像文件最大大小, 字节
+const AVATAR_MAX_SIZE: usize = 2 * 1024 * 1024;
+
+/// 服务层
+#[injectable]
+pub struct AccountService {
+    user_dao: UserBaseDao,
+    phone_dao: PhoneDao,
+    email_dao: EmailDao,
+    account_dao: AccountDao,
+    verify_token_dao: VerifyTokenDao,
+    verify_token_service: VerifyTokenService,
+    phone_code_service: PhoneCodeService,
+    file_resource_service: FileResourceService,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl AccountService {
+    /// 获取个人信息
+    pub async fn profile(&self, user_id: i32) -> Result<AccountProfileResp, ErrorMsg> {
+        let user = self.user(user_id).await?;
+        let phone = self
+            .phone_dao
+            .info_by_user_id(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户手机号失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户手机号失败")
+            })?;
+        let email = self
+            .email_dao
+            .info_by_user_id(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户邮箱失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户邮箱失败")
+            })?;
+
+        Ok(AccountProfileResp {
+            id: user.id,
+            username: user.username,
+            real_name: user.real_name,
+            gender: user.gender,
+            age: user.age,
+            date_birth: user.date_birth,
+            avatar: user.avatar,
+            intro: user.intro,
+            desc: user.desc,
+            phone: phone.map(|v| v.phone),
+            email: email.map(|v| v.email),
+            created_at: user.created_at,
+        })
+    }
+
+    /// 更新个人信息
+    pub async fn update_profile(
+        &self,
+        user_id: i32,
+        req: UpdateAccountProfileReq,
+    ) -> Result<(), ErrorMsg> {
+        let model = user_base::ActiveModel {
+            id: Set(user_id),
+            real_name: Set(req.real_name),
+            gender: Set(req.gender as i8),
+            age: Set(req.age),
+            date_birth: Set(req.date_birth),
+            avatar: Set(req.avatar),
+            intro: Set(req.intro),
+            desc: Set(req.desc),
+            ..Default::default()
+        };
+        self.update_user(model).await
+    }
+
+    /// 修改密码, 需校验原密码
+    pub async fn change_password(
+        &self,
+        user_id: i32,
+        req: ChangePasswordReq,
+    ) -> Result<(), ErrorMsg> {
+        let user = self.user(user_id).await?;
+        if user.password != sha2_256(&req.old_password) {
+            error!("user_id: {user_id}, 原密码错误");
+            return Err(Error::UserOldPasswordError.into_err());
+        }
+
+        let model = user_base::ActiveModel {
+            id: Set(user_id),
+            password: Set(sha2_256(&req.new_password)),
+            ..Default::default()
+        };
+        self.update_user(model).await
+    }
+}
+
+/// 绑定手机号与邮箱
+impl AccountService {
+    /// 绑定手机号, 需校验绑定场景的短信验证码
+    pub async fn bind_phone(&self, user_id: i32, req: BindPhoneReq) -> Result<(), ErrorMsg> {
+        let phone = self
+            .phone_dao
+            .info_by_phone(req.phone.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户手机号失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户手机号失败")
+            })?;
+        if phone.is_some() {
+            error!("{} 该手机号码已被绑定", req.phone);
+            return Err(Error::DbDataExistError.into_err_with_msg("该手机号码已被绑定"));
+        }
+
+        self.phone_code_service
+            .check(req.phone.clone(), CodeScene::Bind, req.sms_code)
+            .await?;
+
+        self.account_dao
+            .bind_phone(user_id, req.phone)
+            .await
+            .map_err(|err| {
+                error!("绑定手机号失败, err: {:#?}", err);
+                Error::DbAddError.into_err_with_msg("绑定手机号失败")
+            })?;
+        Ok(())
+    }
+
+    /// 解绑手机号, 需校验登录密码, 且需保留已绑定的邮箱
+    pub async fn unbind_phone(&self, user_id: i32, req: UnbindPhoneReq) -> Result<(), ErrorMsg> {
+        self.check_password(user_id, &req.password).await?;
+
+        let email = self
+            .email_dao
+            .info_by_user_id(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户邮箱失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户邮箱失败")
+            })?;
+        if email.is_none() {
+            error!("user_id: {user_id}, 未绑定邮箱, 不能解绑手机号");
+            return Err(Error::InvalidParameter("unbind phone".to_string())
+                .into_err_with_msg("请先绑定邮箱, 至少需要保留一种联系方式"));
+        }
+
+        self.account_dao
+            .unbind_phone(user_id)
+            .await
+            .map_err(|err| {
+                error!("解绑手机号失败, err: {:#?}", err);
+                Error::DbDeleteError.into_err_with_msg("解绑手机号失败")
+            })?;
+        Ok(())
+    }
+
+    /// 绑定邮箱, 发送确认邮件
+    pub async fn bind_email(&self, user_id: i32, req: BindEmailReq) -> Result<(), ErrorMsg> {
+        self.check_email_unbound(req.email.clone()).await?;
+
+        let token = self
+            .verify_token_service
+            .issue_with_payload(
+                user_id,
+                TokenType::EmailBind,
+                Duration::minutes(EMAIL_BIND_EXPIRE_MINUTES),
+                Some(req.email.clone()),
+            )
+            .await?;
+
+        let link = format!(
+            "{}/bind-email?token={}",
+            self.mailer.base_url().trim_end_matches('/'),
+            token
+        );
+        let mail = Mail {
+            to: req.email,
+            subject: "绑定邮箱".to_string(),
+            body: format!(
+                "您好, 请点击以下链接完成邮箱绑定, 链接 {} 分钟内有效:\n\n{}\n\n如非本人操作, 请忽略此邮件。",
+                EMAIL_BIND_EXPIRE_MINUTES, link
+            ),
+        };
+        self.mailer.send(mail).await.map_err(|err| {
+            error!("{} 发送绑定邮箱邮件失败, err: {:#?}", user_id, err);
+            Error::MailSendError(err.to_string()).into_err_with_msg("发送绑定邮箱邮件失败")
+        })?;
+
+        Ok(())
+    }
+
+    /// 确认绑定邮箱, 令牌仅能由签发时的用户使用
+    pub async fn confirm_bind_email(
+        &self,
+        user_id: i32,
+        req: ConfirmBindEmailReq,
+    ) -> Result<(), ErrorMsg> {
+        let token = self
+            .verify_token_service
+            .check(&req.token, TokenType::EmailBind)
+            .await?;
+        if token.user_id != user_id {
+            error!("user_id: {user_id}, 绑定邮箱令牌不属于当前用户");
+            return Err(Error::VerifyTokenInvalid.into_err());
+        }
+        let email = token.payload.ok_or_else(|| {
+            error!("{} 绑定邮箱令牌缺少邮箱", token.id);
+            Error::VerifyTokenInvalid.into_err()
+        })?;
+
+        // 令牌签发后邮箱可能已被其他用户绑定
+        self.check_email_unbound(email.clone()).await?;
+
+        self.verify_token_dao
+            .consume_and_bind_email(token.id, user_id, email)
+            .await
+            .map_err(|err| {
+                if err == RecordNotUpdated {
+                    error!("{} 验证令牌已使用", token.id);
+                    return Error::VerifyTokenUsed.into_err();
+                }
+                error!("绑定邮箱失败, err: {:#?}", err);
+                Error::DbAddError.into_err_with_msg("绑定邮箱失败")
+            })?;
+        Ok(())
+    }
+
+    /// 解绑邮箱, 需校验登录密码, 且需保留已绑定的手机号
+    pub async fn unbind_email(&self, user_id: i32, req: UnbindEmailReq) -> Result<(), ErrorMsg> {
+        self.check_password(user_id, &req.password).await?;
+
+        let phone = self
+            .phone_dao
+            .info_by_user_id(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户手机号失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户手机号失败")
+            })?;
+        if phone.is_none() {
+            error!("user_id: {user_id}, 未绑定手机号, 不能解绑邮箱");
+            return Err(Error::InvalidParameter("unbind email".to_string())
+                .into_err_with_msg("请先绑定手机号, 至少需要保留一种联系方式"));
+        }
+
+        self.account_dao
+            .unbind_email(user_id)
+            .await
+            .map_err(|err| {
+                error!("解绑邮箱失败, err: {:#?}", err);
+                Error::DbDeleteError.into_err_with_msg("解绑邮箱失败")
+            })?;
+        Ok(())
+    }
+
+    /// 检查邮箱是否已被绑定
+    async fn check_email_unbound(&self, email: String) -> Result<(), ErrorMsg> {
+        let result = self
+            .email_dao
+            .info_by_email(email.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户邮箱失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户邮箱失败")
+            })?;
+        if result.is_some() {
+            error!("{email} 该邮箱已被绑定");
+            return Err(Error::DbDataExistError.into_err_with_msg("该邮箱已被绑定"));
+        }
+        Ok(())
+    }
+}
+
+/// 头像
+impl AccountService {
+    /// 上传头像, 文件保存至文件资源后更新用户头像
+    pub async fn upload_avatar(
+        &self,
+        user_id: i32,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Result<String, ErrorMsg> {
+        if data.len() > AVATAR_MAX_SIZE {
+            error!("user_id: {user_id}, 头像文件过大, size: {}", data.len());
+            return Err(Error::UploadFileError("头像文件不能超过2MB".to_string()).into_err());
+        }
+        if !infer::is_image(&data) {
+            error!("user_id: {user_id}, 头像文件不是图片");
+            return Err(Error::UploadFileError("头像必须为图片".to_string()).into_err());
+        }
+
+        let file = self.file_resource_service.upload(file_name, data).await?;
+        let avatar = format!("{}/{}", FILE_RESOURCE_URL, file.hash);
+
+        let model = user_base::ActiveModel {
+            id: Set(user_id),
+            avatar: Set(Some(avatar.clone())),
+            ..Default::default()
+        };
+        self.update_user(model).await?;
+
+        Ok(avatar)
+    }
+}
+
+impl AccountService {
+    /// 获取当前用户
+    async fn user(&self, user_id: i32) -> Result<user_base::Model, ErrorMsg> {
+        self.user_dao
+            .info(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("user_id: {user_id}, 用户不存在");
+                Error::DbQueryEmptyError.into_err_with_msg("用户不存在")
+            })
+    }
+
+    /// 校验当前用户的登录密码
+    async fn check_password(&self, user_id: i32, password: &str) -> Result<(), ErrorMsg> {
+        let user = self.user(user_id).await?;
+        if user.password != sha2_256(password) {
+            error!("user_id: {user_id}, 登录密码错误");
+            return Err(Error::LoginPasswordError.into_err_with_msg("登录密码错误"));
+        }
+        Ok(())
+    }
+
+    /// 更新当前用户信息
+    async fn update_user(&self, model: user_base::ActiveModel) -> Result<(), ErrorMsg> {
+        let rows = self.user_dao.update(model).await.map_err(|err| {
+            error!("更新用户信息失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("更新用户信息失败")
+        })?;
+        if rows == 0 {
+            error!("用户不存在");
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("用户不存在"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! 图片验证码
+
+use chrono::{Duration, Local};
+use log::error;
+use nject::injectable;
+use sea_orm::Set;
+use uuid::Uuid;
+
+use entity::system::image_captcha;
+use err_code::{Error, ErrorMsg};
+use utils::captcha::generate_captcha;
+
+use crate::{dao::captcha::CaptchaDao, dto::captcha::CaptchaResp};
+
+/// 图片验证码有效期, 秒
+const CAPTCHA_EXPIRE: i16 = 120;
+
+/// 服务层
+#[injectable]
+pub struct CaptchaService {
+    captcha_dao: CaptchaDao,
+}
+
+impl CaptchaService {
+    /// 生成图片验证码
+    pub async fn generate(&self) -> Result<CaptchaResp, ErrorMsg> {
+        let (captcha, base_img) = generate_captcha();
+        let captcha_id = Uuid::new_v4().to_string();
+
+        let model = image_captcha::ActiveModel {
+            captcha_id: Set(captcha_id.clone()),
+            captcha: Set(captcha),
+            data: Set(base_img.clone().into_bytes()),
+            expire: Set(CAPTCHA_EXPIRE),
+            status: Set(true),
+            ..Default::default()
+        };
+        self.captcha_dao.create(model).await.map_err(|err| {
+            error!("添加验证码失败, err: {:#?}", err);
+            Error::DbAddError.into_err_with_msg("添加验证码失败")
+        })?;
+
+        Ok(CaptchaResp {
+            captcha_id,
+            base_img,
+            expire: CAPTCHA_EXPIRE,
+        })
+    }
+
+    /// 校验图片验证码
+    ///
+    /// 验证码无论校验是否通过都会被作废, 防止被重复尝试
+    pub async fn check(&self, captcha_id: String, captcha: String) -> Result<(), ErrorMsg> {
+        let model = self
+            .captcha_dao
+            .info_by_captcha_id(captcha_id)
+            .await
+            .map_err(|err| {
+                error!("查询验证码失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询验证码失败")
+            })?
+            .ok_or_else(|| {
+                error!("验证码不存在");
+                Error::CaptchaNotExist.into_err()
+            })?;
+
+        let valid = self.captcha_dao.invalidate(model.id).await.map_err(|err| {
+            error!("作废验证码失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("作废验证码失败")
+        })?;
+        if !valid {
+            error!("{} 验证码已失效", model.id);
+            return Err(Error::CaptchaExpire.into_err());
+        }
+
+        let expired_at = model.created_at + Duration::seconds(model.expire as i64);
+        if expired_at < Local::now().naive_local() {
+            error!("{} 验证码已过期", model.id);
+            return Err(Error::CaptchaExpire.into_err());
+        }
+        if !model.captcha.eq_ignore_ascii_case(&captcha) {
+            error!("{} 验证码错误", model.id);
+            return Err(Error::CaptchaInvalid.into_err());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! 邮箱验证
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use log::{error, warn};
+use mailer::{Mail, Mailer};
+use nject::injectable;
+use sea_orm::DbErr::RecordNotUpdated;
+
+use entity::user::verify_token::enums::TokenType;
+use err_code::{Error, ErrorMsg};
+use user::{EmailDao, UserBaseDao};
+
+use crate::{
+    dao::verify_token::VerifyTokenDao,
+    dto::email_verify::{ResendVerifyEmailReq, VerifyEmailReq},
+    service::verify_token::VerifyTokenService,
+};
+
+/// 邮箱验证令牌有效期, 小时
+const EMAIL_VERIFY_EXPIRE_HOURS: i64 = 24;
+
+/// 服务层
+#[injectable]
+pub struct EmailVerifyService {
+    user_dao: UserBaseDao,
+    email_dao: EmailDao,
+    verify_token_dao: VerifyTokenDao,
+    verify_token_service: VerifyTokenService,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl EmailVerifyService {
+    /// 发送邮箱验证邮件
+    pub async fn send(&self, user_id: i32, email: String) -> Result<(), ErrorMsg> {
+        let token = self
+            .verify_token_service
+            .issue(
+                user_id,
+                TokenType::EmailVerify,
+                Duration::hours(EMAIL_VERIFY_EXPIRE_HOURS),
+            )
+            .await?;
+
+        let link = format!(
+            "{}/api/v1/auth/verify-email?token={}",
+            self.mailer.base_url().trim_end_matches('/'),
+            token
+        );
+        let mail = Mail {
+            to: email,
+            subject: "邮箱验证".to_string(),
+            body: format!(
+                "您好, 请点击以下链接完成邮箱验证, 链接 {} 小时内有效:\n\n{}\n\n如非本人操作, 请忽略此邮件。",
+                EMAIL_VERIFY_EXPIRE_HOURS, link
+            ),
+        };
+        self.mailer.send(mail).await.map_err(|err| {
+            error!("{} 发送邮箱验证邮件失败, err: {:#?}", user_id, err);
+            Error::MailSendError(err.to_string()).into_err_with_msg("发送邮箱验证邮件失败")
+        })?;
+
+        Ok(())
+    }
+
+    /// 验证邮箱, 验证通过后启用用户
+    pub async fn verify(&self, req: VerifyEmailReq) -> Result<(), ErrorMsg> {
+        let token = self
+            .verify_token_service
+            .check(&req.token, TokenType::EmailVerify)
+            .await?;
+
+        self.verify_token_dao
+            .consume_and_enable_user(token.id, token.user_id)
+            .await
+            .map_err(|err| {
+                if err == RecordNotUpdated {
+                    error!("{} 验证令牌已使用", token.id);
+                    return Error::VerifyTokenUsed.into_err();
+                }
+                error!("邮箱验证失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("邮箱验证失败")
+            })?;
+
+        Ok(())
+    }
+
+    /// 重新发送邮箱验证邮件
+    ///
+    /// 邮箱不存在或用户已启用时不返回错误, 防止被用于探测邮箱
+    pub async fn resend(&self, req: ResendVerifyEmailReq) -> Result<(), ErrorMsg> {
+        let email = self
+            .email_dao
+            .info_by_email(req.email.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户邮箱失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户邮箱失败")
+            })?;
+        let email = match email {
+            Some(v) => v,
+            None => {
+                warn!("{} 邮箱未注册, 忽略重新发送", req.email);
+                return Ok(());
+            }
+        };
+
+        let user = self.user_dao.info(email.user_id).await.map_err(|err| {
+            error!("查询用户信息失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+        })?;
+        match user {
+            Some(user) if !user.status => self.send(user.id, email.email).await,
+            _ => {
+                warn!("{} 用户不存在或已启用, 忽略重新发送", email.user_id);
+                Ok(())
+            }
+        }
+    }
+}
@@ -4,12 +4,16 @@ use log::error;
 use nject::injectable;
 
 use axum_jwt::Claims;
-use entity::user::user_base;
+use entity::user::{phone_code::enums::CodeScene, user_base};
 use err_code::{Error, ErrorMsg};
+use utils::crypto::sha2_256;
 
 use user::{EmailDao, PhoneDao, UserBaseDao, enums::user_base::UserType};
 
-use crate::dto::login::{LoginReq, LoginResp};
+use crate::{
+    dto::login::{LoginReq, LoginResp},
+    service::phone_code::PhoneCodeService,
+};
 
 /// 服务层
 #[injectable]
@@ -17,6 +21,7 @@ pub struct LoginService {
     user_dao: UserBaseDao,
     email_dao: EmailDao,
     phone_dao: PhoneDao,
+    phone_code_service: PhoneCodeService,
 }
 
 impl LoginService {
@@ -29,11 +34,21 @@ impl LoginService {
             error!("{} 用户已被禁用", user.id);
             return Err(Error::LoginUserDisableError.into_err_with_msg("用户已被禁用"));
         }
-        // 检测密码
-        if user.password != req.password {
-            error!("{} 账号或密码错误", user.id);
+        match (&req.user_type, req.sms_code.clone(), req.phone.clone()) {
+            // 检测手机验证码
+            (UserType::Phone, Some(sms_code), Some(phone)) => {
+                self.phone_code_service
+                    .check(phone, CodeScene::Login, sms_code)
+                    .await?;
+            }
+            // 检测密码
+            _ => {
+                if user.password != sha2_256(&req.password) {
+                    error!("{} 账号或密码错误", user.id);
 
-            return Err(Error::LoginPasswordError.into_err_with_msg("账号或密码错误"));
+                    return Err(Error::LoginPasswordError.into_err_with_msg("账号或密码错误"));
+                }
+            }
         }
 
         // JWT
@@ -1,4 +1,10 @@
 //! 服务层
+pub mod account;
+pub mod captcha;
+pub mod email_verify;
 pub mod login;
 pub mod logout;
+pub mod password;
+pub mod phone_code;
 pub mod register;
+pub mod verify_token;
@@ -0,0 +1,122 @@
+//! 找回密码
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use log::{error, warn};
+use mailer::{Mail, Mailer};
+use nject::injectable;
+use sea_orm::DbErr::RecordNotUpdated;
+
+use entity::user::verify_token::enums::TokenType;
+use err_code::{Error, ErrorMsg};
+use user::{EmailDao, UserBaseDao};
+use utils::crypto::sha2_256;
+
+use crate::{
+    dao::verify_token::VerifyTokenDao,
+    dto::password::{ForgotPasswordReq, ResetPasswordReq},
+    service::verify_token::VerifyTokenService,
+};
+
+/// 重置密码令牌有效期, 分钟
+const PASSWORD_RESET_EXPIRE_MINUTES: i64 = 30;
+
+/// 服务层
+#[injectable]
+pub struct PasswordService {
+    user_dao: UserBaseDao,
+    email_dao: EmailDao,
+    verify_token_dao: VerifyTokenDao,
+    verify_token_service: VerifyTokenService,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl PasswordService {
+    /// 忘记密码, 发送重置密码邮件
+    ///
+    /// 邮箱不存在或用户被禁用时不返回错误, 防止被用于探测邮箱
+    pub async fn forgot_password(&self, req: ForgotPasswordReq) -> Result<(), ErrorMsg> {
+        let email = self
+            .email_dao
+            .info_by_email(req.email.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户邮箱失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户邮箱失败")
+            })?;
+        let email = match email {
+            Some(v) => v,
+            None => {
+                warn!("{} 邮箱未注册, 忽略找回密码", req.email);
+                return Ok(());
+            }
+        };
+
+        let user = self.user_dao.info(email.user_id).await.map_err(|err| {
+            error!("查询用户信息失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+        })?;
+        let user = match user {
+            Some(v) if v.status => v,
+            _ => {
+                warn!("{} 用户不存在或已被禁用, 忽略找回密码", email.user_id);
+                return Ok(());
+            }
+        };
+
+        let token = self
+            .verify_token_service
+            .issue(
+                user.id,
+                TokenType::PasswordReset,
+                Duration::minutes(PASSWORD_RESET_EXPIRE_MINUTES),
+            )
+            .await?;
+
+        let link = format!(
+            "{}/reset-password?token={}",
+            self.mailer.base_url().trim_end_matches('/'),
+            token
+        );
+        let mail = Mail {
+            to: email.email,
+            subject: "重置密码".to_string(),
+            body: format!(
+                "您好, {}:\n\n请点击以下链接重置密码, 链接 {} 分钟内有效:\n\n{}\n\n如非本人操作, 请忽略此邮件。",
+                user.username, PASSWORD_RESET_EXPIRE_MINUTES, link
+            ),
+        };
+        self.mailer.send(mail).await.map_err(|err| {
+            error!("{} 发送重置密码邮件失败, err: {:#?}", user.id, err);
+            Error::MailSendError(err.to_string()).into_err_with_msg("发送重置密码邮件失败")
+        })?;
+
+        Ok(())
+    }
+
+    /// 重置密码
+    pub async fn reset_password(&self, req: ResetPasswordReq) -> Result<(), ErrorMsg> {
+        let token = self
+            .verify_token_service
+            .check(&req.token, TokenType::PasswordReset)
+            .await?;
+
+        // 密码加密
+        let password = sha2_256(&req.password);
+
+        self.verify_token_dao
+            .consume_and_reset_password(token.id, token.user_id, password)
+            .await
+            .map_err(|err| {
+                if err == RecordNotUpdated {
+                    error!("{} 验证令牌已使用", token.id);
+                    return Error::VerifyTokenUsed.into_err();
+                }
+                error!("重置密码失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("重置密码失败")
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+//! 短信验证码
+
+use std::sync::Arc;
+
+use chrono::{Duration, Local};
+use log::error;
+use nject::injectable;
+use sea_orm::Set;
+use sms::SmsSender;
+use uuid::Uuid;
+
+use entity::user::phone_code::{self, enums::CodeScene};
+use err_code::{Error, ErrorMsg};
+use user::PhoneDao;
+use utils::crypto::sha2_256;
+
+use crate::{
+    dao::phone_code::PhoneCodeDao, dto::phone_code::SendSmsCodeReq,
+    service::captcha::CaptchaService,
+};
+
+/// 短信验证码有效期, 秒
+const PHONE_CODE_EXPIRE: i64 = 300;
+/// 短信验证码重新发送间隔, 秒
+const PHONE_CODE_RESEND_INTERVAL: i64 = 60;
+/// 短信验证码最大校验次数
+const PHONE_CODE_MAX_ATTEMPTS: i32 = 5;
+
+/// 生成6位数字验证码
+fn generate_code() -> String {
+    format!("{:06}", Uuid::new_v4().as_u128() % 1_000_000)
+}
+
+/// 服务层
+#[injectable]
+pub struct PhoneCodeService {
+    phone_dao: PhoneDao,
+    phone_code_dao: PhoneCodeDao,
+    captcha_service: CaptchaService,
+    sms: Arc<dyn SmsSender>,
+}
+
+impl PhoneCodeService {
+    /// 发送短信验证码
+    pub async fn send(&self, req: SendSmsCodeReq) -> Result<(), ErrorMsg> {
+        // 校验图片验证码, 防止被空刷短信
+        self.captcha_service
+            .check(req.captcha_id.clone(), req.captcha.clone())
+            .await?;
+
+        // 检查手机号注册状态
+        let phone = self
+            .phone_dao
+            .info_by_phone(req.phone.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+            })?;
+        match (req.scene, phone) {
+            (CodeScene::Register | CodeScene::Bind, Some(_)) => {
+                error!("该手机号码已注册");
+                return Err(Error::DbDataExistError.into_err_with_msg("该手机号码已注册"));
+            }
+            (CodeScene::Login, None) => {
+                error!("该用户手机号不存在");
+                return Err(Error::DbQueryEmptyError.into_err_with_msg("该用户手机号不存在"));
+            }
+            _ => {}
+        }
+
+        // 重新发送间隔
+        let scene = req.scene as i8;
+        let latest = self
+            .phone_code_dao
+            .latest(req.phone.clone(), scene)
+            .await
+            .map_err(|err| {
+                error!("查询短信验证码失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询短信验证码失败")
+            })?;
+        let now = Local::now().naive_local();
+        if let Some(latest) = latest
+            && latest.created_at + Duration::seconds(PHONE_CODE_RESEND_INTERVAL) > now
+        {
+            error!("{} 短信验证码发送过于频繁", req.phone);
+            return Err(Error::PhoneCodeTooFrequent.into_err());
+        }
+
+        // 作废历史验证码
+        self.phone_code_dao
+            .invalidate(req.phone.clone(), scene)
+            .await
+            .map_err(|err| {
+                error!("作废短信验证码失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("作废短信验证码失败")
+            })?;
+
+        let code = generate_code();
+        let model = phone_code::ActiveModel {
+            phone: Set(req.phone.clone()),
+            scene: Set(scene),
+            code_hash: Set(sha2_256(&code)),
+            attempts: Set(0),
+            expired_at: Set(now + Duration::seconds(PHONE_CODE_EXPIRE)),
+            status: Set(true),
+            ..Default::default()
+        };
+        self.phone_code_dao.create(model).await.map_err(|err| {
+            error!("添加短信验证码失败, err: {:#?}", err);
+            Error::DbAddError.into_err_with_msg("添加短信验证码失败")
+        })?;
+
+        self.sms.send_code(&req.phone, &code).await.map_err(|err| {
+            error!("{} 发送短信验证码失败, err: {:#?}", req.phone, err);
+            Error::SmsSendError(err.to_string()).into_err_with_msg("发送短信验证码失败")
+        })?;
+
+        Ok(())
+    }
+
+    /// 校验短信验证码, 校验通过后验证码失效
+    pub async fn check(
+        &self,
+        phone: String,
+        scene: CodeScene,
+        code: String,
+    ) -> Result<(), ErrorMsg> {
+        let model = self
+            .phone_code_dao
+            .latest(phone.clone(), scene as i8)
+            .await
+            .map_err(|err| {
+                error!("查询短信验证码失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询短信验证码失败")
+            })?
+            .filter(|v| v.status)
+            .ok_or_else(|| {
+                error!("{} 短信验证码不存在", phone);
+                Error::PhoneCodeInvalid.into_err()
+            })?;
+
+        if model.expired_at < Local::now().naive_local() {
+            error!("{} 短信验证码已过期", model.id);
+            return Err(Error::PhoneCodeExpire.into_err());
+        }
+
+        // 先占用校验次数再比较, 超过上限后验证码不再可用
+        let acquired = self
+            .phone_code_dao
+            .acquire_attempt(model.id, PHONE_CODE_MAX_ATTEMPTS)
+            .await
+            .map_err(|err| {
+                error!("更新短信验证码失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("更新短信验证码失败")
+            })?;
+        if !acquired {
+            error!("{} 短信验证码校验次数已达上限", model.id);
+            return Err(Error::PhoneCodeInvalid.into_err());
+        }
+
+        if model.code_hash != sha2_256(&code) {
+            error!("{} 短信验证码错误", model.id);
+            return Err(Error::PhoneCodeInvalid.into_err());
+        }
+
+        let consumed = self.phone_code_dao.consume(model.id).await.map_err(|err| {
+            error!("更新短信验证码失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("更新短信验证码失败")
+        })?;
+        if !consumed {
+            error!("{} 短信验证码已使用", model.id);
+            return Err(Error::PhoneCodeInvalid.into_err());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_code() {
+        for _ in 0..100 {
+            let code = generate_code();
+            assert_eq!(code.len(), 6);
+            assert!(code.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+}
@@ -3,11 +3,16 @@
 use log::error;
 use nject::injectable;
 
+use entity::user::phone_code::enums::CodeScene;
 use err_code::{Error, ErrorMsg};
 use user::{EmailDao, PhoneDao, UserBaseDao, enums::user_base::UserType};
 use utils::crypto::sha2_256;
 
-use crate::{dao::register::RegisterDao, dto::register::RegisterReq};
+use crate::{
+    dao::register::RegisterDao,
+    dto::register::RegisterReq,
+    service::{email_verify::EmailVerifyService, phone_code::PhoneCodeService},
+};
 
 /// 服务层
 #[injectable]
@@ -16,6 +21,8 @@ pub struct RegisterService {
     email_dao: EmailDao,
     phone_dao: PhoneDao,
     register_dao: RegisterDao,
+    email_verify_service: EmailVerifyService,
+    phone_code_service: PhoneCodeService,
 }
 
 impl RegisterService {
@@ -59,11 +66,16 @@ impl RegisterService {
         data.password = sha2_256(&data.password);
 
         // 添加用户
-        let _result = self.register_dao.add_user(data).await.map_err(|err| {
+        let user = self.register_dao.add_user(data).await.map_err(|err| {
             error!("注册用户失败, err: {:#?}", err);
             Error::DbAddError.into_err_with_msg("注册用户失败")
         })?;
 
+        // 邮箱注册, 发送验证链接, 确认完毕后启用用户
+        if let (UserType::Email, Some(email)) = (&req.register_type, req.email) {
+            self.email_verify_service.send(user.id, email).await?;
+        }
+
         Ok(())
     }
 
@@ -94,20 +106,36 @@ impl RegisterService {
             }
         };
 
-        // TODO 检测手机验证码, 待接入第三方服务
-
         // 检测是否已注册用户
-        let phone = self.phone_dao.info_by_phone(phone).await.map_err(|err| {
-            error!("查询用户信息失败, err: {:#?}", err);
-            Error::DbQueryError.into_err_with_msg("查询用户信息失败")
-        })?;
-        if phone.is_some() {
+        let user = self
+            .phone_dao
+            .info_by_phone(phone.clone())
+            .await
+            .map_err(|err| {
+                error!("查询用户信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+            })?;
+        if user.is_some() {
             {
                 error!("该手机号码已注册");
                 return Err(Error::DbDataExistError.into_err_with_msg("该手机号码已注册"));
             };
         }
 
+        // 检测手机验证码
+        let sms_code = match req.sms_code {
+            Some(v) => v,
+            None => {
+                return Err(
+                    Error::InvalidParameter("请求参数错误, sms_code 不能为空".to_string())
+                        .into_err(),
+                );
+            }
+        };
+        self.phone_code_service
+            .check(phone, CodeScene::Register, sms_code)
+            .await?;
+
         Ok(())
     }
 
@@ -134,8 +162,6 @@ impl RegisterService {
             };
         }
 
-        // TODO 邮箱验证, 发送链接点击后确认, 确认完毕后启用用户
-
         Ok(())
     }
 }
@@ -0,0 +1,266 @@
+//! 验证令牌
+//!
+//! 令牌格式: `base64(user_id.token_type.expired_at.nonce).base64(signature)`,
+//! 签名为使用 `auth.token_secret` 密钥计算的 HMAC-SHA256, 用于快速拒绝伪造或过期的令牌,
+//! 数据库中只保存令牌摘要, 用于保证令牌仅能使用一次。
+use std::sync::OnceLock;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Local};
+use log::{error, warn};
+use nject::injectable;
+use sea_orm::Set;
+use uuid::Uuid;
+
+use config::AppConfig;
+use entity::user::verify_token::{self, enums::TokenType};
+use err_code::{Error, ErrorMsg};
+use utils::crypto::{hmac_sha256, sha2_256, verify_hmac_sha256};
+
+use crate::dao::verify_token::VerifyTokenDao;
+
+/// 令牌签名密钥
+static TOKEN_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// 获取令牌签名密钥, 未配置时使用进程内随机密钥
+fn token_secret() -> &'static [u8] {
+    TOKEN_SECRET.get_or_init(|| {
+        let secret = AppConfig::instance()
+            .map(|config| config.auth.token_secret.clone())
+            .unwrap_or_default();
+        if !secret.is_empty() {
+            return secret.into_bytes();
+        }
+
+        warn!("未配置验证令牌签名密钥 auth.token_secret, 使用随机密钥, 重启后已签发的令牌失效");
+        [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat()
+    })
+}
+
+/// 令牌声明
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenClaims {
+    /// 用户ID
+    pub user_id: i32,
+    /// 令牌类型
+    pub token_type: TokenType,
+    /// 过期时间戳, 秒
+    pub expired_at: i64,
+}
+
+/// 签发令牌
+pub fn sign_token(claims: &TokenClaims, nonce: &str, secret: &[u8]) -> String {
+    let payload = format!(
+        "{}.{}.{}.{}",
+        claims.user_id, claims.token_type as i8, claims.expired_at, nonce
+    );
+    let payload = URL_SAFE_NO_PAD.encode(payload);
+    let signature = URL_SAFE_NO_PAD.encode(hmac_sha256(secret, payload.as_bytes()));
+    format!("{payload}.{signature}")
+}
+
+/// 解析并校验令牌签名与有效期
+pub fn parse_token(token: &str, now: i64, secret: &[u8]) -> Result<TokenClaims, Error> {
+    let (payload, signature) = token.split_once('.').ok_or(Error::VerifyTokenInvalid)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| Error::VerifyTokenInvalid)?;
+    if !verify_hmac_sha256(secret, payload.as_bytes(), &signature) {
+        return Err(Error::VerifyTokenInvalid);
+    }
+
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| Error::VerifyTokenInvalid)?;
+    let payload = String::from_utf8(payload).map_err(|_| Error::VerifyTokenInvalid)?;
+    let fields: Vec<&str> = payload.split('.').collect();
+    if fields.len() != 4 {
+        return Err(Error::VerifyTokenInvalid);
+    }
+
+    let user_id = fields[0]
+        .parse::<i32>()
+        .map_err(|_| Error::VerifyTokenInvalid)?;
+    let token_type = fields[1]
+        .parse::<i8>()
+        .ok()
+        .and_then(|v| TokenType::try_from(v).ok())
+        .ok_or(Error::VerifyTokenInvalid)?;
+    let expired_at = fields[2]
+        .parse::<i64>()
+        .map_err(|_| Error::VerifyTokenInvalid)?;
+    if expired_at <= now {
+        return Err(Error::VerifyTokenExpire);
+    }
+
+    Ok(TokenClaims {
+        user_id,
+        token_type,
+        expired_at,
+    })
+}
+
+/// 服务层
+#[injectable]
+pub struct VerifyTokenService {
+    verify_token_dao: VerifyTokenDao,
+}
+
+impl VerifyTokenService {
+    /// 签发令牌, 同时作废该用户同类型的未使用令牌
+    pub async fn issue(
+        &self,
+        user_id: i32,
+        token_type: TokenType,
+        expire: Duration,
+    ) -> Result<String, ErrorMsg> {
+        self.issue_with_payload(user_id, token_type, expire, None)
+            .await
+    }
+
+    /// 签发携带附加数据的令牌, 同时作废该用户同类型的未使用令牌
+    pub async fn issue_with_payload(
+        &self,
+        user_id: i32,
+        token_type: TokenType,
+        expire: Duration,
+        payload: Option<String>,
+    ) -> Result<String, ErrorMsg> {
+        self.verify_token_dao
+            .invalidate(user_id, token_type as i8)
+            .await
+            .map_err(|err| {
+                error!("作废历史令牌失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("作废历史令牌失败")
+            })?;
+
+        let expired_at = Local::now() + expire;
+        let claims = TokenClaims {
+            user_id,
+            token_type,
+            expired_at: expired_at.timestamp(),
+        };
+        let token = sign_token(
+            &claims,
+            &Uuid::new_v4().simple().to_string(),
+            token_secret(),
+        );
+
+        let model = verify_token::ActiveModel {
+            user_id: Set(user_id),
+            token_type: Set(token_type as i8),
+            token_hash: Set(sha2_256(&token)),
+            payload: Set(payload),
+            expired_at: Set(expired_at.naive_local()),
+            ..Default::default()
+        };
+        self.verify_token_dao.create(model).await.map_err(|err| {
+            error!("添加验证令牌失败, err: {:#?}", err);
+            Error::DbAddError.into_err_with_msg("添加验证令牌失败")
+        })?;
+
+        Ok(token)
+    }
+
+    /// 校验令牌, 返回未使用的令牌信息
+    pub async fn check(
+        &self,
+        token: &str,
+        token_type: TokenType,
+    ) -> Result<verify_token::Model, ErrorMsg> {
+        let claims =
+            parse_token(token, Local::now().timestamp(), token_secret()).map_err(|err| {
+                error!("验证令牌校验失败, err: {:#?}", err);
+                err.into_err()
+            })?;
+        if claims.token_type != token_type {
+            error!("验证令牌类型不匹配");
+            return Err(Error::VerifyTokenInvalid.into_err());
+        }
+
+        let model = self
+            .verify_token_dao
+            .info_by_hash(sha2_256(token))
+            .await
+            .map_err(|err| {
+                error!("查询验证令牌失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询验证令牌失败")
+            })?
+            .ok_or_else(|| {
+                error!("验证令牌不存在");
+                Error::VerifyTokenInvalid.into_err()
+            })?;
+
+        if model.used_at.is_some() {
+            error!("{} 验证令牌已使用", model.id);
+            return Err(Error::VerifyTokenUsed.into_err());
+        }
+        let expired_at: DateTime<Local> = model
+            .expired_at
+            .and_local_timezone(Local)
+            .single()
+            .ok_or_else(|| Error::VerifyTokenInvalid.into_err())?;
+        if expired_at <= Local::now() {
+            error!("{} 验证令牌已过期", model.id);
+            return Err(Error::VerifyTokenExpire.into_err());
+        }
+
+        Ok(model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"secret";
+
+    fn claims(expired_at: i64) -> TokenClaims {
+        TokenClaims {
+            user_id: 7,
+            token_type: TokenType::PasswordReset,
+            expired_at,
+        }
+    }
+
+    #[test]
+    fn test_sign_and_parse_token() {
+        let token = sign_token(&claims(200), "nonce", SECRET);
+        let result = parse_token(&token, 100, SECRET).expect("parse token");
+        assert_eq!(result, claims(200));
+    }
+
+    #[test]
+    fn test_parse_expired_token() {
+        let token = sign_token(&claims(100), "nonce", SECRET);
+        let result = parse_token(&token, 100, SECRET);
+        assert!(matches!(result, Err(Error::VerifyTokenExpire)));
+    }
+
+    #[test]
+    fn test_parse_forged_token() {
+        // 不知道密钥时无法构造有效的签名
+        let payload = URL_SAFE_NO_PAD.encode("7.1.200.nonce");
+        let signature = URL_SAFE_NO_PAD.encode(sha2_256(&payload));
+        let result = parse_token(&format!("{payload}.{signature}"), 100, SECRET);
+        assert!(matches!(result, Err(Error::VerifyTokenInvalid)));
+
+        let token = sign_token(&claims(200), "nonce", b"other");
+        let result = parse_token(&token, 100, SECRET);
+        assert!(matches!(result, Err(Error::VerifyTokenInvalid)));
+    }
+
+    #[test]
+    fn test_parse_tampered_token() {
+        let token = sign_token(&claims(200), "nonce", SECRET);
+        let (_, signature) = token.split_once('.').expect("split token");
+        let forged = URL_SAFE_NO_PAD.encode("1.1.200.nonce");
+        let result = parse_token(&format!("{forged}.{signature}"), 100, SECRET);
+        assert!(matches!(result, Err(Error::VerifyTokenInvalid)));
+
+        assert!(matches!(
+            parse_token("invalid", 100, SECRET),
+            Err(Error::VerifyTokenInvalid)
+        ));
+    }
+}
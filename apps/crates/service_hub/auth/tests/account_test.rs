@@ -0,0 +1,336 @@
+//! 个人账户测试
+
+use std::sync::Arc;
+
+use axum::Router;
+use database::PoolTrait;
+use entity::{
+    system::{ImageCaptchaEntity, image_captcha},
+    user::{phone_code::enums::CodeScene, user_base},
+};
+use err_code::Error;
+use inject::InjectProvider;
+use mailer::MemoryMailer;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
+use sms::LogSender;
+use system::FileResourceService;
+
+use auth::{
+    AccountRouter, AccountService, CaptchaService, PhoneCodeService,
+    dto::{
+        account::{
+            BindEmailReq, BindPhoneReq, ChangePasswordReq, ConfirmBindEmailReq, UnbindEmailReq,
+            UnbindPhoneReq, UpdateAccountProfileReq,
+        },
+        phone_code::SendSmsCodeReq,
+    },
+};
+use user::{UserBaseDao, UserRouter, enums::user_base::Gender};
+use utils::crypto::sha2_256;
+
+const PHONE: &str = "13900000000";
+const EMAIL: &str = "me@example.com";
+
+struct TestEnv {
+    provider: InjectProvider,
+    db: Arc<dyn PoolTrait>,
+    mailer: MemoryMailer,
+    sms: LogSender,
+}
+
+async fn setup() -> TestEnv {
+    let mailer = MemoryMailer::default();
+    let sms = LogSender::default();
+    let provider = InjectProvider::mock_with(mailer.clone(), sms.clone()).await;
+    let db = provider.provide();
+    TestEnv {
+        provider,
+        db,
+        mailer,
+        sms,
+    }
+}
+
+async fn add_user(env: &TestEnv, username: &str) -> i32 {
+    let user_dao: UserBaseDao = env.provider.provide();
+    let user = user_dao
+        .create(user_base::ActiveModel {
+            username: Set(username.to_string()),
+            password: Set(sha2_256("123456")),
+            status: Set(true),
+            gender: Set(0),
+            ..Default::default()
+        })
+        .await
+        .expect("add user");
+    user.id
+}
+
+/// 发送绑定手机号的短信验证码
+async fn send_bind_code(env: &TestEnv) -> String {
+    let captcha_service: CaptchaService = env.provider.provide();
+    let resp = captcha_service.generate().await.expect("generate captcha");
+    let captcha = ImageCaptchaEntity::find()
+        .filter(image_captcha::Column::CaptchaId.eq(resp.captcha_id.clone()))
+        .one(env.db.db())
+        .await
+        .expect("query captcha")
+        .expect("captcha exists");
+
+    let phone_code_service: PhoneCodeService = env.provider.provide();
+    phone_code_service
+        .send(SendSmsCodeReq {
+            phone: PHONE.to_string(),
+            scene: CodeScene::Bind,
+            captcha_id: resp.captcha_id,
+            captcha: captcha.captcha,
+        })
+        .await
+        .expect("send code");
+    env.sms.last_code_to(PHONE).await.expect("code sent")
+}
+
+/// 从邮件正文中提取令牌
+async fn last_token(mailer: &MemoryMailer) -> String {
+    let mail = mailer.last_mail_to(EMAIL).await.expect("mail sent");
+    let (_, rest) = mail.body.split_once("token=").expect("token in mail");
+    rest.split_whitespace().next().expect("token").to_string()
+}
+
+#[test]
+fn test_router_register() {
+    let _ = Router::<()>::new()
+        .merge(AccountRouter::register())
+        .merge(UserRouter::register());
+}
+
+#[tokio::test]
+async fn test_profile_and_password() {
+    let env = setup().await;
+    let user_id = add_user(&env, "user01").await;
+    let other_id = add_user(&env, "user02").await;
+    let account_service: AccountService = env.provider.provide();
+
+    account_service
+        .update_profile(
+            user_id,
+            UpdateAccountProfileReq {
+                real_name: Some("张三".to_string()),
+                gender: Gender::Male,
+                age: Some(20),
+                date_birth: None,
+                avatar: None,
+                intro: Some("hello".to_string()),
+                desc: None,
+            },
+        )
+        .await
+        .expect("update profile");
+    let profile = account_service.profile(user_id).await.expect("profile");
+    assert_eq!(profile.real_name.as_deref(), Some("张三"));
+    assert_eq!(profile.intro.as_deref(), Some("hello"));
+    assert_eq!(profile.gender, Gender::Male as i8);
+
+    // 仅更新当前用户
+    let other = account_service.profile(other_id).await.expect("profile");
+    assert_ne!(other.real_name.as_deref(), Some("张三"));
+
+    let err = account_service
+        .change_password(
+            user_id,
+            ChangePasswordReq {
+                old_password: "000000".to_string(),
+                new_password: "654321".to_string(),
+            },
+        )
+        .await
+        .expect_err("wrong old password");
+    assert_eq!(err.code(), Error::UserOldPasswordError.code());
+
+    account_service
+        .change_password(
+            user_id,
+            ChangePasswordReq {
+                old_password: "123456".to_string(),
+                new_password: "654321".to_string(),
+            },
+        )
+        .await
+        .expect("change password");
+    let user_dao: UserBaseDao = env.provider.provide();
+    let user = user_dao.info(user_id).await.expect("query").unwrap();
+    assert_eq!(user.password, sha2_256("654321"));
+    let other = user_dao.info(other_id).await.expect("query").unwrap();
+    assert_eq!(other.password, sha2_256("123456"));
+}
+
+#[tokio::test]
+async fn test_bind_phone_and_email() {
+    let env = setup().await;
+    let user_id = add_user(&env, "user01").await;
+    let other_id = add_user(&env, "user02").await;
+    let account_service: AccountService = env.provider.provide();
+
+    // 绑定手机号
+    let code = send_bind_code(&env).await;
+    account_service
+        .bind_phone(
+            user_id,
+            BindPhoneReq {
+                phone: PHONE.to_string(),
+                sms_code: code,
+            },
+        )
+        .await
+        .expect("bind phone");
+    let profile = account_service.profile(user_id).await.expect("profile");
+    assert_eq!(profile.phone.as_deref(), Some(PHONE));
+
+    // 已绑定的手机号不能被其他用户绑定
+    let err = account_service
+        .bind_phone(
+            other_id,
+            BindPhoneReq {
+                phone: PHONE.to_string(),
+                sms_code: "000000".to_string(),
+            },
+        )
+        .await
+        .expect_err("phone bound");
+    assert_eq!(err.code(), Error::DbDataExistError.code());
+
+    // 仅绑定了手机号时不能解绑
+    let err = account_service
+        .unbind_phone(
+            user_id,
+            UnbindPhoneReq {
+                password: "123456".to_string(),
+            },
+        )
+        .await
+        .expect_err("last contact");
+    assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+
+    // 绑定邮箱, 令牌不能被其他用户使用
+    account_service
+        .bind_email(
+            user_id,
+            BindEmailReq {
+                email: EMAIL.to_string(),
+            },
+        )
+        .await
+        .expect("bind email");
+    let token = last_token(&env.mailer).await;
+    let err = account_service
+        .confirm_bind_email(
+            other_id,
+            ConfirmBindEmailReq {
+                token: token.clone(),
+            },
+        )
+        .await
+        .expect_err("token of other user");
+    assert_eq!(err.code(), Error::VerifyTokenInvalid.code());
+
+    account_service
+        .confirm_bind_email(
+            user_id,
+            ConfirmBindEmailReq {
+                token: token.clone(),
+            },
+        )
+        .await
+        .expect("confirm bind email");
+    let profile = account_service.profile(user_id).await.expect("profile");
+    assert_eq!(profile.email.as_deref(), Some(EMAIL));
+
+    let err = account_service
+        .bind_email(
+            other_id,
+            BindEmailReq {
+                email: EMAIL.to_string(),
+            },
+        )
+        .await
+        .expect_err("email bound");
+    assert_eq!(err.code(), Error::DbDataExistError.code());
+
+    // 解绑需校验登录密码
+    let err = account_service
+        .unbind_email(
+            user_id,
+            UnbindEmailReq {
+                password: "654321".to_string(),
+            },
+        )
+        .await
+        .expect_err("wrong password");
+    assert_eq!(err.code(), Error::LoginPasswordError.code());
+
+    account_service
+        .unbind_email(
+            user_id,
+            UnbindEmailReq {
+                password: "123456".to_string(),
+            },
+        )
+        .await
+        .expect("unbind email");
+    let profile = account_service.profile(user_id).await.expect("profile");
+    assert_eq!(profile.email, None);
+    assert_eq!(profile.phone.as_deref(), Some(PHONE));
+
+    // 仅剩手机号时不能解绑
+    let err = account_service
+        .unbind_phone(
+            user_id,
+            UnbindPhoneReq {
+                password: "123456".to_string(),
+            },
+        )
+        .await
+        .expect_err("last contact");
+    assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+}
+
+#[tokio::test]
+async fn test_upload_avatar() {
+    let env = setup().await;
+    let user_id = add_user(&env, "user01").await;
+    let account_service: AccountService = env.provider.provide();
+
+    let err = account_service
+        .upload_avatar(user_id, "avatar.txt".to_string(), b"hello".to_vec())
+        .await
+        .expect_err("not an image");
+    assert_eq!(err.code(), Error::UploadFileError(String::new()).code());
+
+    // PNG 文件头及结束块
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    png.extend_from_slice(&[0; 17]);
+    png.extend_from_slice(b"\0\0\0\0IEND\xaeB`\x82");
+    let avatar = account_service
+        .upload_avatar(user_id, "avatar.png".to_string(), png.clone())
+        .await
+        .expect("upload avatar");
+    let profile = account_service.profile(user_id).await.expect("profile");
+    assert_eq!(profile.avatar.as_deref(), Some(avatar.as_str()));
+
+    // 相同内容的文件仅保存一份
+    let again = account_service
+        .upload_avatar(user_id, "avatar2.png".to_string(), png.clone())
+        .await
+        .expect("upload avatar");
+    assert_eq!(avatar, again);
+
+    let hash = avatar.rsplit('/').next().expect("hash").to_string();
+    let file_resource_service: FileResourceService = env.provider.provide();
+    let file = file_resource_service
+        .info_by_hash(hash)
+        .await
+        .expect("file resource");
+    assert_eq!(file.data, png);
+    assert_eq!(file.content_type, "image/png");
+    assert_eq!(file.file_name, "avatar.png");
+}
@@ -0,0 +1,133 @@
+//! 邮箱验证与找回密码测试
+
+use err_code::Error;
+use inject::InjectProvider;
+use mailer::MemoryMailer;
+use sms::LogSender;
+
+use auth::{
+    EmailVerifyService, PasswordService, RegisterService,
+    dto::{
+        email_verify::VerifyEmailReq,
+        password::{ForgotPasswordReq, ResetPasswordReq},
+        register::RegisterReq,
+    },
+};
+use user::{UserBaseDao, enums::user_base::UserType};
+use utils::crypto::sha2_256;
+
+const EMAIL: &str = "user@example.com";
+
+async fn provider(mailer: MemoryMailer) -> InjectProvider {
+    InjectProvider::mock_with(mailer, LogSender::default()).await
+}
+
+/// 从邮件正文中提取令牌
+async fn last_token(mailer: &MemoryMailer) -> String {
+    let mail = mailer.last_mail_to(EMAIL).await.expect("mail sent");
+    let (_, rest) = mail.body.split_once("token=").expect("token in mail");
+    rest.split_whitespace().next().expect("token").to_string()
+}
+
+#[tokio::test]
+async fn test_email_verify_and_reset_password() {
+    let mailer = MemoryMailer::default();
+    let provider = provider(mailer.clone()).await;
+
+    // 邮箱注册, 用户在验证前处于停用状态
+    let register_service: RegisterService = provider.provide();
+    register_service
+        .register(RegisterReq {
+            register_type: UserType::Email,
+            phone: None,
+            email: Some(EMAIL.to_string()),
+            blockchain_wallet: None,
+            password: "123456".to_string(),
+            username: "user01".to_string(),
+            real_name: None,
+            gender: 0,
+            age: None,
+            date_birth: None,
+            avatar: None,
+            sms_code: None,
+            captcha_id: String::new(),
+            captcha: String::new(),
+        })
+        .await
+        .expect("register");
+
+    let user_dao: UserBaseDao = provider.provide();
+    let user = user_dao
+        .info_by_username("user01".to_string())
+        .await
+        .expect("query user")
+        .expect("user exists");
+    assert!(!user.status);
+
+    // 验证邮箱后启用用户, 令牌仅能使用一次
+    let token = last_token(&mailer).await;
+    let email_verify_service: EmailVerifyService = provider.provide();
+    email_verify_service
+        .verify(VerifyEmailReq {
+            token: token.clone(),
+        })
+        .await
+        .expect("verify email");
+    let user = user_dao.info(user.id).await.expect("query user").unwrap();
+    assert!(user.status);
+
+    let err = email_verify_service
+        .verify(VerifyEmailReq { token })
+        .await
+        .expect_err("token reused");
+    assert_eq!(err.code(), Error::VerifyTokenUsed.code());
+
+    // 找回密码
+    let password_service: PasswordService = provider.provide();
+    password_service
+        .forgot_password(ForgotPasswordReq {
+            email: EMAIL.to_string(),
+        })
+        .await
+        .expect("forgot password");
+    let token = last_token(&mailer).await;
+
+    // 无效的令牌
+    let err = password_service
+        .reset_password(ResetPasswordReq {
+            token: "invalid".to_string(),
+            password: "654321".to_string(),
+        })
+        .await
+        .expect_err("invalid token");
+    assert_eq!(err.code(), Error::VerifyTokenInvalid.code());
+
+    password_service
+        .reset_password(ResetPasswordReq {
+            token: token.clone(),
+            password: "654321".to_string(),
+        })
+        .await
+        .expect("reset password");
+    let user = user_dao.info(user.id).await.expect("query user").unwrap();
+    assert_eq!(user.password, sha2_256("654321"));
+
+    let err = password_service
+        .reset_password(ResetPasswordReq {
+            token,
+            password: "abcdef".to_string(),
+        })
+        .await
+        .expect_err("token reused");
+    assert_eq!(err.code(), Error::VerifyTokenUsed.code());
+
+    // 未注册的邮箱不返回错误, 也不发送邮件
+    let sent = mailer.mails().await.len();
+    password_service
+        .forgot_password(ForgotPasswordReq {
+            email: "unknown@example.com".to_string(),
+        })
+        .await
+        .expect("forgot password");
+    assert_eq!(mailer.mails().await.len(), sent);
+}
@@ -0,0 +1,51 @@
+//! 账号密码登陆测试
+
+use std::sync::Arc;
+
+use database::PoolTrait;
+use err_code::Error;
+use inject::InjectProvider;
+use migration::{Seeder, seed::AdminSeed};
+
+use auth::{LoginService, dto::login::LoginReq};
+use user::enums::user_base::UserType;
+
+fn login_req(username: &str, password: &str) -> LoginReq {
+    LoginReq {
+        user_type: UserType::Base,
+        username: Some(username.to_string()),
+        password: password.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_login_seeded_admin() {
+    let provider = InjectProvider::mock().await;
+    let db: Arc<dyn PoolTrait> = provider.provide();
+    let admin = AdminSeed {
+        username: "admin".to_string(),
+        password: None,
+    };
+    let credential = Seeder::run_with(db.db(), &admin)
+        .await
+        .expect("seed")
+        .admin
+        .expect("admin created");
+    assert!(credential.generated);
+
+    let login_service: LoginService = provider.provide();
+    let resp = login_service
+        .login(login_req(&credential.username, &credential.password))
+        .await
+        .expect("login with generated password");
+    assert_eq!(resp.user_id, credential.user_id);
+    assert!(!resp.token.is_empty());
+
+    let err = login_service
+        .login(login_req(&credential.username, "wrong-password"))
+        .await
+        .err()
+        .expect("login with wrong password");
+    assert_eq!(err.code(), Error::LoginPasswordError.code());
+}
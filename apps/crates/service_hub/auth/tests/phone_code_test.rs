@@ -0,0 +1,189 @@
+//! 短信验证码注册与登陆测试
+
+use std::sync::Arc;
+
+use database::PoolTrait;
+use entity::{
+    system::{ImageCaptchaEntity, image_captcha},
+    user::{PhoneCodeEntity, phone_code, phone_code::enums::CodeScene},
+};
+use err_code::Error;
+use inject::InjectProvider;
+use mailer::MemoryMailer;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sms::LogSender;
+
+use auth::{
+    CaptchaService, LoginService, PhoneCodeService, RegisterService,
+    dto::{login::LoginReq, phone_code::SendSmsCodeReq, register::RegisterReq},
+};
+use user::enums::user_base::UserType;
+
+const PHONE: &str = "13800000000";
+
+async fn provider(sms: LogSender) -> (InjectProvider, Arc<dyn PoolTrait>) {
+    let provider = InjectProvider::mock_with(MemoryMailer::default(), sms).await;
+    let db = provider.provide();
+    (provider, db)
+}
+
+/// 生成图片验证码, 返回验证码ID及验证码
+async fn captcha(provider: &InjectProvider, db: &Arc<dyn PoolTrait>) -> (String, String) {
+    let captcha_service: CaptchaService = provider.provide();
+    let resp = captcha_service.generate().await.expect("generate captcha");
+    let model = ImageCaptchaEntity::find()
+        .filter(image_captcha::Column::CaptchaId.eq(resp.captcha_id.clone()))
+        .one(db.db())
+        .await
+        .expect("query captcha")
+        .expect("captcha exists");
+    (resp.captcha_id, model.captcha)
+}
+
+async fn send_code(
+    provider: &InjectProvider,
+    db: &Arc<dyn PoolTrait>,
+    scene: CodeScene,
+) -> Result<(), err_code::ErrorMsg> {
+    let (captcha_id, captcha) = captcha(provider, db).await;
+    let phone_code_service: PhoneCodeService = provider.provide();
+    phone_code_service
+        .send(SendSmsCodeReq {
+            phone: PHONE.to_string(),
+            scene,
+            captcha_id,
+            captcha,
+        })
+        .await
+}
+
+fn register_req(sms_code: Option<String>) -> RegisterReq {
+    RegisterReq {
+        register_type: UserType::Phone,
+        phone: Some(PHONE.to_string()),
+        email: None,
+        blockchain_wallet: None,
+        password: "123456".to_string(),
+        username: "user02".to_string(),
+        real_name: None,
+        gender: 0,
+        age: None,
+        date_birth: None,
+        avatar: None,
+        sms_code,
+        captcha_id: String::new(),
+        captcha: String::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_captcha_single_use() {
+    let (provider, db) = provider(LogSender::default()).await;
+    let (captcha_id, captcha) = captcha(&provider, &db).await;
+
+    let captcha_service: CaptchaService = provider.provide();
+    captcha_service
+        .check(captcha_id.clone(), captcha.to_lowercase())
+        .await
+        .expect("check captcha");
+    let err = captcha_service
+        .check(captcha_id, captcha)
+        .await
+        .expect_err("captcha reused");
+    assert_eq!(err.code(), Error::CaptchaExpire.code());
+}
+
+#[tokio::test]
+async fn test_phone_code_register_and_login() {
+    let sms = LogSender::default();
+    let (provider, db) = provider(sms.clone()).await;
+
+    // 发送注册验证码, 冷却时间内不能重复发送
+    send_code(&provider, &db, CodeScene::Register)
+        .await
+        .expect("send code");
+    let err = send_code(&provider, &db, CodeScene::Register)
+        .await
+        .expect_err("resend too frequent");
+    assert_eq!(err.code(), Error::PhoneCodeTooFrequent.code());
+    let code = sms.last_code_to(PHONE).await.expect("code sent");
+
+    // 注册
+    let register_service: RegisterService = provider.provide();
+    let err = register_service
+        .register(register_req(None))
+        .await
+        .expect_err("missing sms code");
+    assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+    let err = register_service
+        .register(register_req(Some("000000x".to_string())))
+        .await
+        .expect_err("wrong sms code");
+    assert_eq!(err.code(), Error::PhoneCodeInvalid.code());
+    register_service
+        .register(register_req(Some(code)))
+        .await
+        .expect("register");
+
+    // 验证码登陆
+    send_code(&provider, &db, CodeScene::Login)
+        .await
+        .expect("send code");
+    let code = sms.last_code_to(PHONE).await.expect("code sent");
+    let login_req = LoginReq {
+        user_type: UserType::Phone,
+        phone: Some(PHONE.to_string()),
+        sms_code: Some(code),
+        ..Default::default()
+    };
+    let login_service: LoginService = provider.provide();
+    let resp = login_service.login(login_req.clone()).await.expect("login");
+    assert_eq!(resp.username, "user02");
+
+    // 验证码仅能使用一次
+    let result = login_service.login(login_req).await;
+    assert_eq!(
+        result.err().map(|err| err.code()),
+        Some(Error::PhoneCodeInvalid.code())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_phone_code_max_attempts() {
+    let sms = LogSender::default();
+    let (provider, db) = provider(sms.clone()).await;
+    send_code(&provider, &db, CodeScene::Register)
+        .await
+        .expect("send code");
+    let code = sms.last_code_to(PHONE).await.expect("code sent");
+
+    // 并发提交错误的验证码, 校验次数不超过上限
+    let mut tasks = tokio::task::JoinSet::new();
+    for i in 0..20 {
+        let phone_code_service: PhoneCodeService = provider.provide();
+        tasks.spawn(async move {
+            phone_code_service
+                .check(PHONE.to_string(), CodeScene::Register, format!("x{i:05}"))
+                .await
+        });
+    }
+    while let Some(result) = tasks.join_next().await {
+        let err = result.expect("join task").expect_err("wrong sms code");
+        assert_eq!(err.code(), Error::PhoneCodeInvalid.code());
+    }
+    let model = PhoneCodeEntity::find()
+        .filter(phone_code::Column::Phone.eq(PHONE))
+        .one(db.db())
+        .await
+        .expect("query phone code")
+        .expect("phone code exists");
+    assert_eq!(model.attempts, 5);
+
+    // 达到上限后正确的验证码也无法使用
+    let phone_code_service: PhoneCodeService = provider.provide();
+    let err = phone_code_service
+        .check(PHONE.to_string(), CodeScene::Register, code)
+        .await
+        .expect_err("attempts exhausted");
+    assert_eq!(err.code(), Error::PhoneCodeInvalid.code());
+}
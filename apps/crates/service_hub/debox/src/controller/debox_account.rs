@@ -1,5 +1,6 @@
 //! DeBox账号管理
 
+use axum_context::Context;
 use axum_response::{Responder, Response};
 use axum_validator::{Extension, Json, Query};
 
@@ -22,10 +23,11 @@ impl DeboxAccountController {
     /// 获DeBox账号列表
     pub async fn list(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Query(req): Query<GetDeboxAccountsReq>,
     ) -> Responder<GetDeboxAccountsResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        let (results, total) = debox_account_service.list(req).await?;
+        let (results, total) = debox_account_service.list(ctx.get_user_id(), req).await?;
 
         let resp = Response::data_list(results, total).to_json()?;
         Ok(resp)
@@ -34,10 +36,11 @@ impl DeboxAccountController {
     /// 获取DeBox账号信息
     pub async fn info(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Query(req): Query<GetDeboxAccountReq>,
     ) -> Responder<GetDeboxAccountResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        let result = debox_account_service.info(req).await?;
+        let result = debox_account_service.info(ctx.get_user_id(), req).await?;
 
         let resp = Response::data(result).to_json()?;
         Ok(resp)
@@ -46,10 +49,11 @@ impl DeboxAccountController {
     /// 添加DeBox账号
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<CreateDeboxAccountReq>,
     ) -> Responder<CreateDeboxAccountResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        let _result = debox_account_service.create(req).await?;
+        let _result = debox_account_service.create(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -58,10 +62,11 @@ impl DeboxAccountController {
     /// 更新DeBox账号
     pub async fn update(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<UpdateDeboxAccountReq>,
     ) -> Responder<UpdateDeboxAccountResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        let _result = debox_account_service.update(req).await?;
+        let _result = debox_account_service.update(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -70,10 +75,13 @@ impl DeboxAccountController {
     /// 更新DeBox账号状态
     pub async fn update_status(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<UpdateDeboxAccountStatusReq>,
     ) -> Responder<UpdateDeboxAccountStatusResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        debox_account_service.update_status(req).await?;
+        debox_account_service
+            .update_status(ctx.get_user_id(), req)
+            .await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -82,10 +90,11 @@ impl DeboxAccountController {
     /// 删除DeBox账号
     pub async fn delete(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<DeleteDeboxAccountReq>,
     ) -> Responder<DeleteDeboxAccountResp> {
         let debox_account_service: DeboxAccountService = provider.provide();
-        let _result = debox_account_service.delete(req).await?;
+        let _result = debox_account_service.delete(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -1,5 +1,6 @@
 //! DeBox群组管理
 
+use axum_context::Context;
 use axum_response::{Responder, Response};
 use axum_validator::{Extension, Json, Query};
 
@@ -22,10 +23,11 @@ impl DeboxGroupController {
     /// 获DeBox群组列表
     pub async fn list(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Query(req): Query<GetDeboxGroupsReq>,
     ) -> Responder<GetDeboxGroupsResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        let (results, total) = debox_group_service.list(req).await?;
+        let (results, total) = debox_group_service.list(ctx.get_user_id(), req).await?;
 
         let resp = Response::data_list(results, total).to_json()?;
         Ok(resp)
@@ -34,10 +36,11 @@ impl DeboxGroupController {
     /// 获取DeBox群组信息
     pub async fn info(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Query(req): Query<GetDeboxGroupReq>,
     ) -> Responder<GetDeboxGroupResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        let result = debox_group_service.info(req).await?;
+        let result = debox_group_service.info(ctx.get_user_id(), req).await?;
 
         let resp = Response::data(result).to_json()?;
         Ok(resp)
@@ -46,10 +49,11 @@ impl DeboxGroupController {
     /// 添加DeBox群组
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<CreateDeboxGroupReq>,
     ) -> Responder<CreateDeboxGroupResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        let _result = debox_group_service.create(req).await?;
+        let _result = debox_group_service.create(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -58,10 +62,11 @@ impl DeboxGroupController {
     /// 更新DeBox群组
     pub async fn update(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<UpdateDeboxGroupReq>,
     ) -> Responder<UpdateDeboxGroupResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        let _result = debox_group_service.update(req).await?;
+        let _result = debox_group_service.update(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -70,10 +75,13 @@ impl DeboxGroupController {
     /// 更新DeBox群组状态
     pub async fn update_status(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<UpdateDeboxGroupStatusReq>,
     ) -> Responder<UpdateDeboxGroupStatusResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        debox_group_service.update_status(req).await?;
+        debox_group_service
+            .update_status(ctx.get_user_id(), req)
+            .await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
@@ -82,10 +90,11 @@ impl DeboxGroupController {
     /// 删除DeBox群组
     pub async fn delete(
         Extension(provider): Extension<AInjectProvider>,
+        ctx: Context,
         Json(req): Json<DeleteDeboxGroupReq>,
     ) -> Responder<DeleteDeboxGroupResp> {
         let debox_group_service: DeboxGroupService = provider.provide();
-        let _result = debox_group_service.delete(req).await?;
+        let _result = debox_group_service.delete(ctx.get_user_id(), req).await?;
 
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
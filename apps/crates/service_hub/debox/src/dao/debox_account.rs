@@ -10,7 +10,7 @@ use sea_orm::{
 use database::{Pagination, PoolTrait};
 use entity::debox::{DeboxAccountEntity, debox_account};
 
-use crate::dto::debox_account::GetDeboxAccountsReq;
+use crate::{dto::debox_account::GetDeboxAccountsReq, enums::DataScope};
 
 /// 数据访问
 #[injectable]
@@ -20,9 +20,12 @@ pub struct DeboxAccountDao {
 
 impl DeboxAccountDao {
     /// 获取所有数据
-    pub async fn all(&self) -> Result<(Vec<debox_account::Model>, u64), DbErr> {
+    pub async fn all(&self, scope: DataScope) -> Result<(Vec<debox_account::Model>, u64), DbErr> {
         let results = DeboxAccountEntity::find()
             .filter(debox_account::Column::Status.eq(true))
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
             .order_by_asc(debox_account::Column::Id)
             .all(self.db.db())
             .await?;
@@ -33,14 +36,18 @@ impl DeboxAccountDao {
     /// 获取数据列表
     pub async fn list(
         &self,
+        scope: DataScope,
         req: GetDeboxAccountsReq,
     ) -> Result<(Vec<debox_account::Model>, u64), DbErr> {
         let page = Pagination::new(req.page, req.page_size);
 
         let states = DeboxAccountEntity::find()
             .filter(debox_account::Column::Status.eq(true))
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
             .apply_if(req.user_id, |query, v| {
-                query.filter(debox_account::Column::UserId.ne(v))
+                query.filter(debox_account::Column::UserId.eq(v))
             })
             .apply_if(req.start_time, |query, v| {
                 query.filter(debox_account::Column::CreatedAt.gte(v))
@@ -65,8 +72,17 @@ impl DeboxAccountDao {
     }
 
     /// 获取详情信息
-    pub async fn info(&self, id: i32) -> Result<Option<debox_account::Model>, DbErr> {
-        DeboxAccountEntity::find_by_id(id).one(self.db.db()).await
+    pub async fn info(
+        &self,
+        scope: DataScope,
+        id: i32,
+    ) -> Result<Option<debox_account::Model>, DbErr> {
+        DeboxAccountEntity::find_by_id(id)
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
+            .one(self.db.db())
+            .await
     }
 
     /// 添加详情信息
@@ -78,11 +94,18 @@ impl DeboxAccountDao {
     }
 
     /// 更新数据
-    pub async fn update(&self, active_model: debox_account::ActiveModel) -> Result<u64, DbErr> {
+    pub async fn update(
+        &self,
+        scope: DataScope,
+        active_model: debox_account::ActiveModel,
+    ) -> Result<u64, DbErr> {
         let id: i32 = *(active_model.id.clone().as_ref());
         let result = DeboxAccountEntity::update_many()
             .set(active_model)
             .filter(debox_account::Column::Id.eq(id))
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
             .exec(self.db.db())
             .await?;
 
@@ -90,19 +113,36 @@ impl DeboxAccountDao {
     }
 
     /// 更新状态
-    pub async fn update_status(&self, id: i32, status: bool) -> Result<(), DbErr> {
+    pub async fn update_status(
+        &self,
+        scope: DataScope,
+        id: i32,
+        status: bool,
+    ) -> Result<(), DbErr> {
         let active_model = debox_account::ActiveModel {
-            id: Set(id),
             status: Set(status),
             ..Default::default()
         };
-        let _ = active_model.update(self.db.db()).await?;
+        let result = DeboxAccountEntity::update_many()
+            .set(active_model)
+            .filter(debox_account::Column::Id.eq(id))
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
+            .exec(self.db.db())
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotUpdated);
+        }
         Ok(())
     }
 
     /// 按主键删除信息
-    pub async fn delete(&self, id: i32) -> Result<u64, DbErr> {
+    pub async fn delete(&self, scope: DataScope, id: i32) -> Result<u64, DbErr> {
         let result = DeboxAccountEntity::delete_by_id(id)
+            .apply_if(scope.user_id(), |query, v| {
+                query.filter(debox_account::Column::UserId.eq(v))
+            })
             .exec(self.db.db())
             .await?;
         Ok(result.rows_affected)
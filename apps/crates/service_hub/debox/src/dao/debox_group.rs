@@ -4,13 +4,13 @@ use std::sync::Arc;
 use nject::injectable;
 use sea_orm::{
     ActiveModelTrait, ActiveValue::Set, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, sea_query::SimpleExpr,
 };
 
 use database::{Pagination, PoolTrait};
-use entity::debox::{DeboxGroupEntity, debox_group};
+use entity::debox::{DeboxAccountEntity, DeboxGroupEntity, debox_account, debox_group};
 
-use crate::dto::debox_group::GetDeboxGroupsReq;
+use crate::{dto::debox_group::GetDeboxGroupsReq, enums::DataScope};
 
 /// 数据访问
 #[injectable]
@@ -20,9 +20,10 @@ pub struct DeboxGroupDao {
 
 impl DeboxGroupDao {
     /// 获取所有数据
-    pub async fn all(&self) -> Result<(Vec<debox_group::Model>, u64), DbErr> {
+    pub async fn all(&self, scope: DataScope) -> Result<(Vec<debox_group::Model>, u64), DbErr> {
         let results = DeboxGroupEntity::find()
             .filter(debox_group::Column::Status.eq(true))
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
             .order_by_asc(debox_group::Column::Id)
             .all(self.db.db())
             .await?;
@@ -33,14 +34,16 @@ impl DeboxGroupDao {
     /// 获取数据列表
     pub async fn list(
         &self,
+        scope: DataScope,
         req: GetDeboxGroupsReq,
     ) -> Result<(Vec<debox_group::Model>, u64), DbErr> {
         let page = Pagination::new(req.page, req.page_size);
 
         let states = DeboxGroupEntity::find()
             .filter(debox_group::Column::Status.eq(true))
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
             .apply_if(req.account_id, |query, v| {
-                query.filter(debox_group::Column::AccountId.ne(v))
+                query.filter(debox_group::Column::AccountId.eq(v))
             })
             .apply_if(req.group_name, |query, v| {
                 query.filter(debox_group::Column::GroupName.like(format!("%{v}%")))
@@ -68,8 +71,15 @@ impl DeboxGroupDao {
     }
 
     /// 获取详情信息
-    pub async fn info(&self, id: i32) -> Result<Option<debox_group::Model>, DbErr> {
-        DeboxGroupEntity::find_by_id(id).one(self.db.db()).await
+    pub async fn info(
+        &self,
+        scope: DataScope,
+        id: i32,
+    ) -> Result<Option<debox_group::Model>, DbErr> {
+        DeboxGroupEntity::find_by_id(id)
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
+            .one(self.db.db())
+            .await
     }
 
     /// 添加详情信息
@@ -81,11 +91,16 @@ impl DeboxGroupDao {
     }
 
     /// 更新数据
-    pub async fn update(&self, active_model: debox_group::ActiveModel) -> Result<u64, DbErr> {
+    pub async fn update(
+        &self,
+        scope: DataScope,
+        active_model: debox_group::ActiveModel,
+    ) -> Result<u64, DbErr> {
         let id: i32 = *(active_model.id.clone().as_ref());
         let result = DeboxGroupEntity::update_many()
             .set(active_model)
             .filter(debox_group::Column::Id.eq(id))
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
             .exec(self.db.db())
             .await?;
 
@@ -93,21 +108,45 @@ impl DeboxGroupDao {
     }
 
     /// 更新状态
-    pub async fn update_status(&self, id: i32, status: bool) -> Result<(), DbErr> {
+    pub async fn update_status(
+        &self,
+        scope: DataScope,
+        id: i32,
+        status: bool,
+    ) -> Result<(), DbErr> {
         let active_model = debox_group::ActiveModel {
-            id: Set(id),
             status: Set(status),
             ..Default::default()
         };
-        let _ = active_model.update(self.db.db()).await?;
+        let result = DeboxGroupEntity::update_many()
+            .set(active_model)
+            .filter(debox_group::Column::Id.eq(id))
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
+            .exec(self.db.db())
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotUpdated);
+        }
         Ok(())
     }
 
     /// 按主键删除信息
-    pub async fn delete(&self, id: i32) -> Result<u64, DbErr> {
+    pub async fn delete(&self, scope: DataScope, id: i32) -> Result<u64, DbErr> {
         let result = DeboxGroupEntity::delete_by_id(id)
+            .apply_if(scope.user_id(), |query, v| query.filter(Self::owned_by(v)))
             .exec(self.db.db())
             .await?;
         Ok(result.rows_affected)
     }
+
+    /// 群组所属账号归属于指定用户
+    fn owned_by(user_id: i32) -> SimpleExpr {
+        debox_group::Column::AccountId.in_subquery(
+            DeboxAccountEntity::find()
+                .select_only()
+                .column(debox_account::Column::Id)
+                .filter(debox_account::Column::UserId.eq(user_id))
+                .into_query(),
+        )
+    }
 }
@@ -58,12 +58,12 @@ pub struct CreateDeboxAccountReq {
     pub debox_user_id: String,
     /// 用户钱包地址
     pub wallet_address: String,
-    /// ApiKey 状态(0:无效,1:有效)
-    pub api_key_status: String,
-    /// Access Token 状态(0:无效,1:有效)
-    pub access_token_status: String,
-    /// Web Token 状态(0:无效,1:有效)
-    pub web_token_status: String,
+    /// ApiKey 状态(false:无效,true:有效)
+    pub api_key_status: bool,
+    /// Access Token 状态(false:无效,true:有效)
+    pub access_token_status: bool,
+    /// Web Token 状态(false:无效,true:有效)
+    pub web_token_status: bool,
     /// 描述信息
     pub desc: Option<String>,
     /// 状态(false:停用,true:正常)
@@ -92,12 +92,12 @@ pub struct UpdateDeboxAccountReq {
     pub debox_user_id: String,
     /// 用户钱包地址
     pub wallet_address: String,
-    /// ApiKey 状态(0:无效,1:有效)
-    pub api_key_status: String,
-    /// Access Token 状态(0:无效,1:有效)
-    pub access_token_status: String,
-    /// Web Token 状态(0:无效,1:有效)
-    pub web_token_status: String,
+    /// ApiKey 状态(false:无效,true:有效)
+    pub api_key_status: bool,
+    /// Access Token 状态(false:无效,true:有效)
+    pub access_token_status: bool,
+    /// Web Token 状态(false:无效,true:有效)
+    pub web_token_status: bool,
     /// 描述信息
     pub desc: Option<String>,
     /// 状态(false:停用,true:正常)
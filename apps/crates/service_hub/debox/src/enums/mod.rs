@@ -1 +1,20 @@
 //! 枚举
+
+/// 数据权限范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataScope {
+    /// 所有数据
+    All,
+    /// 仅指定用户的数据
+    User(i32),
+}
+
+impl DataScope {
+    /// 返回需要限定的用户ID, 为`None`时不做限定
+    pub fn user_id(&self) -> Option<i32> {
+        match self {
+            DataScope::All => None,
+            DataScope::User(user_id) => Some(*user_id),
+        }
+    }
+}
@@ -6,7 +6,10 @@ pub(crate) mod dao;
 pub use dao::{debox_account::DeboxAccountDao, debox_group::DeboxGroupDao};
 
 pub(crate) mod service;
-pub use service::{debox_account::DeboxAccountService, debox_group::DeboxGroupService};
+pub use service::{
+    data_scope::DataScopeService, debox_account::DeboxAccountService,
+    debox_group::DeboxGroupService,
+};
 
 pub(crate) mod controller;
 pub use controller::{debox_account::DeboxAccountController, debox_group::DeboxGroupController};
@@ -0,0 +1,27 @@
+//! 数据权限范围
+
+use nject::injectable;
+
+use err_code::ErrorMsg;
+
+use user::UserRoleRelService;
+
+use crate::enums::DataScope;
+
+/// 服务层
+#[injectable]
+pub struct DataScopeService {
+    user_role_rel_service: UserRoleRelService,
+}
+
+impl DataScopeService {
+    /// 获取用户的数据权限范围
+    ///
+    /// 管理员可访问所有数据, 其他用户仅能访问自己的数据
+    pub async fn scope(&self, user_id: i32) -> Result<DataScope, ErrorMsg> {
+        if self.user_role_rel_service.is_admin(user_id).await? {
+            return Ok(DataScope::All);
+        }
+        Ok(DataScope::User(user_id))
+    }
+}
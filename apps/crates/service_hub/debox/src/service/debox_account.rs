@@ -13,41 +13,55 @@ use crate::{
         CreateDeboxAccountReq, DeleteDeboxAccountReq, GetDeboxAccountReq, GetDeboxAccountsReq,
         UpdateDeboxAccountReq, UpdateDeboxAccountStatusReq,
     },
+    service::data_scope::DataScopeService,
 };
 
 /// 服务层
 #[injectable]
 pub struct DeboxAccountService {
     debox_account_dao: DeboxAccountDao,
+    data_scope_service: DataScopeService,
 }
 
 impl DeboxAccountService {
     /// 获取列表数据
     pub async fn list(
         &self,
+        user_id: i32,
         req: GetDeboxAccountsReq,
     ) -> Result<(Vec<debox_account::Model>, u64), ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+
         // 获取所有数据
         if let Some(true) = req.all {
-            return self.debox_account_dao.all().await.map_err(|err| {
+            return self.debox_account_dao.all(scope).await.map_err(|err| {
                 error!("查询DeBox账号列表失败, err: {:#?}", err);
                 Error::DbQueryError.into_err_with_msg("查询DeBox账号列表失败")
             });
         }
 
-        let (results, total) = self.debox_account_dao.list(req).await.map_err(|err| {
-            error!("查询DeBox账号列表失败, err: {:#?}", err);
-            Error::DbQueryError.into_err_with_msg("查询DeBox账号列表失败")
-        })?;
+        let (results, total) = self
+            .debox_account_dao
+            .list(scope, req)
+            .await
+            .map_err(|err| {
+                error!("查询DeBox账号列表失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询DeBox账号列表失败")
+            })?;
 
         Ok((results, total))
     }
 
     /// 获取详情数据
-    pub async fn info(&self, req: GetDeboxAccountReq) -> Result<debox_account::Model, ErrorMsg> {
+    pub async fn info(
+        &self,
+        user_id: i32,
+        req: GetDeboxAccountReq,
+    ) -> Result<debox_account::Model, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         let result = self
             .debox_account_dao
-            .info(req.id)
+            .info(scope, req.id)
             .await
             .map_err(|err| {
                 error!("查询DeBox账号信息失败, err: {:#?}", err);
@@ -62,12 +76,16 @@ impl DeboxAccountService {
     }
 
     /// 添加数据
+    ///
+    /// 非管理员只能为自己添加账号
     pub async fn create(
         &self,
+        user_id: i32,
         req: CreateDeboxAccountReq,
     ) -> Result<debox_account::Model, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         let model = debox_account::ActiveModel {
-            user_id: Set(req.user_id),
+            user_id: Set(scope.user_id().unwrap_or(req.user_id)),
             api_key: Set(req.api_key),
             app_secret: Set(req.app_secret),
             access_token: Set(req.access_token),
@@ -90,10 +108,11 @@ impl DeboxAccountService {
     }
 
     /// 更新DeBox账号
-    pub async fn update(&self, req: UpdateDeboxAccountReq) -> Result<u64, ErrorMsg> {
+    pub async fn update(&self, user_id: i32, req: UpdateDeboxAccountReq) -> Result<u64, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         let model = debox_account::ActiveModel {
             id: Set(req.id),
-            user_id: Set(req.user_id),
+            user_id: Set(scope.user_id().unwrap_or(req.user_id)),
             api_key: Set(req.api_key),
             app_secret: Set(req.app_secret),
             access_token: Set(req.access_token),
@@ -108,18 +127,31 @@ impl DeboxAccountService {
             ..Default::default()
         };
 
-        let result = self.debox_account_dao.update(model).await.map_err(|err| {
-            error!("更新DeBox账号失败, err: {:#?}", err);
-            Error::DbUpdateError.into_err_with_msg("更新DeBox账号失败")
-        })?;
+        let result = self
+            .debox_account_dao
+            .update(scope, model)
+            .await
+            .map_err(|err| {
+                error!("更新DeBox账号失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("更新DeBox账号失败")
+            })?;
+        if result == 0 {
+            error!("更新DeBox账号失败, 该DeBox账号不存在");
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("DeBox账号不存在"));
+        }
 
         Ok(result)
     }
 
     /// 更新数据状态
-    pub async fn update_status(&self, req: UpdateDeboxAccountStatusReq) -> Result<(), ErrorMsg> {
+    pub async fn update_status(
+        &self,
+        user_id: i32,
+        req: UpdateDeboxAccountStatusReq,
+    ) -> Result<(), ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         self.debox_account_dao
-            .update_status(req.id, req.status)
+            .update_status(scope, req.id, req.status)
             .await
             .map_err(|err| {
                 if err == RecordNotUpdated {
@@ -135,11 +167,20 @@ impl DeboxAccountService {
     }
 
     /// 删除数据
-    pub async fn delete(&self, req: DeleteDeboxAccountReq) -> Result<u64, ErrorMsg> {
-        let result = self.debox_account_dao.delete(req.id).await.map_err(|err| {
-            error!("删除DeBox账号信息失败, err: {:#?}", err);
-            Error::DbDeleteError.into_err_with_msg("删除DeBox账号信息失败")
-        })?;
+    pub async fn delete(&self, user_id: i32, req: DeleteDeboxAccountReq) -> Result<u64, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+        let result = self
+            .debox_account_dao
+            .delete(scope, req.id)
+            .await
+            .map_err(|err| {
+                error!("删除DeBox账号信息失败, err: {:#?}", err);
+                Error::DbDeleteError.into_err_with_msg("删除DeBox账号信息失败")
+            })?;
+        if result == 0 {
+            error!("删除DeBox账号信息失败, 该DeBox账号不存在");
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("DeBox账号不存在"));
+        }
 
         Ok(result)
     }
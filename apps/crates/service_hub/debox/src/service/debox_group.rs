@@ -8,34 +8,41 @@ use entity::debox::debox_group;
 use err_code::{Error, ErrorMsg};
 
 use crate::{
-    dao::debox_group::DeboxGroupDao,
+    dao::{debox_account::DeboxAccountDao, debox_group::DeboxGroupDao},
     dto::debox_group::{
         CreateDeboxGroupReq, DeleteDeboxGroupReq, GetDeboxGroupReq, GetDeboxGroupsReq,
         UpdateDeboxGroupReq, UpdateDeboxGroupStatusReq,
     },
+    enums::DataScope,
+    service::data_scope::DataScopeService,
 };
 
 /// 服务层
 #[injectable]
 pub struct DeboxGroupService {
     debox_group_dao: DeboxGroupDao,
+    debox_account_dao: DeboxAccountDao,
+    data_scope_service: DataScopeService,
 }
 
 impl DeboxGroupService {
     /// 获取列表数据
     pub async fn list(
         &self,
+        user_id: i32,
         req: GetDeboxGroupsReq,
     ) -> Result<(Vec<debox_group::Model>, u64), ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+
         // 获取所有数据
         if let Some(true) = req.all {
-            return self.debox_group_dao.all().await.map_err(|err| {
+            return self.debox_group_dao.all(scope).await.map_err(|err| {
                 error!("查询DeBox群组列表失败, err: {:#?}", err);
                 Error::DbQueryError.into_err_with_msg("查询DeBox群组列表失败")
             });
         }
 
-        let (results, total) = self.debox_group_dao.list(req).await.map_err(|err| {
+        let (results, total) = self.debox_group_dao.list(scope, req).await.map_err(|err| {
             error!("查询DeBox群组列表失败, err: {:#?}", err);
             Error::DbQueryError.into_err_with_msg("查询DeBox群组列表失败")
         })?;
@@ -44,10 +51,15 @@ impl DeboxGroupService {
     }
 
     /// 获取详情数据
-    pub async fn info(&self, req: GetDeboxGroupReq) -> Result<debox_group::Model, ErrorMsg> {
+    pub async fn info(
+        &self,
+        user_id: i32,
+        req: GetDeboxGroupReq,
+    ) -> Result<debox_group::Model, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         let result = self
             .debox_group_dao
-            .info(req.id)
+            .info(scope, req.id)
             .await
             .map_err(|err| {
                 error!("查询DeBox群组信息失败, err: {:#?}", err);
@@ -62,7 +74,14 @@ impl DeboxGroupService {
     }
 
     /// 添加数据
-    pub async fn create(&self, req: CreateDeboxGroupReq) -> Result<debox_group::Model, ErrorMsg> {
+    pub async fn create(
+        &self,
+        user_id: i32,
+        req: CreateDeboxGroupReq,
+    ) -> Result<debox_group::Model, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+        self.check_account(scope, req.account_id).await?;
+
         let model = debox_group::ActiveModel {
             account_id: Set(req.account_id),
             url: Set(req.url),
@@ -81,7 +100,10 @@ impl DeboxGroupService {
     }
 
     /// 更新DeBox群组
-    pub async fn update(&self, req: UpdateDeboxGroupReq) -> Result<u64, ErrorMsg> {
+    pub async fn update(&self, user_id: i32, req: UpdateDeboxGroupReq) -> Result<u64, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+        self.check_account(scope, req.account_id).await?;
+
         let model = debox_group::ActiveModel {
             id: Set(req.id),
             account_id: Set(req.account_id),
@@ -93,18 +115,31 @@ impl DeboxGroupService {
             ..Default::default()
         };
 
-        let result = self.debox_group_dao.update(model).await.map_err(|err| {
-            error!("更新DeBox群组失败, err: {:#?}", err);
-            Error::DbUpdateError.into_err_with_msg("更新DeBox群组失败")
-        })?;
+        let result = self
+            .debox_group_dao
+            .update(scope, model)
+            .await
+            .map_err(|err| {
+                error!("更新DeBox群组失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("更新DeBox群组失败")
+            })?;
+        if result == 0 {
+            error!("更新DeBox群组失败, 该DeBox群组不存在");
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("DeBox群组不存在"));
+        }
 
         Ok(result)
     }
 
     /// 更新数据状态
-    pub async fn update_status(&self, req: UpdateDeboxGroupStatusReq) -> Result<(), ErrorMsg> {
+    pub async fn update_status(
+        &self,
+        user_id: i32,
+        req: UpdateDeboxGroupStatusReq,
+    ) -> Result<(), ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
         self.debox_group_dao
-            .update_status(req.id, req.status)
+            .update_status(scope, req.id, req.status)
             .await
             .map_err(|err| {
                 if err == RecordNotUpdated {
@@ -120,12 +155,39 @@ impl DeboxGroupService {
     }
 
     /// 删除数据
-    pub async fn delete(&self, req: DeleteDeboxGroupReq) -> Result<u64, ErrorMsg> {
-        let result = self.debox_group_dao.delete(req.id).await.map_err(|err| {
-            error!("删除DeBox群组信息失败, err: {:#?}", err);
-            Error::DbDeleteError.into_err_with_msg("删除DeBox群组信息失败")
-        })?;
+    pub async fn delete(&self, user_id: i32, req: DeleteDeboxGroupReq) -> Result<u64, ErrorMsg> {
+        let scope = self.data_scope_service.scope(user_id).await?;
+        let result = self
+            .debox_group_dao
+            .delete(scope, req.id)
+            .await
+            .map_err(|err| {
+                error!("删除DeBox群组信息失败, err: {:#?}", err);
+                Error::DbDeleteError.into_err_with_msg("删除DeBox群组信息失败")
+            })?;
+        if result == 0 {
+            error!("删除DeBox群组信息失败, 该DeBox群组不存在");
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("DeBox群组不存在"));
+        }
 
         Ok(result)
     }
 }
+
+impl DeboxGroupService {
+    /// 检查群组所属账号是否在数据权限范围内
+    async fn check_account(&self, scope: DataScope, account_id: i32) -> Result<(), ErrorMsg> {
+        self.debox_account_dao
+            .info(scope, account_id)
+            .await
+            .map_err(|err| {
+                error!("查询DeBox账号信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询DeBox账号信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("DeBox账号不存在, account_id: {account_id}");
+                Error::DbQueryEmptyError.into_err_with_msg("DeBox账号不存在")
+            })?;
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
 //! 服务层
+pub mod data_scope;
 pub mod debox_account;
 pub mod debox_group;
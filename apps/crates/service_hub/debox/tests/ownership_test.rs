@@ -0,0 +1,328 @@
+//! DeBox数据归属测试
+
+use std::sync::Arc;
+
+use database::PoolTrait;
+use entity::user::{
+    RoleEntity, UserRoleRelEntity,
+    role::{self, ADMIN_ROLE_NAME},
+    user_role_rel,
+};
+use err_code::Error;
+use inject::InjectProvider;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+
+use debox::{
+    DeboxAccountService, DeboxGroupService,
+    dto::{
+        debox_account::{
+            CreateDeboxAccountReq, DeleteDeboxAccountReq, GetDeboxAccountReq, GetDeboxAccountsReq,
+            UpdateDeboxAccountReq, UpdateDeboxAccountStatusReq,
+        },
+        debox_group::{
+            CreateDeboxGroupReq, DeleteDeboxGroupReq, GetDeboxGroupReq, GetDeboxGroupsReq,
+            UpdateDeboxGroupReq,
+        },
+    },
+};
+
+const ALICE: i32 = 1;
+const BOB: i32 = 2;
+const ADMIN: i32 = 3;
+const CAROL: i32 = 4;
+
+async fn setup() -> (InjectProvider, Arc<dyn PoolTrait>) {
+    let provider = InjectProvider::mock().await;
+    let db: Arc<dyn PoolTrait> = provider.provide();
+
+    // 授予管理员角色
+    let admin_role = RoleEntity::find()
+        .filter(role::Column::Name.eq(ADMIN_ROLE_NAME))
+        .one(db.db())
+        .await
+        .expect("query role")
+        .expect("admin role exists");
+    user_role_rel::ActiveModel {
+        user_id: Set(ADMIN),
+        role_id: Set(admin_role.id),
+        ..Default::default()
+    }
+    .insert(db.db())
+    .await
+    .expect("grant admin role");
+    assert_eq!(
+        UserRoleRelEntity::find()
+            .all(db.db())
+            .await
+            .expect("query roles")
+            .len(),
+        1
+    );
+
+    (provider, db)
+}
+
+fn account_req(user_id: i32, api_key: &str) -> CreateDeboxAccountReq {
+    CreateDeboxAccountReq {
+        user_id,
+        api_key: api_key.to_string(),
+        app_secret: format!("{api_key}-secret"),
+        access_token: String::new(),
+        web_token: String::new(),
+        debox_user_id: api_key.to_string(),
+        wallet_address: String::new(),
+        api_key_status: true,
+        access_token_status: false,
+        web_token_status: false,
+        desc: None,
+        status: true,
+    }
+}
+
+fn update_req(id: i32, user_id: i32, api_key: &str) -> UpdateDeboxAccountReq {
+    let req = account_req(user_id, api_key);
+    UpdateDeboxAccountReq {
+        id,
+        user_id: req.user_id,
+        api_key: req.api_key,
+        app_secret: req.app_secret,
+        access_token: req.access_token,
+        web_token: req.web_token,
+        debox_user_id: req.debox_user_id,
+        wallet_address: req.wallet_address,
+        api_key_status: req.api_key_status,
+        access_token_status: req.access_token_status,
+        web_token_status: req.web_token_status,
+        desc: req.desc,
+        status: req.status,
+    }
+}
+
+fn group_req(account_id: i32, group_name: &str) -> CreateDeboxGroupReq {
+    CreateDeboxGroupReq {
+        account_id,
+        url: format!("https://m.debox.pro/group?id={group_name}"),
+        group_name: group_name.to_string(),
+        group_code: group_name.to_string(),
+        desc: None,
+        status: true,
+    }
+}
+
+fn list_req() -> GetDeboxAccountsReq {
+    GetDeboxAccountsReq {
+        page: 1,
+        page_size: 10,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_account_cross_user_access() {
+    let (provider, _db) = setup().await;
+    let service: DeboxAccountService = provider.provide();
+
+    // 非管理员只能为自己添加账号
+    let alice_account = service
+        .create(ALICE, account_req(BOB, "alice"))
+        .await
+        .expect("create account");
+    assert_eq!(alice_account.user_id, ALICE);
+    let bob_account = service
+        .create(BOB, account_req(BOB, "bob"))
+        .await
+        .expect("create account");
+
+    // 列表仅返回自己的数据, 不能通过 user_id 查询他人数据
+    let (results, total) = service.list(ALICE, list_req()).await.expect("list");
+    assert_eq!(total, 1);
+    assert_eq!(results[0].id, alice_account.id);
+    let req = GetDeboxAccountsReq {
+        user_id: Some(BOB),
+        ..list_req()
+    };
+    let (_, total) = service.list(ALICE, req).await.expect("list");
+    assert_eq!(total, 0);
+    let req = GetDeboxAccountsReq {
+        all: Some(true),
+        ..Default::default()
+    };
+    let (_, total) = service.list(ALICE, req).await.expect("list all");
+    assert_eq!(total, 1);
+
+    // 不能查看/更新/删除他人的账号
+    let err = service
+        .info(ALICE, GetDeboxAccountReq { id: bob_account.id })
+        .await
+        .expect_err("read other's account");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+    let err = service
+        .update(ALICE, update_req(bob_account.id, ALICE, "hijack"))
+        .await
+        .expect_err("update other's account");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+    let err = service
+        .update_status(
+            ALICE,
+            UpdateDeboxAccountStatusReq {
+                id: bob_account.id,
+                status: false,
+            },
+        )
+        .await
+        .expect_err("update other's account status");
+    assert_eq!(err.code(), Error::DbUpdateError.code());
+    let err = service
+        .delete(ALICE, DeleteDeboxAccountReq { id: bob_account.id })
+        .await
+        .expect_err("delete other's account");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+
+    // 他人的账号保持不变
+    let result = service
+        .info(BOB, GetDeboxAccountReq { id: bob_account.id })
+        .await
+        .expect("read own account");
+    assert_eq!(result, bob_account);
+
+    // 更新自己的账号时不能转移给他人
+    service
+        .update(ALICE, update_req(alice_account.id, BOB, "alice2"))
+        .await
+        .expect("update own account");
+    let result = service
+        .info(
+            ALICE,
+            GetDeboxAccountReq {
+                id: alice_account.id,
+            },
+        )
+        .await
+        .expect("read own account");
+    assert_eq!(result.user_id, ALICE);
+    assert_eq!(result.api_key, "alice2");
+
+    // 管理员可以访问所有数据
+    let (_, total) = service.list(ADMIN, list_req()).await.expect("list");
+    assert_eq!(total, 2);
+    let req = GetDeboxAccountsReq {
+        user_id: Some(BOB),
+        ..list_req()
+    };
+    let (results, total) = service.list(ADMIN, req).await.expect("list");
+    assert_eq!(total, 1);
+    assert_eq!(results[0].id, bob_account.id);
+    let result = service
+        .create(ADMIN, account_req(CAROL, "carol"))
+        .await
+        .expect("create account for other");
+    assert_eq!(result.user_id, CAROL);
+    service
+        .delete(ADMIN, DeleteDeboxAccountReq { id: bob_account.id })
+        .await
+        .expect("admin delete account");
+}
+
+#[tokio::test]
+async fn test_group_cross_user_access() {
+    let (provider, _db) = setup().await;
+    let account_service: DeboxAccountService = provider.provide();
+    let group_service: DeboxGroupService = provider.provide();
+
+    let alice_account = account_service
+        .create(ALICE, account_req(ALICE, "alice"))
+        .await
+        .expect("create account");
+    let bob_account = account_service
+        .create(BOB, account_req(BOB, "bob"))
+        .await
+        .expect("create account");
+
+    // 不能在他人的账号下添加群组
+    let err = group_service
+        .create(ALICE, group_req(bob_account.id, "hijack"))
+        .await
+        .expect_err("create group under other's account");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+
+    let alice_group = group_service
+        .create(ALICE, group_req(alice_account.id, "alice"))
+        .await
+        .expect("create group");
+    let bob_group = group_service
+        .create(BOB, group_req(bob_account.id, "bob"))
+        .await
+        .expect("create group");
+
+    // 群组通过所属账号限定归属
+    let req = GetDeboxGroupsReq {
+        page: 1,
+        page_size: 10,
+        ..Default::default()
+    };
+    let (results, total) = group_service.list(ALICE, req).await.expect("list");
+    assert_eq!(total, 1);
+    assert_eq!(results[0].id, alice_group.id);
+    let req = GetDeboxGroupsReq {
+        page: 1,
+        page_size: 10,
+        account_id: Some(bob_account.id),
+        ..Default::default()
+    };
+    let (_, total) = group_service.list(ALICE, req).await.expect("list");
+    assert_eq!(total, 0);
+
+    let err = group_service
+        .info(ALICE, GetDeboxGroupReq { id: bob_group.id })
+        .await
+        .expect_err("read other's group");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+    let err = group_service
+        .update(
+            ALICE,
+            UpdateDeboxGroupReq {
+                id: bob_group.id,
+                account_id: alice_account.id,
+                url: String::new(),
+                group_name: "hijack".to_string(),
+                group_code: String::new(),
+                desc: None,
+                status: true,
+            },
+        )
+        .await
+        .expect_err("move other's group");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+    let err = group_service
+        .update(
+            ALICE,
+            UpdateDeboxGroupReq {
+                id: alice_group.id,
+                account_id: bob_account.id,
+                url: String::new(),
+                group_name: "alice".to_string(),
+                group_code: String::new(),
+                desc: None,
+                status: true,
+            },
+        )
+        .await
+        .expect_err("move own group to other's account");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+    let err = group_service
+        .delete(ALICE, DeleteDeboxGroupReq { id: bob_group.id })
+        .await
+        .expect_err("delete other's group");
+    assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+
+    // 管理员可以访问所有群组
+    let result = group_service
+        .info(ADMIN, GetDeboxGroupReq { id: bob_group.id })
+        .await
+        .expect("admin read group");
+    assert_eq!(result, bob_group);
+    group_service
+        .delete(ADMIN, DeleteDeboxGroupReq { id: alice_group.id })
+        .await
+        .expect("admin delete group");
+}
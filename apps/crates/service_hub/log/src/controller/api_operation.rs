@@ -1,16 +1,24 @@
 //! API操作日志
 
-use axum_response::{Responder, Response};
+use axum_response::{Responder, Response, ResponseErr};
+use axum_streams::StreamBodyAs;
 use axum_validator::{Extension, Json, Query};
 use inject::AInjectProvider;
+use logger::config::RetentionConfig;
 
 use crate::{
-    dto::api_operation::{
-        CreateApiOperationReq, CreateApiOperationResp, DeleteApiOperationReq,
-        DeleteApiOperationResp, GetApiOperationReq, GetApiOperationResp, GetApiOperationsReq,
-        GetApiOperationsResp,
+    controller::export::stream_body,
+    dto::{
+        api_operation::{
+            CreateApiOperationReq, CreateApiOperationResp, DeleteApiOperationReq,
+            DeleteApiOperationResp, GetApiOperationReq, GetApiOperationResp, GetApiOperationsReq,
+            GetApiOperationsResp,
+        },
+        export::ExportLogsReq,
+        purge::{PurgeLogsReq, PurgeLogsResp},
     },
-    service::api_operation::ApiOperationService,
+    enums::log_kind::LogKind,
+    service::{api_operation::ApiOperationService, purge::LogPurgeService},
 };
 
 /// 控制器
@@ -41,6 +49,19 @@ impl ApiOperationController {
         Ok(resp)
     }
 
+    /// 导出API操作日志
+    pub async fn export(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<GetApiOperationsReq>,
+        Query(export): Query<ExportLogsReq>,
+    ) -> Result<StreamBodyAs<'static>, ResponseErr> {
+        let api_operation_service: ApiOperationService = provider.provide();
+        let stream = api_operation_service.export(req);
+
+        let body = stream_body("api-operation-logs", export.format, stream)?;
+        Ok(body)
+    }
+
     /// 添加API操作日志
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
@@ -64,4 +85,19 @@ impl ApiOperationController {
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
     }
+
+    /// 清理API操作日志
+    pub async fn purge(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(config): Extension<RetentionConfig>,
+        Json(req): Json<PurgeLogsReq>,
+    ) -> Responder<PurgeLogsResp> {
+        let purge_service: LogPurgeService = provider.provide();
+        let result = purge_service
+            .purge_logs(LogKind::ApiOperation, &config, req)
+            .await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
 }
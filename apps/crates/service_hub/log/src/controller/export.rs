@@ -0,0 +1,136 @@
+//! 日志导出
+
+use axum::http::{HeaderValue, header};
+use axum_streams::{CsvStreamFormat, StreamBodyAs};
+use futures::{Stream, StreamExt};
+use log::error;
+use serde::Serialize;
+
+use err_code::{Error, ErrorMsg};
+
+use crate::dto::export::ExportFormat;
+
+/// 将日志数据流转换为附件下载的响应体
+///
+/// 文件名为 `{name}-{当前时间}.{扩展名}`
+pub(crate) fn stream_body<S, T>(
+    name: &str,
+    format: ExportFormat,
+    stream: S,
+) -> Result<StreamBodyAs<'static>, ErrorMsg>
+where
+    S: Stream<Item = Result<T, ErrorMsg>> + Send + 'static,
+    T: Serialize + Send + Sync + 'static,
+{
+    let filename = format!(
+        "attachment; filename=\"{}-{}.{}\"",
+        name,
+        chrono::Local::now().format("%Y%m%d%H%M%S"),
+        format.extension()
+    );
+    let disposition = HeaderValue::from_str(&filename).map_err(|err| {
+        error!("导出文件名转换失败, err: {:#?}", err);
+        Error::HeaderValue(err.to_string()).into_err_with_msg("导出文件名转换失败")
+    })?;
+
+    let stream = stream.map(|v| v.map_err(|err| axum::Error::new(err.msg().to_string())));
+    let body = match format {
+        ExportFormat::Jsonl => StreamBodyAs::json_nl_with_errors(stream),
+        ExportFormat::Csv => StreamBodyAs::new(CsvStreamFormat::new(true, b','), stream),
+    };
+    Ok(body.header(header::CONTENT_DISPOSITION, disposition))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{body::to_bytes, response::IntoResponse};
+    use database::PoolTrait;
+    use entity::log::{LogSystemEntity, log_system};
+    use inject::InjectProvider;
+    use sea_orm::{ActiveValue::NotSet, EntityTrait};
+
+    use crate::{
+        dto::system_log::GetSystemLogsReq,
+        service::{export::EXPORT_BATCH_SIZE, system_log::SystemLogService},
+    };
+
+    use super::*;
+
+    async fn provider() -> (InjectProvider, Arc<dyn PoolTrait>) {
+        let provider = InjectProvider::mock().await;
+        let db = provider.provide();
+        (provider, db)
+    }
+
+    /// 添加系统日志, 偶数条的 target 为 `export_test`
+    async fn seed(db: &Arc<dyn PoolTrait>, total: usize) {
+        let models = (0..total)
+            .map(|i| {
+                let mut active_model: log_system::ActiveModel = log_system::Model {
+                    name: "test".to_string(),
+                    target: if i % 2 == 0 { "export_test" } else { "other" }.to_string(),
+                    level: "INFO".to_string(),
+                    kind: "event".to_string(),
+                    message: Some(format!("log {i}")),
+                    created_at: Some(chrono::Local::now().naive_local()),
+                    ..Default::default()
+                }
+                .into();
+                active_model.id = NotSet;
+                active_model
+            })
+            .collect::<Vec<_>>();
+        // SQLite 单条语句的参数数量有限, 分批插入
+        for chunk in models.chunks(100) {
+            LogSystemEntity::insert_many(chunk.to_vec())
+                .exec(db.db())
+                .await
+                .expect("insert logs");
+        }
+    }
+
+    async fn export(provider: &InjectProvider, format: ExportFormat) -> (String, String) {
+        let system_service: SystemLogService = provider.provide();
+        let stream = system_service.export(GetSystemLogsReq {
+            target: Some("export_test".to_string()),
+            ..Default::default()
+        });
+        let response = stream_body("system-logs", format, stream)
+            .expect("stream body")
+            .into_response();
+        let disposition = response.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .expect("content disposition")
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (disposition, String::from_utf8(body.to_vec()).expect("utf8"))
+    }
+
+    #[tokio::test]
+    async fn test_export() {
+        let (provider, db) = provider().await;
+        // 过滤后的数据量超过两个批次
+        let total = (EXPORT_BATCH_SIZE * 5) as usize + 2;
+        seed(&db, total).await;
+        let expected = total.div_ceil(2);
+
+        let (disposition, body) = export(&provider, ExportFormat::Jsonl).await;
+        assert!(disposition.starts_with("attachment; filename=\"system-logs-"));
+        assert!(disposition.ends_with(".jsonl\""));
+        let lines = body.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), expected);
+        let last: log_system::Model = serde_json::from_str(lines[lines.len() - 1]).expect("json");
+        assert_eq!(last.message, Some(format!("log {}", total - 2)));
+
+        let (disposition, body) = export(&provider, ExportFormat::Csv).await;
+        assert!(disposition.ends_with(".csv\""));
+        let lines = body.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), expected + 1);
+        assert!(lines[0].contains(",message,"));
+        assert!(lines[1].contains("log 0"));
+    }
+}
@@ -1,4 +1,5 @@
 //! 控制器层
 pub mod api_operation;
+pub mod export;
 pub mod system_log;
 pub mod web_log;
@@ -1,15 +1,26 @@
 //! 系统日志
 
-use axum_response::{Responder, Response};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum_response::{Responder, Response, ResponseErr};
+use axum_streams::StreamBodyAs;
 use axum_validator::{Extension, Json, Query};
+use futures::{Stream, StreamExt};
 use inject::AInjectProvider;
+use logger::config::RetentionConfig;
 
 use crate::{
-    dto::system_log::{
-        CreateSystemLogReq, CreateSystemLogResp, DeleteSystemLogReq, DeleteSystemLogResp,
-        GetSystemLogReq, GetSystemLogResp, GetSystemLogsReq, GetSystemLogsResp,
+    controller::export::stream_body,
+    dto::{
+        export::ExportLogsReq,
+        purge::{PurgeLogsReq, PurgeLogsResp},
+        system_log::{
+            CreateSystemLogReq, CreateSystemLogResp, DeleteSystemLogReq, DeleteSystemLogResp,
+            GetSystemLogReq, GetSystemLogResp, GetSystemLogsReq, GetSystemLogsResp,
+            TailSystemLogEvent, TailSystemLogsReq,
+        },
     },
-    service::system_log::SystemLogService,
+    enums::log_kind::LogKind,
+    service::{purge::LogPurgeService, system_log::SystemLogService},
 };
 
 /// 控制器
@@ -40,6 +51,37 @@ impl SystemLogController {
         Ok(resp)
     }
 
+    /// 导出系统日志
+    pub async fn export(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<GetSystemLogsReq>,
+        Query(export): Query<ExportLogsReq>,
+    ) -> Result<StreamBodyAs<'static>, ResponseErr> {
+        let system_service: SystemLogService = provider.provide();
+        let stream = system_service.export(req);
+
+        let body = stream_body("system-logs", export.format, stream)?;
+        Ok(body)
+    }
+
+    /// 实时查看系统日志
+    ///
+    /// 以 Server-Sent Events 推送新产生的系统日志, 路由需经管理员访问守卫校验后才订阅日志
+    pub async fn tail(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<TailSystemLogsReq>,
+    ) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+        let system_service: SystemLogService = provider.provide();
+        let stream = system_service.tail(req).map(|event| match event {
+            TailSystemLogEvent::Log(log) => Event::default().event("log").json_data(log),
+            TailSystemLogEvent::Lagged(count) => {
+                Ok(Event::default().event("lagged").data(count.to_string()))
+            }
+        });
+
+        Sse::new(stream).keep_alive(KeepAlive::default())
+    }
+
     /// 添加系统日志
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
@@ -63,4 +105,19 @@ impl SystemLogController {
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
     }
+
+    /// 清理系统日志
+    pub async fn purge(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(config): Extension<RetentionConfig>,
+        Json(req): Json<PurgeLogsReq>,
+    ) -> Responder<PurgeLogsResp> {
+        let purge_service: LogPurgeService = provider.provide();
+        let result = purge_service
+            .purge_logs(LogKind::System, &config, req)
+            .await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
 }
@@ -1,15 +1,23 @@
 //! WEB日志管理
 
-use axum_response::{Responder, Response};
+use axum_response::{Responder, Response, ResponseErr};
+use axum_streams::StreamBodyAs;
 use axum_validator::{Extension, Json, Query};
 use inject::AInjectProvider;
+use logger::config::RetentionConfig;
 
 use crate::{
-    dto::web_log::{
-        CreateWebLogReq, CreateWebLogResp, GetWebLogReq, GetWebLogResp, GetWebLogsReq,
-        GetWebLogsResp,
+    controller::export::stream_body,
+    dto::{
+        export::ExportLogsReq,
+        purge::{PurgeLogsReq, PurgeLogsResp},
+        web_log::{
+            CreateWebLogReq, CreateWebLogResp, GetWebLogReq, GetWebLogResp, GetWebLogsReq,
+            GetWebLogsResp,
+        },
     },
-    service::web_log::WebLogService,
+    enums::log_kind::LogKind,
+    service::{purge::LogPurgeService, web_log::WebLogService},
 };
 
 /// 控制器
@@ -40,6 +48,19 @@ impl WebLogController {
         Ok(resp)
     }
 
+    /// 导出WEB日志
+    pub async fn export(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<GetWebLogsReq>,
+        Query(export): Query<ExportLogsReq>,
+    ) -> Result<StreamBodyAs<'static>, ResponseErr> {
+        let log_web_service: WebLogService = provider.provide();
+        let stream = log_web_service.export(req);
+
+        let body = stream_body("web-logs", export.format, stream)?;
+        Ok(body)
+    }
+
     /// 添加WEB日志
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
@@ -51,4 +72,17 @@ impl WebLogController {
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
     }
+
+    /// 清理WEB日志
+    pub async fn purge(
+        Extension(provider): Extension<AInjectProvider>,
+        Extension(config): Extension<RetentionConfig>,
+        Json(req): Json<PurgeLogsReq>,
+    ) -> Responder<PurgeLogsResp> {
+        let purge_service: LogPurgeService = provider.provide();
+        let result = purge_service.purge_logs(LogKind::Web, &config, req).await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
 }
@@ -5,13 +5,13 @@ use std::sync::Arc;
 use nject::injectable;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, QueryTrait,
+    QuerySelect, QueryTrait, Select,
 };
 
 use database::{Pagination, PoolTrait};
 use entity::log::{LogApiOperationEntity, log_api_operation};
 
-use crate::dto::api_operation::GetApiOperationsReq;
+use crate::{dao::like_prefix, dto::api_operation::GetApiOperationsReq};
 
 /// 数据访问
 #[injectable]
@@ -20,6 +20,38 @@ pub struct ApiOperationDao {
 }
 
 impl ApiOperationDao {
+    /// 构建查询条件
+    fn select(&self, req: GetApiOperationsReq) -> Select<LogApiOperationEntity> {
+        LogApiOperationEntity::find()
+            .apply_if(req.start_time, |query, v| {
+                query.filter(log_api_operation::Column::CreatedAt.gte(v))
+            })
+            .apply_if(req.end_time, |query, v| {
+                query.filter(log_api_operation::Column::CreatedAt.lt(v))
+            })
+            .apply_if(req.request_id, |query, v| {
+                query.filter(log_api_operation::Column::RequestId.eq(v))
+            })
+            .apply_if(req.user_id, |query, v| {
+                query.filter(log_api_operation::Column::UserId.eq(v))
+            })
+            .apply_if(req.method, |query, v| {
+                query.filter(log_api_operation::Column::Method.eq(v.to_uppercase()))
+            })
+            .apply_if(req.path, |query, v| {
+                query.filter(log_api_operation::Column::Path.like(like_prefix(&v)))
+            })
+            .apply_if(req.status_code_min, |query, v| {
+                query.filter(log_api_operation::Column::StatusCode.gte(v))
+            })
+            .apply_if(req.status_code_max, |query, v| {
+                query.filter(log_api_operation::Column::StatusCode.lte(v))
+            })
+            .apply_if(req.min_cost, |query, v| {
+                query.filter(log_api_operation::Column::Cost.gte(v))
+            })
+    }
+
     /// 获取数据列表
     pub async fn list(
         &self,
@@ -27,13 +59,7 @@ impl ApiOperationDao {
     ) -> Result<(Vec<log_api_operation::Model>, u64), DbErr> {
         let page = Pagination::new(req.page, req.page_size);
 
-        let states = LogApiOperationEntity::find()
-            .apply_if(req.start_time, |query, v| {
-                query.filter(log_api_operation::Column::CreatedAt.gte(v))
-            })
-            .apply_if(req.end_time, |query, v| {
-                query.filter(log_api_operation::Column::CreatedAt.lt(v))
-            });
+        let states = self.select(req);
 
         let total = states.clone().count(self.db.db()).await?;
         if total == 0 {
@@ -50,6 +76,21 @@ impl ApiOperationDao {
         Ok((results, total))
     }
 
+    /// 按主键顺序分批获取数据, 用于导出
+    pub async fn batch(
+        &self,
+        req: GetApiOperationsReq,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<log_api_operation::Model>, DbErr> {
+        self.select(req)
+            .filter(log_api_operation::Column::Id.gt(after_id))
+            .order_by_asc(log_api_operation::Column::Id)
+            .limit(limit)
+            .all(self.db.db())
+            .await
+    }
+
     /// 获取详情信息
     pub async fn info(&self, id: i32) -> Result<Option<log_api_operation::Model>, DbErr> {
         LogApiOperationEntity::find_by_id(id)
@@ -1,4 +1,32 @@
 //! 数据层
 pub mod api_operation;
+pub mod purge;
 pub mod system_log;
 pub mod web_log;
+
+use sea_orm::sea_query::LikeExpr;
+
+/// LIKE 查询的转义字符
+const LIKE_ESCAPE: char = '\\';
+
+/// 转义 LIKE 查询中的通配符 `%` 与 `_`
+fn escape_like(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_') || c == LIKE_ESCAPE {
+            result.push(LIKE_ESCAPE);
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// 前缀匹配
+pub(crate) fn like_prefix(text: &str) -> LikeExpr {
+    LikeExpr::new(format!("{}%", escape_like(text))).escape(LIKE_ESCAPE)
+}
+
+/// 包含匹配
+pub(crate) fn like_contains(text: &str) -> LikeExpr {
+    LikeExpr::new(format!("%{}%", escape_like(text))).escape(LIKE_ESCAPE)
+}
@@ -0,0 +1,143 @@
+//! 日志清理
+
+use std::sync::Arc;
+
+use chrono::{Duration, Local};
+use nject::injectable;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+
+use database::PoolTrait;
+use entity::log::{
+    LogApiOperationEntity, LogSystemEntity, LogWebEntity, log_api_operation, log_system, log_web,
+};
+
+/// 可清理的日志表
+pub trait LogTable: EntityTrait {
+    /// 自增ID列
+    fn id_column() -> Self::Column;
+    /// 创建时间列
+    fn created_at_column() -> Self::Column;
+}
+
+impl LogTable for LogSystemEntity {
+    fn id_column() -> Self::Column {
+        log_system::Column::Id
+    }
+    fn created_at_column() -> Self::Column {
+        log_system::Column::CreatedAt
+    }
+}
+
+impl LogTable for LogWebEntity {
+    fn id_column() -> Self::Column {
+        log_web::Column::Id
+    }
+    fn created_at_column() -> Self::Column {
+        log_web::Column::CreatedAt
+    }
+}
+
+impl LogTable for LogApiOperationEntity {
+    fn id_column() -> Self::Column {
+        log_api_operation::Column::Id
+    }
+    fn created_at_column() -> Self::Column {
+        log_api_operation::Column::CreatedAt
+    }
+}
+
+/// 数据访问
+#[injectable]
+pub struct LogPurgeDao {
+    db: Arc<dyn PoolTrait>,
+}
+
+impl LogPurgeDao {
+    /// 构建清理条件, 满足任一保留条件之外的日志将被清理
+    ///
+    /// 未配置任何条件或无需清理时返回 `None`
+    pub async fn condition<E: LogTable>(
+        &self,
+        max_age_days: Option<u32>,
+        max_rows: Option<u64>,
+    ) -> Result<Option<Condition>, DbErr> {
+        let mut cond = Condition::any();
+        let mut is_empty = true;
+
+        if let Some(days) = max_age_days {
+            let cutoff = Local::now().naive_local() - Duration::days(days as i64);
+            cond = cond.add(E::created_at_column().lt(cutoff));
+            is_empty = false;
+        }
+
+        if let Some(rows) = max_rows {
+            // 保留最新的 rows 条日志, 获取需要清理的最大ID
+            let max_id = E::find()
+                .select_only()
+                .column(E::id_column())
+                .order_by_desc(E::id_column())
+                .offset(rows)
+                .limit(1)
+                .into_tuple::<i32>()
+                .one(self.db.db())
+                .await?;
+            if let Some(max_id) = max_id {
+                cond = cond.add(E::id_column().lte(max_id));
+                is_empty = false;
+            }
+        }
+
+        if is_empty {
+            return Ok(None);
+        }
+        Ok(Some(cond))
+    }
+
+    /// 统计满足条件的日志数量
+    pub async fn count<E>(&self, cond: Condition) -> Result<u64, DbErr>
+    where
+        E: LogTable,
+        E::Model: Sync,
+    {
+        E::find().filter(cond).count(self.db.db()).await
+    }
+
+    /// 按批次删除满足条件的日志, 返回本批次删除的数量
+    pub async fn delete_batch<E: LogTable>(
+        &self,
+        cond: Condition,
+        batch_size: u64,
+    ) -> Result<u64, DbErr> {
+        let ids = E::find()
+            .select_only()
+            .column(E::id_column())
+            .filter(cond)
+            .order_by_asc(E::id_column())
+            .limit(batch_size)
+            .into_tuple::<i32>()
+            .all(self.db.db())
+            .await?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = E::delete_many()
+            .filter(E::id_column().is_in(ids))
+            .exec(self.db.db())
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// 回收 SQLite 数据库空间, 非 SQLite 数据库时返回 `false`
+    pub async fn vacuum(&self) -> Result<bool, DbErr> {
+        let db = self.db.db();
+        if db.get_database_backend() != DbBackend::Sqlite {
+            return Ok(false);
+        }
+        db.execute_unprepared("VACUUM").await?;
+        Ok(true)
+    }
+}
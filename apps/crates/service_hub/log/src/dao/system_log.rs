@@ -4,14 +4,20 @@ use std::sync::Arc;
 
 use nject::injectable;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DbErr, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    ActiveModelTrait,
+    ActiveValue::NotSet,
+    ColumnTrait, ConnectionTrait, DbBackend, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait, Select,
+    sea_query::{Alias, Expr, Query, SimpleExpr},
 };
 
 use database::{Pagination, PoolTrait};
 use entity::log::{LogSystemEntity, log_system};
 
-use crate::dto::system_log::GetSystemLogsReq;
+use crate::{
+    dao::{like_contains, like_prefix},
+    dto::system_log::GetSystemLogsReq,
+};
 
 /// 数据访问
 #[injectable]
@@ -20,6 +26,38 @@ pub struct SystemLogDao {
 }
 
 impl SystemLogDao {
+    /// 构建查询条件
+    fn select(&self, req: GetSystemLogsReq) -> Select<LogSystemEntity> {
+        LogSystemEntity::find()
+            .apply_if(req.start_time, |query, v| {
+                query.filter(log_system::Column::CreatedAt.gte(v))
+            })
+            .apply_if(req.end_time, |query, v| {
+                query.filter(log_system::Column::CreatedAt.lt(v))
+            })
+            .apply_if(req.request_id, |query, v| {
+                query.filter(log_system::Column::RequestId.eq(v))
+            })
+            .apply_if(req.user_id, |query, v| {
+                query.filter(log_system::Column::UserId.eq(v))
+            })
+            .apply_if(req.username, |query, v| {
+                query.filter(log_system::Column::Username.eq(v))
+            })
+            .apply_if(req.level, |query, v| {
+                query.filter(log_system::Column::Level.eq(v.to_uppercase()))
+            })
+            .apply_if(req.target, |query, v| {
+                query.filter(log_system::Column::Target.like(like_prefix(&v)))
+            })
+            .apply_if(req.module_path, |query, v| {
+                query.filter(log_system::Column::ModulePath.like(like_prefix(&v)))
+            })
+            .apply_if(req.message.filter(|v| !v.is_empty()), |query, v| {
+                query.filter(self.message_filter(v))
+            })
+    }
+
     /// 获取数据列表
     pub async fn list(
         &self,
@@ -27,13 +65,7 @@ impl SystemLogDao {
     ) -> Result<(Vec<log_system::Model>, u64), DbErr> {
         let page = Pagination::new(req.page, req.page_size);
 
-        let states = LogSystemEntity::find()
-            .apply_if(req.start_time, |query, v| {
-                query.filter(log_system::Column::CreatedAt.gte(v))
-            })
-            .apply_if(req.end_time, |query, v| {
-                query.filter(log_system::Column::CreatedAt.lt(v))
-            });
+        let states = self.select(req);
 
         let total = states.clone().count(self.db.db()).await?;
         if total == 0 {
@@ -50,6 +82,40 @@ impl SystemLogDao {
         Ok((results, total))
     }
 
+    /// 日志信息检索条件
+    ///
+    /// Sqlite 使用 FTS5 全文索引, 其他数据库及不足 3 个字符的关键词使用 LIKE 查询
+    fn message_filter(&self, text: String) -> SimpleExpr {
+        let backend = self.db.db().get_database_backend();
+        // trigram 分词器的关键词至少需要 3 个字符
+        if backend == DbBackend::Sqlite && text.chars().count() >= 3 {
+            // 作为短语匹配, 避免关键词被解析为 FTS5 查询语法
+            let phrase = format!("\"{}\"", text.replace('"', "\"\""));
+            let sub_query = Query::select()
+                .expr(Expr::cust("rowid"))
+                .from(Alias::new("t_log_system_fts"))
+                .and_where(Expr::cust_with_values("t_log_system_fts MATCH ?", [phrase]))
+                .to_owned();
+            return log_system::Column::Id.in_subquery(sub_query);
+        }
+        log_system::Column::Message.like(like_contains(&text))
+    }
+
+    /// 按主键顺序分批获取数据, 用于导出
+    pub async fn batch(
+        &self,
+        req: GetSystemLogsReq,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<log_system::Model>, DbErr> {
+        self.select(req)
+            .filter(log_system::Column::Id.gt(after_id))
+            .order_by_asc(log_system::Column::Id)
+            .limit(limit)
+            .all(self.db.db())
+            .await
+    }
+
     /// 获取详情信息
     pub async fn info(&self, id: i32) -> Result<Option<log_system::Model>, DbErr> {
         LogSystemEntity::find_by_id(id).one(self.db.db()).await
@@ -68,3 +134,129 @@ impl SystemLogDao {
         Ok(result.rows_affected)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use inject::InjectProvider;
+
+    use super::*;
+
+    async fn dao() -> SystemLogDao {
+        let provider = InjectProvider::mock().await;
+        provider.provide()
+    }
+
+    async fn messages(dao: &SystemLogDao, req: GetSystemLogsReq) -> Vec<String> {
+        let (results, _total) = dao.list(req).await.expect("list logs");
+        results.into_iter().filter_map(|v| v.message).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_filter() {
+        let dao = dao().await;
+        let logs = [
+            ("INFO", "auth::service", "用户登录成功 user01"),
+            ("ERROR", "auth::service", "用户登录失败 user02"),
+            ("WARN", "log::task", "clean up 100% done"),
+            ("DEBUG", "log_task", "task started"),
+        ];
+        for (level, target, message) in logs {
+            dao.create(log_system::Model {
+                level: level.to_string(),
+                target: target.to_string(),
+                message: Some(message.to_string()),
+                created_at: Some(chrono::Local::now().naive_local()),
+                ..Default::default()
+            })
+            .await
+            .expect("create log");
+        }
+
+        let req = || GetSystemLogsReq {
+            page: 1,
+            page_size: 10,
+            ..Default::default()
+        };
+
+        // 全文检索
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("登录失败".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result, vec!["用户登录失败 user02"]);
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("USER0".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result.len(), 2);
+        // 含 FTS5 语法字符的关键词
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("100% \"done".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert!(result.is_empty());
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("100% done".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result, vec!["clean up 100% done"]);
+        // 过短的关键词使用 LIKE 查询
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("登录".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result.len(), 2);
+
+        // 级别及目标前缀
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                level: Some("error".to_string()),
+                target: Some("auth".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result, vec!["用户登录失败 user02"]);
+
+        // 通配符按字面匹配
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                target: Some("log_".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result, vec!["task started"]);
+        let result = messages(
+            &dao,
+            GetSystemLogsReq {
+                message: Some("%".to_string()),
+                ..req()
+            },
+        )
+        .await;
+        assert_eq!(result, vec!["clean up 100% done"]);
+    }
+}
@@ -5,13 +5,13 @@ use std::sync::Arc;
 use nject::injectable;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, QueryTrait,
+    QuerySelect, QueryTrait, Select,
 };
 
 use database::{Pagination, PoolTrait};
 use entity::log::{LogWebEntity, log_web};
 
-use crate::dto::web_log::GetWebLogsReq;
+use crate::{dao::like_prefix, dto::web_log::GetWebLogsReq};
 
 /// 数据访问
 #[injectable]
@@ -20,11 +20,9 @@ pub struct WebLogDao {
 }
 
 impl WebLogDao {
-    /// 获取数据列表
-    pub async fn list(&self, req: GetWebLogsReq) -> Result<(Vec<log_web::Model>, u64), DbErr> {
-        let page = Pagination::new(req.page, req.page_size);
-
-        let states = LogWebEntity::find()
+    /// 构建查询条件
+    fn select(&self, req: GetWebLogsReq) -> Select<LogWebEntity> {
+        LogWebEntity::find()
             .apply_if(req.start_time, |query, v| {
                 query.filter(log_web::Column::CreatedAt.gte(v))
             })
@@ -35,8 +33,21 @@ impl WebLogDao {
                 query.filter(log_web::Column::UserId.eq(v))
             })
             .apply_if(req.username, |query, v| {
-                query.filter(log_web::Column::Username.like(format!("{v}%")))
-            });
+                query.filter(log_web::Column::Username.like(like_prefix(&v)))
+            })
+            .apply_if(req.os_type, |query, v| {
+                query.filter(log_web::Column::OsType.eq(v as i8))
+            })
+            .apply_if(req.error_type, |query, v| {
+                query.filter(log_web::Column::ErrorType.eq(v as i8))
+            })
+    }
+
+    /// 获取数据列表
+    pub async fn list(&self, req: GetWebLogsReq) -> Result<(Vec<log_web::Model>, u64), DbErr> {
+        let page = Pagination::new(req.page, req.page_size);
+
+        let states = self.select(req);
 
         let total = states.clone().count(self.db.db()).await?;
         if total == 0 {
@@ -53,6 +64,21 @@ impl WebLogDao {
         Ok((results, total))
     }
 
+    /// 按主键顺序分批获取数据, 用于导出
+    pub async fn batch(
+        &self,
+        req: GetWebLogsReq,
+        after_id: i32,
+        limit: u64,
+    ) -> Result<Vec<log_web::Model>, DbErr> {
+        self.select(req)
+            .filter(log_web::Column::Id.gt(after_id))
+            .order_by_asc(log_web::Column::Id)
+            .limit(limit)
+            .all(self.db.db())
+            .await
+    }
+
     /// 获取详情信息
     pub async fn info(&self, id: i32) -> Result<Option<log_web::Model>, DbErr> {
         LogWebEntity::find_by_id(id).one(self.db.db()).await
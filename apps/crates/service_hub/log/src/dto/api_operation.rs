@@ -6,16 +6,32 @@ use validator::Validate;
 use entity::log::{log_api_operation, log_api_operation::enums::HttpType};
 
 /// 查询API操作日志列表 请求体
-#[derive(Default, Deserialize, Validate)]
+#[derive(Clone, Default, Deserialize, Validate)]
 pub struct GetApiOperationsReq {
     /// 当前分页
+    #[serde(default)]
     pub page: u64,
     /// 页面大小
+    #[serde(default)]
     pub page_size: u64,
     /// 开始时间
     pub start_time: Option<String>,
     /// 结束时间
     pub end_time: Option<String>,
+    /// 请求ID
+    pub request_id: Option<String>,
+    /// 用户ID
+    pub user_id: Option<i32>,
+    /// 请求方法
+    pub method: Option<String>,
+    /// 请求路径, 前缀匹配
+    pub path: Option<String>,
+    /// 最小请求状态码
+    pub status_code_min: Option<i32>,
+    /// 最大请求状态码
+    pub status_code_max: Option<i32>,
+    /// 慢请求阈值, 耗时不小于该值的请求, 毫秒
+    pub min_cost: Option<i16>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
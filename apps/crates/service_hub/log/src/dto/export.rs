@@ -0,0 +1,35 @@
+//! 日志导出
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 导出格式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// JSON Lines, 每行一条记录
+    #[default]
+    Jsonl,
+    /// CSV, 首行为表头
+    Csv,
+}
+
+impl ExportFormat {
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// 导出日志 请求体
+///
+/// 过滤条件与对应日志列表的查询参数一致, 分页参数将被忽略
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct ExportLogsReq {
+    /// 导出格式
+    #[serde(default)]
+    pub format: ExportFormat,
+}
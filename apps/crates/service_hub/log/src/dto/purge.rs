@@ -0,0 +1,26 @@
+//! 日志清理
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// 清理日志 请求体
+///
+/// 未指定保留条件时, 使用配置文件中对应日志表的保留策略
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct PurgeLogsReq {
+    /// 最长保留天数
+    pub max_age_days: Option<u32>,
+    /// 最多保留行数
+    pub max_rows: Option<u64>,
+    /// 仅统计待清理的数量, 不执行删除
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PurgeLogsResp {
+    /// 清理或待清理的数量
+    pub count: u64,
+    /// 是否仅统计
+    pub dry_run: bool,
+}
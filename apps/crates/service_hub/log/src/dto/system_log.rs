@@ -4,18 +4,35 @@ use serde::{Deserialize, Serialize};
 use validator::Validate;
 
 use entity::log::log_system;
+use logger::config::Level;
 
 /// 查询系统日志列表 请求体
-#[derive(Default, Deserialize, Validate)]
+#[derive(Clone, Default, Deserialize, Validate)]
 pub struct GetSystemLogsReq {
     /// 当前分页
+    #[serde(default)]
     pub page: u64,
     /// 页面大小
+    #[serde(default)]
     pub page_size: u64,
     /// 开始时间
     pub start_time: Option<String>,
     /// 结束时间
     pub end_time: Option<String>,
+    /// 请求ID
+    pub request_id: Option<String>,
+    /// 用户ID
+    pub user_id: Option<i32>,
+    /// 用户名称
+    pub username: Option<String>,
+    /// 日志级别
+    pub level: Option<String>,
+    /// 目标, 前缀匹配
+    pub target: Option<String>,
+    /// 模块路径, 前缀匹配
+    pub module_path: Option<String>,
+    /// 日志信息, 全文检索
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +41,24 @@ pub struct GetSystemLogsResp {
     pub total: u64,
 }
 
+/// 实时查看系统日志 请求体
+#[derive(Debug, Default, Clone, Deserialize, Validate)]
+pub struct TailSystemLogsReq {
+    /// 最低日志级别, 如 `warn` 时包含 `WARN` 及 `ERROR`
+    pub level: Option<Level>,
+    /// 目标, 前缀匹配
+    pub target: Option<String>,
+}
+
+/// 实时日志事件
+#[derive(Debug)]
+pub enum TailSystemLogEvent {
+    /// 系统日志
+    Log(Box<log_system::Model>),
+    /// 订阅者处理过慢, 丢弃的日志数量
+    Lagged(u64),
+}
+
 /// 查询数据 请求体
 #[derive(Debug, Default, Serialize, Deserialize, Validate)]
 pub struct GetSystemLogReq {
@@ -44,6 +79,8 @@ pub struct CreateSystemLogReq {
     pub user_id: Option<i32>,
     /// 用户名称
     pub username: Option<String>,
+    /// 请求ID
+    pub request_id: Option<String>,
 
     /// 日志记录器名称
     #[serde(default)]
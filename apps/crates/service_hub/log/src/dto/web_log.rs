@@ -9,11 +9,13 @@ use entity::log::{
 };
 
 /// 查询WEB日志列表 请求体
-#[derive(Default, Deserialize, Serialize, Validate)]
+#[derive(Clone, Default, Deserialize, Serialize, Validate)]
 pub struct GetWebLogsReq {
     /// 当前分页
+    #[serde(default)]
     pub page: u64,
     /// 页面大小
+    #[serde(default)]
     pub page_size: u64,
     /// 开始时间
     pub start_time: Option<String>,
@@ -23,6 +25,10 @@ pub struct GetWebLogsReq {
     pub user_id: Option<i32>,
     /// 用户名称
     pub username: Option<String>,
+    /// 终端类型
+    pub os_type: Option<OsType>,
+    /// 错误类型
+    pub error_type: Option<ErrorType>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,37 @@
+//! 日志类型
+
+use logger::config::{RetentionConfig, RetentionPolicy};
+
+/// 日志类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogKind {
+    /// 系统日志
+    System,
+    /// WEB日志
+    Web,
+    /// API操作日志
+    ApiOperation,
+}
+
+impl LogKind {
+    /// 全部日志类型
+    pub const ALL: [LogKind; 3] = [LogKind::System, LogKind::Web, LogKind::ApiOperation];
+
+    /// 日志类型名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogKind::System => "系统日志",
+            LogKind::Web => "WEB日志",
+            LogKind::ApiOperation => "API操作日志",
+        }
+    }
+
+    /// 获取对应的保留策略
+    pub fn policy<'a>(&self, config: &'a RetentionConfig) -> &'a RetentionPolicy {
+        match self {
+            LogKind::System => &config.system,
+            LogKind::Web => &config.web,
+            LogKind::ApiOperation => &config.api_operation,
+        }
+    }
+}
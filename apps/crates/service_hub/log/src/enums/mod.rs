@@ -1,3 +1,4 @@
 //! 枚举
 pub mod log_api_operation;
+pub mod log_kind;
 pub mod log_web;
@@ -3,11 +3,15 @@ pub mod dto;
 pub mod enums;
 
 pub(crate) mod dao;
-pub use dao::{api_operation::ApiOperationDao, system_log::SystemLogDao, web_log::WebLogDao};
+pub use dao::{
+    api_operation::ApiOperationDao, purge::LogPurgeDao, system_log::SystemLogDao,
+    web_log::WebLogDao,
+};
 
 pub(crate) mod service;
 pub use service::{
-    api_operation::ApiOperationService, system_log::SystemLogService, web_log::WebLogService,
+    api_operation::ApiOperationService, purge::LogPurgeService, system_log::SystemLogService,
+    web_log::WebLogService,
 };
 
 pub(crate) mod controller;
@@ -21,3 +25,6 @@ pub use router::{
     LogRouter, api_operation::ApiOperationRouter, system_log::SystemLogRouter,
     web_log::WebLogRouter,
 };
+
+pub mod task;
+pub use task::{RetentionTask, TaskMonitor, TaskStatus};
@@ -1,6 +1,12 @@
 //! API操作日志
 
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    middleware::from_fn,
+    routing::{get, post},
+};
+
+use user::guard::admin_guard_layer;
 
 use crate::controller::api_operation::ApiOperationController;
 
@@ -10,9 +16,16 @@ pub struct ApiOperationRouter;
 impl ApiOperationRouter {
     /// 注册`API操作日志管理`路由
     pub fn register() -> Router {
+        // 仅管理员可访问
+        let admin_router = Router::new()
+            .route("/purge", post(ApiOperationController::purge))
+            .route("/export", get(ApiOperationController::export))
+            .route_layer(from_fn(admin_guard_layer));
+
         Router::new().nest(
             "/api-operation-logs",
             Router::new()
+                .merge(admin_router)
                 .route(
                     "/",
                     get(ApiOperationController::list).post(ApiOperationController::create),
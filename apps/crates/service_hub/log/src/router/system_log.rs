@@ -1,6 +1,12 @@
 //! 系统日志
 
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    middleware::from_fn,
+    routing::{get, post},
+};
+
+use user::guard::admin_guard_layer;
 
 use crate::controller::system_log::SystemLogController;
 
@@ -10,9 +16,17 @@ pub struct SystemLogRouter;
 impl SystemLogRouter {
     /// 注册`系统日志管理`路由
     pub fn register() -> Router {
+        // 仅管理员可访问
+        let admin_router = Router::new()
+            .route("/purge", post(SystemLogController::purge))
+            .route("/export", get(SystemLogController::export))
+            .route("/tail", get(SystemLogController::tail))
+            .route_layer(from_fn(admin_guard_layer));
+
         Router::new().nest(
             "/system-logs",
             Router::new()
+                .merge(admin_router)
                 .route(
                     "/",
                     get(SystemLogController::list).post(SystemLogController::create),
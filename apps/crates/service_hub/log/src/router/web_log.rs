@@ -1,6 +1,12 @@
 //! WEB日志管理
 
-use axum::{Router, routing::get};
+use axum::{
+    Router,
+    middleware::from_fn,
+    routing::{get, post},
+};
+
+use user::guard::admin_guard_layer;
 
 use crate::controller::web_log::WebLogController;
 
@@ -10,9 +16,16 @@ pub struct WebLogRouter;
 impl WebLogRouter {
     /// 注册`WEB日志管理`路由
     pub fn register() -> Router {
+        // 仅管理员可访问
+        let admin_router = Router::new()
+            .route("/purge", post(WebLogController::purge))
+            .route("/export", get(WebLogController::export))
+            .route_layer(from_fn(admin_guard_layer));
+
         Router::new().nest(
             "/web-logs",
             Router::new()
+                .merge(admin_router)
                 .route(
                     "/",
                     get(WebLogController::list).post(WebLogController::create),
@@ -1,5 +1,8 @@
 //! API操作日志
 
+use std::sync::Arc;
+
+use futures::Stream;
 use log::error;
 use nject::injectable;
 use sea_orm::Set;
@@ -12,6 +15,7 @@ use crate::{
     dto::api_operation::{
         CreateApiOperationReq, DeleteApiOperationReq, GetApiOperationReq, GetApiOperationsReq,
     },
+    service::export::batch_stream,
 };
 
 /// 服务层
@@ -34,6 +38,23 @@ impl ApiOperationService {
         Ok((results, total))
     }
 
+    /// 导出数据, 按主键顺序分批查询并逐行输出
+    pub fn export(
+        self,
+        req: GetApiOperationsReq,
+    ) -> impl Stream<Item = Result<log_api_operation::Model, ErrorMsg>> + Send + 'static {
+        let dao = Arc::new(self.system_dao);
+        batch_stream(
+            "操作日志",
+            move |after_id, limit| {
+                let dao = dao.clone();
+                let req = req.clone();
+                async move { dao.batch(req, after_id, limit).await }
+            },
+            |model| model.id,
+        )
+    }
+
     /// 获取详情数据
     pub async fn info(
         &self,
@@ -0,0 +1,49 @@
+//! 日志导出
+
+use std::future::Future;
+
+use futures::{Stream, StreamExt, stream};
+use log::error;
+use sea_orm::DbErr;
+
+use err_code::{Error, ErrorMsg};
+
+/// 每批次查询的数量
+pub const EXPORT_BATCH_SIZE: u64 = 500;
+
+/// 按主键顺序分批查询, 转换为逐行输出的数据流
+///
+/// `fetch` 接收上一批次的最大ID, 返回下一批次的数据;
+/// 数据量不足一个批次或查询失败时结束.
+pub(crate) fn batch_stream<T, F, Fut>(
+    name: &'static str,
+    fetch: F,
+    id: fn(&T) -> i32,
+) -> impl Stream<Item = Result<T, ErrorMsg>> + Send + 'static
+where
+    T: Send + 'static,
+    F: Fn(i32, u64) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Vec<T>, DbErr>> + Send + 'static,
+{
+    stream::unfold(Some(0), move |after_id| {
+        let fut = after_id.map(|after_id| fetch(after_id, EXPORT_BATCH_SIZE));
+        async move {
+            let results = match fut?.await {
+                Ok(v) => v,
+                Err(err) => {
+                    error!("导出{}失败, err: {:#?}", name, err);
+                    let err = Error::DbQueryError.into_err_with_msg(&format!("导出{name}失败"));
+                    return Some((vec![Err(err)], None));
+                }
+            };
+
+            let next = if (results.len() as u64) < EXPORT_BATCH_SIZE {
+                None
+            } else {
+                results.last().map(id)
+            };
+            Some((results.into_iter().map(Ok).collect::<Vec<_>>(), next))
+        }
+    })
+    .flat_map(stream::iter)
+}
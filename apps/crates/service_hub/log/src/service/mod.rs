@@ -1,4 +1,6 @@
 //! 服务层
 pub mod api_operation;
+pub mod export;
+pub mod purge;
 pub mod system_log;
 pub mod web_log;
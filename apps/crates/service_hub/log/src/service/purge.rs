@@ -0,0 +1,263 @@
+//! 日志清理
+
+use std::time::Duration;
+
+use log::{error, info};
+use nject::injectable;
+
+use entity::log::{LogApiOperationEntity, LogSystemEntity, LogWebEntity};
+use err_code::{Error, ErrorMsg};
+use logger::config::{RetentionConfig, RetentionPolicy};
+
+use crate::{
+    dao::purge::{LogPurgeDao, LogTable},
+    dto::purge::{PurgeLogsReq, PurgeLogsResp},
+    enums::log_kind::LogKind,
+};
+
+/// 服务层
+#[injectable]
+pub struct LogPurgeService {
+    purge_dao: LogPurgeDao,
+}
+
+impl LogPurgeService {
+    /// 手动清理日志
+    pub async fn purge_logs(
+        &self,
+        kind: LogKind,
+        config: &RetentionConfig,
+        req: PurgeLogsReq,
+    ) -> Result<PurgeLogsResp, ErrorMsg> {
+        let mut policy = RetentionPolicy {
+            max_age_days: req.max_age_days,
+            max_rows: req.max_rows,
+        };
+        if policy.is_empty() {
+            policy = kind.policy(config).clone();
+        }
+        if policy.is_empty() {
+            error!("未配置{}保留策略", kind.name());
+            return Err(Error::InvalidParameter("max_age_days/max_rows".to_string())
+                .into_err_with_msg("请指定最长保留天数或最多保留行数"));
+        }
+
+        let count = self.purge(kind, &policy, config, req.dry_run).await?;
+        Ok(PurgeLogsResp {
+            count,
+            dry_run: req.dry_run,
+        })
+    }
+
+    /// 按保留策略清理日志, 返回清理的数量
+    ///
+    /// `dry_run` 为 `true` 时仅返回待清理的数量
+    pub async fn purge(
+        &self,
+        kind: LogKind,
+        policy: &RetentionPolicy,
+        config: &RetentionConfig,
+        dry_run: bool,
+    ) -> Result<u64, ErrorMsg> {
+        match kind {
+            LogKind::System => {
+                self.purge_table::<LogSystemEntity>(kind, policy, config, dry_run)
+                    .await
+            }
+            LogKind::Web => {
+                self.purge_table::<LogWebEntity>(kind, policy, config, dry_run)
+                    .await
+            }
+            LogKind::ApiOperation => {
+                self.purge_table::<LogApiOperationEntity>(kind, policy, config, dry_run)
+                    .await
+            }
+        }
+    }
+
+    /// 回收数据库空间, 仅对 SQLite 生效
+    pub async fn vacuum(&self) -> Result<bool, ErrorMsg> {
+        self.purge_dao.vacuum().await.map_err(|err| {
+            error!("回收数据库空间失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("回收数据库空间失败")
+        })
+    }
+
+    /// 分批清理日志表
+    async fn purge_table<E>(
+        &self,
+        kind: LogKind,
+        policy: &RetentionPolicy,
+        config: &RetentionConfig,
+        dry_run: bool,
+    ) -> Result<u64, ErrorMsg>
+    where
+        E: LogTable,
+        E::Model: Sync,
+    {
+        let cond = self
+            .purge_dao
+            .condition::<E>(policy.max_age_days, policy.max_rows)
+            .await
+            .map_err(|err| {
+                error!("查询{}清理条件失败, err: {:#?}", kind.name(), err);
+                Error::DbQueryError.into_err_with_msg("查询日志清理条件失败")
+            })?;
+        let Some(cond) = cond else {
+            return Ok(0);
+        };
+
+        if dry_run {
+            return self.purge_dao.count::<E>(cond).await.map_err(|err| {
+                error!("查询{}待清理数量失败, err: {:#?}", kind.name(), err);
+                Error::DbQueryError.into_err_with_msg("查询日志待清理数量失败")
+            });
+        }
+
+        // 分批删除, 避免长时间锁定数据库
+        let batch_size = config.batch_size.max(1);
+        let mut total = 0;
+        loop {
+            let rows = self
+                .purge_dao
+                .delete_batch::<E>(cond.clone(), batch_size)
+                .await
+                .map_err(|err| {
+                    error!("清理{}失败, err: {:#?}", kind.name(), err);
+                    Error::DbBatchDeleteError.into_err_with_msg("清理日志失败")
+                })?;
+            total += rows;
+            if rows < batch_size {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(config.batch_interval)).await;
+        }
+
+        if total > 0 {
+            info!("清理{}完成, 数量: {}", kind.name(), total);
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{Duration, Local};
+    use database::PoolTrait;
+    use entity::log::{LogSystemEntity, log_system};
+    use err_code::Error;
+    use inject::InjectProvider;
+    use sea_orm::{ActiveModelTrait, ActiveValue::NotSet, EntityTrait, PaginatorTrait};
+
+    use super::*;
+
+    async fn provider() -> (InjectProvider, Arc<dyn PoolTrait>) {
+        let provider = InjectProvider::mock().await;
+        let db = provider.provide();
+        (provider, db)
+    }
+
+    /// 添加系统日志, 前 `old` 条为 10 天前的日志
+    async fn seed(db: &Arc<dyn PoolTrait>, total: usize, old: usize) {
+        for i in 0..total {
+            let days = if i < old { 10 } else { 0 };
+            let mut active_model: log_system::ActiveModel = log_system::Model {
+                name: "test".to_string(),
+                target: "purge_test".to_string(),
+                level: "INFO".to_string(),
+                kind: "event".to_string(),
+                message: Some(format!("log {i}")),
+                created_at: Some(Local::now().naive_local() - Duration::days(days)),
+                ..Default::default()
+            }
+            .into();
+            active_model.id = NotSet;
+            active_model.insert(db.db()).await.expect("insert log");
+        }
+    }
+
+    async fn count(db: &Arc<dyn PoolTrait>) -> u64 {
+        LogSystemEntity::find()
+            .count(db.db())
+            .await
+            .expect("count logs")
+    }
+
+    #[tokio::test]
+    async fn test_purge_logs() {
+        let (provider, db) = provider().await;
+        seed(&db, 10, 4).await;
+
+        let config = RetentionConfig {
+            batch_size: 3,
+            batch_interval: 0,
+            ..Default::default()
+        };
+        let purge_service: LogPurgeService = provider.provide();
+
+        // 未指定且未配置保留策略
+        let err = purge_service
+            .purge_logs(LogKind::System, &config, PurgeLogsReq::default())
+            .await
+            .expect_err("missing policy");
+        assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+
+        // 仅统计, 不删除
+        let resp = purge_service
+            .purge_logs(
+                LogKind::System,
+                &config,
+                PurgeLogsReq {
+                    max_age_days: Some(7),
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("dry run");
+        assert_eq!(resp.count, 4);
+        assert_eq!(count(&db).await, 10);
+
+        // 按天数清理, 分批删除
+        let resp = purge_service
+            .purge_logs(
+                LogKind::System,
+                &config,
+                PurgeLogsReq {
+                    max_age_days: Some(7),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("purge by age");
+        assert_eq!(resp.count, 4);
+        assert_eq!(count(&db).await, 6);
+
+        // 使用配置的保留策略, 按行数清理
+        let config = RetentionConfig {
+            system: RetentionPolicy {
+                max_age_days: None,
+                max_rows: Some(2),
+            },
+            ..config
+        };
+        let resp = purge_service
+            .purge_logs(LogKind::System, &config, PurgeLogsReq::default())
+            .await
+            .expect("purge by rows");
+        assert_eq!(resp.count, 4);
+        let messages = LogSystemEntity::find()
+            .all(db.db())
+            .await
+            .expect("query logs")
+            .into_iter()
+            .filter_map(|v| v.message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["log 8", "log 9"]);
+
+        // SQLite 回收空间
+        assert!(purge_service.vacuum().await.expect("vacuum"));
+    }
+}
@@ -1,15 +1,24 @@
 //! 系统日志
 
-use log::error;
+use std::sync::Arc;
+
+use futures::{Stream, stream};
+use log::{error, warn};
 use nject::injectable;
 
 use entity::log::log_system;
 use err_code::{Error, ErrorMsg};
+use logger::config::Level;
+use tokio::sync::broadcast::error::RecvError;
 use utils::json::struct_to_struct;
 
 use crate::{
     dao::system_log::SystemLogDao,
-    dto::system_log::{CreateSystemLogReq, DeleteSystemLogReq, GetSystemLogReq, GetSystemLogsReq},
+    dto::system_log::{
+        CreateSystemLogReq, DeleteSystemLogReq, GetSystemLogReq, GetSystemLogsReq,
+        TailSystemLogEvent, TailSystemLogsReq,
+    },
+    service::export::batch_stream,
 };
 
 /// 服务层
@@ -32,6 +41,62 @@ impl SystemLogService {
         Ok((results, total))
     }
 
+    /// 导出数据, 按主键顺序分批查询并逐行输出
+    pub fn export(
+        self,
+        req: GetSystemLogsReq,
+    ) -> impl Stream<Item = Result<log_system::Model, ErrorMsg>> + Send + 'static {
+        let dao = Arc::new(self.system_dao);
+        batch_stream(
+            "系统日志",
+            move |after_id, limit| {
+                let dao = dao.clone();
+                let req = req.clone();
+                async move { dao.batch(req, after_id, limit).await }
+            },
+            |model| model.id,
+        )
+    }
+
+    /// 实时订阅系统日志, 按日志级别及目标过滤
+    pub fn tail(
+        &self,
+        req: TailSystemLogsReq,
+    ) -> impl Stream<Item = TailSystemLogEvent> + Send + use<> {
+        let rx = logger::tail::subscribe();
+        stream::unfold((rx, req), |(mut rx, req)| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(log) => {
+                        if Self::tail_matches(&req, &log) {
+                            return Some((TailSystemLogEvent::Log(Box::new(log)), (rx, req)));
+                        }
+                    }
+                    Err(RecvError::Lagged(count)) => {
+                        warn!("实时日志订阅处理过慢, 丢弃日志数量: {}", count);
+                        return Some((TailSystemLogEvent::Lagged(count), (rx, req)));
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// 实时日志是否满足过滤条件
+    fn tail_matches(req: &TailSystemLogsReq, log: &log_system::Model) -> bool {
+        if let Some(level) = &req.level
+            && Level::from(log.level.to_lowercase()) < *level
+        {
+            return false;
+        }
+        if let Some(target) = &req.target
+            && !log.target.starts_with(target.as_str())
+        {
+            return false;
+        }
+        true
+    }
+
     /// 获取详情数据
     pub async fn info(&self, req: GetSystemLogReq) -> Result<log_system::Model, ErrorMsg> {
         let result = self
@@ -75,3 +140,49 @@ impl SystemLogService {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use inject::InjectProvider;
+
+    use super::*;
+
+    async fn provider() -> InjectProvider {
+        InjectProvider::mock().await
+    }
+
+    fn log(level: &str, target: &str, message: &str) -> log_system::Model {
+        log_system::Model {
+            level: level.to_string(),
+            target: target.to_string(),
+            message: Some(message.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tail() {
+        let provider = provider().await;
+        let system_service: SystemLogService = provider.provide();
+        let stream = system_service.tail(TailSystemLogsReq {
+            level: Some(Level::Warn),
+            target: Some("tail_test".to_string()),
+        });
+
+        logger::tail::publish(&log("INFO", "tail_test", "info"));
+        logger::tail::publish(&log("WARN", "tail_test::inner", "warn"));
+        logger::tail::publish(&log("ERROR", "other", "other"));
+        logger::tail::publish(&log("ERROR", "tail_test", "error"));
+
+        let messages = stream
+            .take(2)
+            .map(|event| match event {
+                TailSystemLogEvent::Log(log) => log.message.unwrap_or_default(),
+                TailSystemLogEvent::Lagged(count) => format!("lagged {count}"),
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(messages, vec!["warn", "error"]);
+    }
+}
@@ -1,5 +1,8 @@
 //! WEB日志管理
 
+use std::sync::Arc;
+
+use futures::Stream;
 use log::error;
 use nject::injectable;
 use sea_orm::Set;
@@ -10,6 +13,7 @@ use err_code::{Error, ErrorMsg};
 use crate::{
     dao::web_log::WebLogDao,
     dto::web_log::{CreateWebLogReq, GetWebLogReq, GetWebLogsReq},
+    service::export::batch_stream,
 };
 
 /// 服务层
@@ -29,6 +33,23 @@ impl WebLogService {
         Ok((results, total))
     }
 
+    /// 导出数据, 按主键顺序分批查询并逐行输出
+    pub fn export(
+        self,
+        req: GetWebLogsReq,
+    ) -> impl Stream<Item = Result<log_web::Model, ErrorMsg>> + Send + 'static {
+        let dao = Arc::new(self.log_web_dao);
+        batch_stream(
+            "WEB日志",
+            move |after_id, limit| {
+                let dao = dao.clone();
+                let req = req.clone();
+                async move { dao.batch(req, after_id, limit).await }
+            },
+            |model| model.id,
+        )
+    }
+
     /// 获取详情数据
     pub async fn info(&self, req: GetWebLogReq) -> Result<log_web::Model, ErrorMsg> {
         let result = self
@@ -0,0 +1,189 @@
+//! 后台任务
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use chrono::{Local, NaiveDateTime};
+use log::error;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    task::JoinHandle,
+    time::{Instant, interval_at},
+};
+
+use inject::AInjectProvider;
+use logger::config::RetentionConfig;
+
+use crate::{enums::log_kind::LogKind, service::purge::LogPurgeService};
+
+/// 日志保留清理任务
+///
+/// 按保留策略定期分批清理日志表, 并按需对 SQLite 执行 VACUUM
+pub struct RetentionTask {
+    provider: AInjectProvider,
+    config: RetentionConfig,
+    monitor: TaskMonitor,
+}
+
+impl RetentionTask {
+    pub fn new(provider: AInjectProvider, config: RetentionConfig) -> Self {
+        RetentionTask {
+            provider,
+            config,
+            monitor: TaskMonitor::default(),
+        }
+    }
+
+    /// 获取任务状态监控
+    pub fn monitor(&self) -> TaskMonitor {
+        self.monitor.clone()
+    }
+
+    /// 启动后台清理任务, 未启用时返回 `None`
+    pub fn spawn(self) -> Option<JoinHandle<()>> {
+        if !self.config.enable {
+            return None;
+        }
+        self.monitor.inner.enabled.store(true, Ordering::Relaxed);
+        self.monitor.inner.running.store(true, Ordering::Relaxed);
+        Some(tokio::spawn(self.run()))
+    }
+
+    /// 循环执行清理
+    async fn run(self) {
+        // 任务退出或异常终止时标记为未运行
+        let _guard = RunningGuard(self.monitor.clone());
+        let period = Duration::from_secs(self.config.interval.max(1));
+        let vacuum_interval = Duration::from_secs(self.config.vacuum_interval);
+        let mut interval = interval_at(Instant::now() + period, period);
+        let mut last_vacuum = Instant::now();
+
+        loop {
+            interval.tick().await;
+
+            let purge_service: LogPurgeService = self.provider.provide();
+            for kind in LogKind::ALL {
+                let policy = kind.policy(&self.config);
+                if policy.is_empty() {
+                    continue;
+                }
+                if let Err(err) = purge_service.purge(kind, policy, &self.config, false).await {
+                    error!("清理{}失败, err: {:#?}", kind.name(), err);
+                }
+            }
+
+            if !vacuum_interval.is_zero() && last_vacuum.elapsed() >= vacuum_interval {
+                if let Err(err) = purge_service.vacuum().await {
+                    error!("回收数据库空间失败, err: {:#?}", err);
+                }
+                last_vacuum = Instant::now();
+            }
+            self.monitor.set_last_run_at(Local::now().naive_local());
+        }
+    }
+}
+
+/// 后台任务状态
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskStatus {
+    /// 是否启用
+    pub enabled: bool,
+    /// 是否运行中
+    pub running: bool,
+    /// 最近一次执行时间
+    pub last_run_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Default)]
+struct TaskState {
+    enabled: AtomicBool,
+    running: AtomicBool,
+    last_run_at: Mutex<Option<NaiveDateTime>>,
+}
+
+/// 后台任务状态监控
+#[derive(Debug, Clone, Default)]
+pub struct TaskMonitor {
+    inner: Arc<TaskState>,
+}
+
+impl TaskMonitor {
+    /// 获取当前任务状态
+    pub fn status(&self) -> TaskStatus {
+        let last_run_at = *self
+            .inner
+            .last_run_at
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        TaskStatus {
+            enabled: self.inner.enabled.load(Ordering::Relaxed),
+            running: self.inner.running.load(Ordering::Relaxed),
+            last_run_at,
+        }
+    }
+
+    /// 任务是否正常, 未启用的任务视为正常
+    pub fn is_healthy(&self) -> bool {
+        let status = self.status();
+        !status.enabled || status.running
+    }
+
+    fn set_last_run_at(&self, at: NaiveDateTime) {
+        *self
+            .inner
+            .last_run_at
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = Some(at);
+    }
+}
+
+/// 任务运行标记, 释放时标记任务已停止
+struct RunningGuard(TaskMonitor);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.inner.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use inject::InjectProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_monitor() {
+        let provider = Arc::new(InjectProvider::mock().await);
+
+        // 未启用的任务视为正常
+        let task = RetentionTask::new(provider.clone(), RetentionConfig::default());
+        let monitor = task.monitor();
+        assert!(task.spawn().is_none());
+        assert!(monitor.is_healthy());
+        assert!(!monitor.status().enabled);
+
+        let config = RetentionConfig {
+            enable: true,
+            interval: 1,
+            ..Default::default()
+        };
+        let task = RetentionTask::new(provider, config);
+        let monitor = task.monitor();
+        let handle = task.spawn().expect("spawn task");
+        assert!(monitor.status().running);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(monitor.status().last_run_at.is_some());
+
+        // 任务终止后标记为未运行
+        handle.abort();
+        let _ = handle.await;
+        assert!(!monitor.is_healthy());
+    }
+}
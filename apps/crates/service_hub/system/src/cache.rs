@@ -0,0 +1,85 @@
+//! 缓存
+//!
+//! - 字典选项缓存: 缓存字典维度编码对应的已启用字典选项, 字典维度或字典数据变更时清空.
+//! - 系统配置缓存: 缓存配置编码对应的已启用配置值, 配置变更时清空.
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
+
+use crate::dto::dict_data::DictOption;
+
+/// 全局字典选项缓存
+static DICT_OPTIONS: LazyLock<RwLock<DictOptionsCache>> =
+    LazyLock::new(|| RwLock::new(DictOptionsCache::default()));
+
+/// 全局系统配置缓存
+static CONFIG_VALUES: LazyLock<RwLock<ConfigCache>> =
+    LazyLock::new(|| RwLock::new(ConfigCache::default()));
+
+#[derive(Default)]
+pub(crate) struct DictOptionsCache {
+    /// 缓存版本, 每次清空时递增
+    version: u64,
+    entries: HashMap<String, Vec<DictOption>>,
+}
+
+impl DictOptionsCache {
+    /// 获取缓存的字典选项及当前缓存版本
+    pub(crate) fn get(code: &str) -> (Option<Vec<DictOption>>, u64) {
+        let cache = DICT_OPTIONS.read().unwrap_or_else(|err| err.into_inner());
+        (cache.entries.get(code).cloned(), cache.version)
+    }
+
+    /// 写入缓存
+    ///
+    /// 查询期间缓存已被清空时不写入, 避免缓存变更前的数据.
+    pub(crate) fn set(code: String, options: Vec<DictOption>, version: u64) {
+        let mut cache = DICT_OPTIONS.write().unwrap_or_else(|err| err.into_inner());
+        if cache.version != version {
+            return;
+        }
+        cache.entries.insert(code, options);
+    }
+
+    /// 清空缓存
+    pub(crate) fn clear() {
+        let mut cache = DICT_OPTIONS.write().unwrap_or_else(|err| err.into_inner());
+        cache.version += 1;
+        cache.entries.clear();
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ConfigCache {
+    /// 缓存版本, 每次清空时递增
+    version: u64,
+    /// 配置编码对应的配置值, 配置不存在或已停用时为 `None`
+    entries: HashMap<String, Option<String>>,
+}
+
+impl ConfigCache {
+    /// 获取缓存的配置值及当前缓存版本
+    pub(crate) fn get(code: &str) -> (Option<Option<String>>, u64) {
+        let cache = CONFIG_VALUES.read().unwrap_or_else(|err| err.into_inner());
+        (cache.entries.get(code).cloned(), cache.version)
+    }
+
+    /// 写入缓存
+    ///
+    /// 查询期间缓存已被清空时不写入, 避免缓存变更前的数据.
+    pub(crate) fn set(code: String, value: Option<String>, version: u64) {
+        let mut cache = CONFIG_VALUES.write().unwrap_or_else(|err| err.into_inner());
+        if cache.version != version {
+            return;
+        }
+        cache.entries.insert(code, value);
+    }
+
+    /// 清空缓存
+    pub(crate) fn clear() {
+        let mut cache = CONFIG_VALUES.write().unwrap_or_else(|err| err.into_inner());
+        cache.version += 1;
+        cache.entries.clear();
+    }
+}
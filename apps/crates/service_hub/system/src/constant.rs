@@ -1 +1,80 @@
 //! 常量
+use crate::enums::config::ConfigValueType;
+
+/// 文件资源访问地址
+pub const FILE_RESOURCE_URL: &str = "/api/v1/system/file-resources";
+
+/// 系统配置声明
+///
+/// 声明的配置编码在写入时按类型校验配置值, 未配置或配置已停用时读取默认值.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigDefinition {
+    /// 配置编码
+    pub code: &'static str,
+    /// 配置名称
+    pub name: &'static str,
+    /// 配置值类型
+    pub value_type: ConfigValueType,
+    /// 默认值
+    pub default: &'static str,
+}
+
+impl ConfigDefinition {
+    /// 通过配置编码查找配置声明
+    pub fn find(code: &str) -> Option<&'static ConfigDefinition> {
+        CONFIG_DEFINITIONS.iter().find(|v| v.code == code)
+    }
+}
+
+/// 已声明的系统配置
+pub const CONFIG_DEFINITIONS: &[ConfigDefinition] = &[
+    ConfigDefinition {
+        code: "site_name",
+        name: "站点名称",
+        value_type: ConfigValueType::String,
+        default: "Debox Pro Tools",
+    },
+    ConfigDefinition {
+        code: "user_register_enabled",
+        name: "开放用户注册",
+        value_type: ConfigValueType::Bool,
+        default: "true",
+    },
+    ConfigDefinition {
+        code: "login_captcha_enabled",
+        name: "登录验证码",
+        value_type: ConfigValueType::Bool,
+        default: "true",
+    },
+    ConfigDefinition {
+        code: "login_max_failures",
+        name: "登录失败锁定次数",
+        value_type: ConfigValueType::Int,
+        default: "5",
+    },
+    ConfigDefinition {
+        code: "notice_banner",
+        name: "公告横幅",
+        value_type: ConfigValueType::Json,
+        default: "{}",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use migration::seed::DEFAULT_CONFIGS;
+
+    use super::*;
+
+    #[test]
+    fn test_seed_configs() {
+        // 预设系统配置须与声明保持一致
+        assert_eq!(DEFAULT_CONFIGS.len(), CONFIG_DEFINITIONS.len());
+        for (code, name, value) in DEFAULT_CONFIGS {
+            let definition = ConfigDefinition::find(code).expect("config declared");
+            assert_eq!(definition.name, *name);
+            assert_eq!(definition.default, *value);
+            assert!(definition.value_type.check(value).is_ok());
+        }
+    }
+}
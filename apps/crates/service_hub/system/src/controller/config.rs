@@ -1,17 +1,22 @@
 //! 配置管理
 
-use axum_response::{Responder, Response};
-use axum_validator::{Extension, Json, Query};
+use axum::{
+    http::{HeaderValue, header},
+    response::{IntoResponse, Response as HttpResponse},
+};
+use axum_response::{Responder, Response, ResponseErr};
+use axum_validator::{Extension, Json, Path, Query};
 
 use inject::AInjectProvider;
 
 use crate::{
     dto::config::{
-        CreateConfigReq, CreateConfigResp, DeleteConfigReq, DeleteConfigResp, GetConfigReq,
-        GetConfigResp, GetConfigTreeReq, GetConfigTreeResp, GetConfigsReq, GetConfigsResp,
+        CreateConfigReq, CreateConfigResp, DeleteConfigReq, DeleteConfigResp, ExportConfigsReq,
+        GetConfigByCodeReq, GetConfigByCodeResp, GetConfigReq, GetConfigResp, GetConfigTreeReq,
+        GetConfigTreeResp, GetConfigsReq, GetConfigsResp, ImportConfigsReq, ImportConfigsResp,
         UpdateConfigReq, UpdateConfigResp, UpdateConfigStatusReq, UpdateConfigStatusResp,
     },
-    service::config::ConfigService,
+    service::{config::ConfigService, system_config::SystemConfig},
 };
 
 /// 控制器
@@ -54,6 +59,18 @@ impl ConfigController {
         Ok(resp)
     }
 
+    /// 通过配置编码获取配置值
+    pub async fn info_by_code(
+        Extension(provider): Extension<AInjectProvider>,
+        Path(req): Path<GetConfigByCodeReq>,
+    ) -> Responder<GetConfigByCodeResp> {
+        let system_config: SystemConfig = provider.provide();
+        let result = system_config.value(&req.code).await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+
     /// 添加配置
     pub async fn create(
         Extension(provider): Extension<AInjectProvider>,
@@ -101,4 +118,52 @@ impl ConfigController {
         let resp = Response::<()>::ok().to_json()?;
         Ok(resp)
     }
+
+    /// 导出配置
+    pub async fn export(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(req): Query<ExportConfigsReq>,
+    ) -> Result<HttpResponse, ResponseErr> {
+        let format = req.format;
+        let config_service: ConfigService = provider.provide();
+        let content = config_service.export(req).await?;
+
+        let disposition = format!("attachment; filename=\"configs.{}\"", format.extension());
+        let headers = [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&disposition)
+                    .unwrap_or_else(|_| HeaderValue::from_static("attachment")),
+            ),
+        ];
+        Ok((headers, content).into_response())
+    }
+
+    /// 预览导入配置的变更
+    pub async fn preview_import(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<ImportConfigsReq>,
+    ) -> Responder<ImportConfigsResp> {
+        let config_service: ConfigService = provider.provide();
+        let result = config_service.preview_import(req).await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+
+    /// 导入配置
+    pub async fn import(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<ImportConfigsReq>,
+    ) -> Responder<ImportConfigsResp> {
+        let config_service: ConfigService = provider.provide();
+        let result = config_service.import(req).await?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
 }
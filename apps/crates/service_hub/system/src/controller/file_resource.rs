@@ -0,0 +1,203 @@
+//! 文件资源管理
+
+use axum::{
+    body::Body,
+    extract::DefaultBodyLimit,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use axum_response::{Responder, Response as JsonResponse, ResponseErr};
+use axum_typed_multipart::TypedMultipart;
+use axum_validator::{Extension, Path, Query};
+use tokio_util::io::ReaderStream;
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::file_resource::{
+        FileBody, GetFileResourceReq, GetThumbnailReq, UploadFileResourceReq,
+        UploadFileResourceResp,
+    },
+    range::RangeRequest,
+    service::file_resource::FileResourceService,
+    storage::upload_config,
+};
+
+/// 控制器
+pub struct FileResourceController;
+
+impl FileResourceController {
+    /// 上传文件
+    pub async fn upload(
+        Extension(provider): Extension<AInjectProvider>,
+        TypedMultipart(req): TypedMultipart<UploadFileResourceReq>,
+    ) -> Responder<UploadFileResourceResp> {
+        let file_name = req.file.metadata.file_name.unwrap_or_default();
+        let file_resource_service: FileResourceService = provider.provide();
+        // 临时文件在请求结束后删除
+        let result = file_resource_service
+            .upload_file(file_name, req.file.contents.path().to_path_buf(), req.desc)
+            .await?;
+
+        let resp = JsonResponse::data(UploadFileResourceResp::from(result)).to_json()?;
+        Ok(resp)
+    }
+
+    /// 获取文件内容
+    ///
+    /// 支持 ETag 协商缓存及 Range 分段下载
+    pub async fn download(
+        Extension(provider): Extension<AInjectProvider>,
+        Path(req): Path<GetFileResourceReq>,
+        headers: HeaderMap,
+    ) -> Result<Response, ResponseErr> {
+        let file_resource_service: FileResourceService = provider.provide();
+        let result = file_resource_service.info_by_hash(req.hash).await?;
+
+        let etag = format!("\"{}\"", result.hash);
+        let mut resp_headers = cache_headers(&etag, &result.content_type);
+        resp_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+        if let Some(value) = headers.get(header::IF_NONE_MATCH)
+            && etag_matches(value, &etag)
+        {
+            return Ok((StatusCode::NOT_MODIFIED, resp_headers).into_response());
+        }
+
+        let size = result.size as u64;
+        let range = match headers.get(header::RANGE).and_then(|v| v.to_str().ok()) {
+            // If-Range 与当前文件不一致时返回完整内容
+            Some(_)
+                if headers
+                    .get(header::IF_RANGE)
+                    .is_some_and(|v| v.as_bytes() != etag.as_bytes()) =>
+            {
+                RangeRequest::Full
+            }
+            Some(value) => RangeRequest::parse(value, size),
+            None => RangeRequest::Full,
+        };
+
+        let (status, range) = match range {
+            RangeRequest::Full => (StatusCode::OK, None),
+            RangeRequest::Partial(range) => {
+                resp_headers.insert(
+                    header::CONTENT_RANGE,
+                    header_value(&range.content_range(size)),
+                );
+                (StatusCode::PARTIAL_CONTENT, Some(range))
+            }
+            RangeRequest::NotSatisfiable => {
+                resp_headers.insert(
+                    header::CONTENT_RANGE,
+                    header_value(&format!("bytes */{size}")),
+                );
+                return Ok((StatusCode::RANGE_NOT_SATISFIABLE, resp_headers).into_response());
+            }
+        };
+
+        let len = range.map_or(size, |v| v.len());
+        resp_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+
+        let body = match file_resource_service.read(&result, range).await? {
+            FileBody::Bytes(data) => Body::from(data),
+            FileBody::File(file) => Body::from_stream(ReaderStream::new(file)),
+        };
+        Ok((status, resp_headers, body).into_response())
+    }
+}
+
+impl FileResourceController {
+    /// 获取图片缩略图
+    pub async fn thumbnail(
+        Extension(provider): Extension<AInjectProvider>,
+        Path(path): Path<GetFileResourceReq>,
+        Query(req): Query<GetThumbnailReq>,
+        headers: HeaderMap,
+    ) -> Result<Response, ResponseErr> {
+        let etag = format!(
+            "\"{}-{}x{}\"",
+            path.hash,
+            req.width.unwrap_or(0),
+            req.height.unwrap_or(0)
+        );
+        if let Some(value) = headers.get(header::IF_NONE_MATCH)
+            && etag_matches(value, &etag)
+        {
+            let resp_headers = cache_headers(&etag, "");
+            return Ok((StatusCode::NOT_MODIFIED, resp_headers).into_response());
+        }
+
+        let file_resource_service: FileResourceService = provider.provide();
+        let (data, content_type) = file_resource_service.thumbnail(path.hash, req).await?;
+
+        let resp_headers = cache_headers(&etag, content_type);
+        Ok((resp_headers, data).into_response())
+    }
+}
+
+/// 文件响应头
+///
+/// 文件内容与HASH值一一对应, 可长期缓存; SVG 禁止执行脚本及加载外部资源
+fn cache_headers(etag: &str, content_type: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ETAG, header_value(etag));
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=31536000, immutable"),
+    );
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    if content_type.is_empty() {
+        return headers;
+    }
+    headers.insert(header::CONTENT_TYPE, header_value(content_type));
+    if content_type == "image/svg+xml" {
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'none'; style-src 'unsafe-inline'; sandbox"),
+        );
+    }
+    headers
+}
+
+/// 上传文件请求体大小上限
+pub(crate) fn upload_body_limit() -> DefaultBodyLimit {
+    // 预留 multipart 边界及其他字段的大小
+    DefaultBodyLimit::max(upload_config().max_bytes() as usize + 64 * 1024)
+}
+
+/// If-None-Match 是否匹配当前文件
+fn etag_matches(value: &HeaderValue, etag: &str) -> bool {
+    let Ok(value) = value.to_str() else {
+        return false;
+    };
+    value
+        .split(',')
+        .map(|v| v.trim().trim_start_matches("W/"))
+        .any(|v| v == "*" || v == etag)
+}
+
+fn header_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(value).unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = "\"abc\"";
+        assert!(etag_matches(&HeaderValue::from_static("\"abc\""), etag));
+        assert!(etag_matches(&HeaderValue::from_static("W/\"abc\""), etag));
+        assert!(etag_matches(
+            &HeaderValue::from_static("\"x\", \"abc\""),
+            etag
+        ));
+        assert!(etag_matches(&HeaderValue::from_static("*"), etag));
+        assert!(!etag_matches(&HeaderValue::from_static("\"abcd\""), etag));
+    }
+}
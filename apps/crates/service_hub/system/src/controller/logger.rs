@@ -0,0 +1,42 @@
+//! 日志级别管理
+
+use axum_response::{Responder, Response};
+use axum_validator::{Extension, Json, Query};
+
+use inject::AInjectProvider;
+
+use crate::{
+    dto::logger::{
+        GetLoggerFiltersReq, GetLoggerFiltersResp, UpdateLoggerFilterReq, UpdateLoggerFilterResp,
+    },
+    service::logger::LoggerService,
+};
+
+/// 控制器
+pub struct LoggerController;
+
+impl LoggerController {
+    /// 获取日志过滤器列表
+    pub async fn list(
+        Extension(provider): Extension<AInjectProvider>,
+        Query(_req): Query<GetLoggerFiltersReq>,
+    ) -> Responder<GetLoggerFiltersResp> {
+        let logger_service: LoggerService = provider.provide();
+        let (results, total) = logger_service.list()?;
+
+        let resp = Response::data_list(results, total).to_json()?;
+        Ok(resp)
+    }
+
+    /// 更新日志过滤器
+    pub async fn update(
+        Extension(provider): Extension<AInjectProvider>,
+        Json(req): Json<UpdateLoggerFilterReq>,
+    ) -> Responder<UpdateLoggerFilterResp> {
+        let logger_service: LoggerService = provider.provide();
+        let result = logger_service.update(req)?;
+
+        let resp = Response::data(result).to_json()?;
+        Ok(resp)
+    }
+}
@@ -1,16 +1,16 @@
 //! 配置管理
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use nject::injectable;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, QueryTrait, Set,
+    QuerySelect, QueryTrait, Set, TransactionTrait,
 };
 
 use database::{Pagination, PoolTrait};
 use entity::system::{ConfigEntity, config};
 
-use crate::dto::config::GetConfigsReq;
+use crate::dto::config::{GetConfigsReq, ImportConfigItem};
 
 /// 数据访问
 #[injectable]
@@ -110,4 +110,46 @@ impl ConfigDao {
         let result = ConfigEntity::delete_by_id(id).exec(self.db.db()).await?;
         Ok(result.rows_affected)
     }
+
+    /// 导入配置, 按配置编码新增或更新, 并按父节点配置编码重建父节点ID
+    ///
+    /// 父节点须在子节点之前, 所有写入在同一事务中完成.
+    pub async fn import(&self, items: Vec<ImportConfigItem>) -> Result<(), DbErr> {
+        let txn = self.db.db().begin().await?;
+
+        let mut ids: HashMap<String, i32> = ConfigEntity::find()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|v| (v.code, v.id))
+            .collect();
+        for item in items {
+            let pid = item
+                .parent_code
+                .as_ref()
+                .and_then(|code| ids.get(code).copied());
+            let mut active_model = config::ActiveModel {
+                pid: Set(pid),
+                name: Set(item.name),
+                code: Set(item.code.clone()),
+                value: Set(item.value),
+                sort: Set(item.sort),
+                desc: Set(item.desc),
+                status: Set(item.status),
+                ..Default::default()
+            };
+            match ids.get(&item.code) {
+                Some(id) => {
+                    active_model.id = Set(*id);
+                    active_model.update(&txn).await?;
+                }
+                None => {
+                    let model = active_model.insert(&txn).await?;
+                    ids.insert(model.code, model.id);
+                }
+            }
+        }
+
+        txn.commit().await
+    }
 }
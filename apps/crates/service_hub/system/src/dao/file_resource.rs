@@ -0,0 +1,37 @@
+//! 文件资源管理
+use std::sync::Arc;
+
+use nject::injectable;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter};
+
+use database::PoolTrait;
+use entity::system::{FileResourceEntity, file_resource};
+
+/// 数据访问
+#[injectable]
+pub struct FileResourceDao {
+    db: Arc<dyn PoolTrait>,
+}
+
+impl FileResourceDao {
+    /// 获取详情信息
+    pub async fn info(&self, id: i32) -> Result<Option<file_resource::Model>, DbErr> {
+        FileResourceEntity::find_by_id(id).one(self.db.db()).await
+    }
+
+    /// 通过文件HASH值获取详情信息
+    pub async fn info_by_hash(&self, hash: String) -> Result<Option<file_resource::Model>, DbErr> {
+        FileResourceEntity::find()
+            .filter(file_resource::Column::Hash.eq(hash))
+            .one(self.db.db())
+            .await
+    }
+
+    /// 添加详情信息
+    pub async fn create(
+        &self,
+        active_model: file_resource::ActiveModel,
+    ) -> Result<file_resource::Model, DbErr> {
+        active_model.insert(self.db.db()).await
+    }
+}
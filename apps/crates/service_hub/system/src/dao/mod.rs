@@ -1,2 +1,5 @@
 //! 数据层
 pub mod config;
+pub mod dict_data;
+pub mod dict_dimension;
+pub mod file_resource;
@@ -5,6 +5,10 @@ use validator::Validate;
 
 use entity::system::config;
 
+use database::utils::GenericTree;
+
+use crate::enums::config::{ConfigFormat, ConfigValueType};
+
 /// 查询配置列表 请求体
 #[derive(Default, Deserialize, Validate)]
 pub struct GetConfigsReq {
@@ -41,6 +45,33 @@ pub struct GetConfigResp {
     data: config::Model,
 }
 
+/// 通过配置编码查询配置值 请求体
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct GetConfigByCodeReq {
+    /// 配置编码
+    #[validate(length(min = 1, max = 64))]
+    pub code: String,
+}
+
+/// 配置值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigValue {
+    /// 配置编码
+    pub code: String,
+    /// 生效的配置值
+    pub value: String,
+    /// 配置值类型, 未声明的配置为空
+    pub value_type: Option<ConfigValueType>,
+    /// 是否为声明的默认值
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetConfigByCodeResp {
+    #[serde(flatten)]
+    data: ConfigValue,
+}
+
 /// 添加配置 请求体
 #[derive(Serialize, Deserialize, Validate)]
 pub struct CreateConfigReq {
@@ -124,3 +155,136 @@ pub struct GetConfigTreeResp {
     #[serde(flatten)]
     pub data: ConfigTreeItem,
 }
+
+/// 导出配置 请求体
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct ExportConfigsReq {
+    /// 子树根节点的配置编码, 为空时导出全部配置
+    pub code: Option<String>,
+    /// 导出格式
+    #[serde(default)]
+    pub format: ConfigFormat,
+}
+
+/// 导入配置 请求体
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportConfigsReq {
+    /// 导入格式
+    #[serde(default)]
+    pub format: ConfigFormat,
+    /// 导入内容
+    #[validate(length(min = 1))]
+    pub content: String,
+}
+
+/// 导入导出的配置节点, 不包含ID, 通过配置编码关联
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigNode {
+    /// 配置名称
+    pub name: String,
+    /// 配置编码(英文)
+    pub code: String,
+    /// 配置值
+    #[serde(default)]
+    pub value: Option<String>,
+    /// 排序
+    #[serde(default)]
+    pub sort: Option<i32>,
+    /// 配置描述
+    #[serde(default)]
+    pub desc: Option<String>,
+    /// 状态(false:停用,true:正常)
+    #[serde(default = "default_status")]
+    pub status: bool,
+    /// 子配置
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ConfigNode>,
+}
+
+fn default_status() -> bool {
+    true
+}
+
+impl From<GenericTree<config::Model>> for ConfigNode {
+    fn from(tree: GenericTree<config::Model>) -> Self {
+        ConfigNode {
+            name: tree.data.name,
+            code: tree.data.code,
+            value: tree.data.value,
+            sort: tree.data.sort,
+            desc: tree.data.desc,
+            status: tree.data.status,
+            children: tree.children.into_iter().map(ConfigNode::from).collect(),
+        }
+    }
+}
+
+/// 待导入的配置, 按父节点在前的顺序写入
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportConfigItem {
+    /// 父节点配置编码
+    pub parent_code: Option<String>,
+    /// 配置名称
+    pub name: String,
+    /// 配置编码(英文)
+    pub code: String,
+    /// 配置值
+    pub value: Option<String>,
+    /// 排序
+    pub sort: Option<i32>,
+    /// 配置描述
+    pub desc: Option<String>,
+    /// 状态(false:停用,true:正常)
+    pub status: bool,
+}
+
+/// 配置导入操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigDiffAction {
+    /// 新增
+    Create,
+    /// 更新
+    Update,
+}
+
+/// 配置字段变更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigFieldChange {
+    /// 字段名称
+    pub field: String,
+    /// 变更前的值
+    pub before: Option<String>,
+    /// 变更后的值
+    pub after: Option<String>,
+}
+
+/// 配置变更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// 配置编码
+    pub code: String,
+    /// 导入操作
+    pub action: ConfigDiffAction,
+    /// 字段变更
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+/// 配置导入结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigImportPlan {
+    /// 新增数量
+    pub created: u64,
+    /// 更新数量
+    pub updated: u64,
+    /// 未变更数量
+    pub unchanged: u64,
+    /// 变更列表
+    pub items: Vec<ConfigDiff>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportConfigsResp {
+    #[serde(flatten)]
+    data: ConfigImportPlan,
+}
@@ -0,0 +1,142 @@
+//! 字典数据管理
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use entity::system::dict_data;
+
+/// 查询字典数据列表 请求体
+#[derive(Default, Deserialize, Validate)]
+pub struct GetDictDatasReq {
+    /// 当前分页
+    pub page: u64,
+    /// 页面大小
+    pub page_size: u64,
+    /// 开始时间
+    pub start_time: Option<String>,
+    /// 结束时间
+    pub end_time: Option<String>,
+    /// 字典维度ID
+    pub dim_id: Option<i32>,
+    /// 字典项标签
+    pub label: Option<String>,
+    /// 返回所有数据
+    pub all: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDictDatasResp {
+    pub data_list: Vec<dict_data::Model>,
+    pub total: u64,
+}
+
+/// 查询字典数据信息 请求体
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct GetDictDataReq {
+    /// 字典项ID
+    pub id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDictDataResp {
+    #[serde(flatten)]
+    data: dict_data::Model,
+}
+
+/// 添加字典数据 请求体
+#[derive(Serialize, Deserialize, Validate)]
+pub struct CreateDictDataReq {
+    /// 字典维度ID
+    pub dim_id: i32,
+    /// 字典项标签
+    #[validate(length(min = 1, max = 64, message = "字典项标签长度必须在1-64之间"))]
+    pub label: String,
+    /// 字典项值
+    #[validate(length(min = 1, message = "字典项值不能为空"))]
+    pub value: String,
+    /// 排序
+    pub sort: Option<i32>,
+    /// 描述信息
+    pub desc: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateDictDataResp {}
+
+/// 更新字典数据 请求体
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct UpdateDictDataReq {
+    /// 字典项ID
+    pub id: i32,
+    /// 字典维度ID
+    pub dim_id: i32,
+    /// 字典项标签
+    #[validate(length(min = 1, max = 64, message = "字典项标签长度必须在1-64之间"))]
+    pub label: String,
+    /// 字典项值
+    #[validate(length(min = 1, message = "字典项值不能为空"))]
+    pub value: String,
+    /// 排序
+    pub sort: Option<i32>,
+    /// 描述信息
+    pub desc: Option<String>,
+    /// 状态(false:停用,true:正常)
+    pub status: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateDictDataResp {}
+
+/// 更新字典数据状态 请求体
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct UpdateDictDataStatusReq {
+    /// 字典项ID
+    pub id: i32,
+    /// 状态(false:停用,true:正常)
+    pub status: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateDictDataStatusResp {}
+
+/// 删除字典数据 请求体
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct DeleteDictDataReq {
+    /// 字典项ID
+    pub id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteDictDataResp {}
+
+/// 查询字典选项 请求体
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct GetDictOptionsReq {
+    /// 字典维度编码
+    #[validate(length(min = 1, max = 64))]
+    pub code: String,
+}
+
+/// 字典选项, 用于下拉框等组件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictOption {
+    /// 字典项标签
+    pub label: String,
+    /// 字典项值
+    pub value: String,
+}
+
+impl From<dict_data::Model> for DictOption {
+    fn from(model: dict_data::Model) -> Self {
+        DictOption {
+            label: model.label,
+            value: model.value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetDictOptionsResp {
+    pub data_list: Vec<DictOption>,
+    pub total: u64,
+}
@@ -0,0 +1,81 @@
+//! 文件资源管理
+
+use axum_typed_multipart::{FieldData, TryFromMultipart};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use tokio::{fs::File, io::Take};
+use validator::Validate;
+
+use entity::system::file_resource;
+
+use crate::constant::FILE_RESOURCE_URL;
+
+/// 获取文件 请求体
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct GetFileResourceReq {
+    /// 文件HASH值
+    #[validate(length(min = 1, max = 64))]
+    pub hash: String,
+}
+
+/// 获取缩略图 请求体
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct GetThumbnailReq {
+    /// 宽度, 不指定时按高度等比缩放
+    #[validate(range(min = 1, max = 2048, message = "缩略图宽度必须在1-2048之间"))]
+    pub width: Option<u32>,
+    /// 高度, 不指定时按宽度等比缩放
+    #[validate(range(min = 1, max = 2048, message = "缩略图高度必须在1-2048之间"))]
+    pub height: Option<u32>,
+}
+
+/// 上传文件 请求体
+#[derive(TryFromMultipart)]
+pub struct UploadFileResourceReq {
+    /// 文件, 大小由上传文件配置限制
+    #[form_data(limit = "unlimited")]
+    pub file: FieldData<NamedTempFile>,
+    /// 描述信息
+    pub desc: Option<String>,
+}
+
+/// 上传文件 响应体
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadFileResourceResp {
+    /// 文件ID
+    pub id: i32,
+    /// 文件名称
+    pub file_name: String,
+    /// 文件HASH值
+    pub hash: String,
+    /// 文件扩展名
+    pub extension: String,
+    /// 内容类型
+    pub content_type: String,
+    /// 文件大小, 单位为字节
+    pub size: i64,
+    /// 文件访问地址
+    pub url: String,
+}
+
+impl From<file_resource::Model> for UploadFileResourceResp {
+    fn from(model: file_resource::Model) -> Self {
+        UploadFileResourceResp {
+            id: model.id,
+            url: format!("{FILE_RESOURCE_URL}/{}", model.hash),
+            file_name: model.file_name,
+            hash: model.hash,
+            extension: model.extension,
+            content_type: model.content_type,
+            size: model.size,
+        }
+    }
+}
+
+/// 文件内容
+pub enum FileBody {
+    /// 数据库中的文件数据
+    Bytes(Vec<u8>),
+    /// 本地文件
+    File(Take<File>),
+}
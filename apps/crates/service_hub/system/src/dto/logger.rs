@@ -0,0 +1,34 @@
+//! 日志级别管理
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use logger::filter::{LayerFilter, LayerKind};
+
+/// 查询日志过滤器列表 请求体
+#[derive(Default, Deserialize, Validate)]
+pub struct GetLoggerFiltersReq {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetLoggerFiltersResp {
+    pub data_list: Vec<LayerFilter>,
+    pub total: u64,
+}
+
+/// 更新日志过滤器 请求体
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateLoggerFilterReq {
+    /// 日志输出层, console/console_bunyan/file/db
+    pub layer: LayerKind,
+    /// 过滤指令, 如 `info,sea_orm=warn`; 为空时恢复为启动时的配置
+    pub directives: Option<String>,
+    /// 自动恢复时间, 单位秒, 最长一天
+    #[validate(range(min = 1, max = 86400))]
+    pub revert_after: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateLoggerFilterResp {
+    #[serde(flatten)]
+    data: LayerFilter,
+}
@@ -1,2 +1,6 @@
 //! 数据传递层
 pub mod config;
+pub mod dict_data;
+pub mod dict_dimension;
+pub mod file_resource;
+pub mod logger;
@@ -0,0 +1,92 @@
+//! 配置管理
+
+use serde::{Deserialize, Serialize};
+
+/// 配置值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigValueType {
+    /// 字符串
+    String,
+    /// 布尔值
+    Bool,
+    /// 整数
+    Int,
+    /// JSON
+    Json,
+}
+
+impl ConfigValueType {
+    /// 校验配置值是否符合类型
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            ConfigValueType::String => Ok(()),
+            ConfigValueType::Bool => parse_bool(value)
+                .map(|_| ())
+                .ok_or_else(|| format!("配置值不是有效的布尔值: {value}")),
+            ConfigValueType::Int => value
+                .trim()
+                .parse::<i64>()
+                .map(|_| ())
+                .map_err(|_| format!("配置值不是有效的整数: {value}")),
+            ConfigValueType::Json => serde_json::from_str::<serde_json::Value>(value)
+                .map(|_| ())
+                .map_err(|err| format!("配置值不是有效的 JSON: {err}")),
+        }
+    }
+}
+
+/// 配置导入导出格式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFormat {
+    /// YAML
+    #[default]
+    Yaml,
+    /// JSON
+    Json,
+}
+
+impl ConfigFormat {
+    /// 内容类型
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ConfigFormat::Yaml => "application/yaml",
+            ConfigFormat::Json => "application/json",
+        }
+    }
+
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Json => "json",
+        }
+    }
+}
+
+/// 解析布尔配置值, 支持 `true`/`false`/`1`/`0`
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(ConfigValueType::String.check("any").is_ok());
+        assert!(ConfigValueType::Bool.check(" TRUE ").is_ok());
+        assert!(ConfigValueType::Bool.check("0").is_ok());
+        assert!(ConfigValueType::Bool.check("yes").is_err());
+        assert!(ConfigValueType::Int.check("-12").is_ok());
+        assert!(ConfigValueType::Int.check("1.5").is_err());
+        assert!(ConfigValueType::Json.check(r#"{"a": [1, 2]}"#).is_ok());
+        assert!(ConfigValueType::Json.check("{a: 1}").is_err());
+    }
+}
@@ -1 +1,2 @@
 //! 枚举
+pub mod config;
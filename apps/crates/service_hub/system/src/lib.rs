@@ -3,14 +3,32 @@ pub mod constant;
 pub mod dto;
 pub mod enums;
 
+pub(crate) mod cache;
+pub(crate) mod media;
+pub(crate) mod range;
+pub(crate) mod storage;
+
 pub(crate) mod dao;
-pub use dao::config::ConfigDao;
+pub use dao::{
+    config::ConfigDao, dict_data::DictDataDao, dict_dimension::DictDimensionDao,
+    file_resource::FileResourceDao,
+};
 
 pub(crate) mod service;
-pub use service::config::ConfigService;
+pub use service::{
+    config::ConfigService, dict_data::DictDataService, dict_dimension::DictDimensionService,
+    file_resource::FileResourceService, logger::LoggerService, system_config::SystemConfig,
+};
 
 pub(crate) mod controller;
-pub use controller::config::ConfigController;
+pub use controller::{
+    config::ConfigController, dict_data::DictDataController,
+    dict_dimension::DictDimensionController, file_resource::FileResourceController,
+    logger::LoggerController,
+};
 
 pub(crate) mod router;
-pub use router::{SystemRouter, config::ConfigRouter};
+pub use router::{
+    SystemRouter, config::ConfigRouter, dict_data::DictDataRouter,
+    dict_dimension::DictDimensionRouter, file_resource::FileResourceRouter, logger::LoggerRouter,
+};
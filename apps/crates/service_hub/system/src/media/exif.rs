@@ -0,0 +1,247 @@
+//! 移除图片元数据
+//!
+//! 直接移除 JPEG/PNG/WebP 中的 EXIF、XMP 及文本元数据, 不重新编码图片.
+//! 拍摄方向等信息一并移除.
+
+/// 元数据格式错误
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MalformedImage;
+
+/// 移除图片元数据, 不支持的格式原样返回
+pub fn strip(mime_type: &str, data: &[u8]) -> Result<Vec<u8>, MalformedImage> {
+    match mime_type {
+        "image/jpeg" => strip_jpeg(data),
+        "image/png" => strip_png(data),
+        "image/webp" => strip_webp(data),
+        _ => Ok(data.to_vec()),
+    }
+}
+
+/// JPEG: 移除 APP1 中的 EXIF 及 XMP 段
+fn strip_jpeg(data: &[u8]) -> Result<Vec<u8>, MalformedImage> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err(MalformedImage);
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..2]);
+
+    let mut pos = 2;
+    while pos < data.len() {
+        if data[pos] != 0xFF {
+            return Err(MalformedImage);
+        }
+        // 跳过填充字节
+        let mut marker_pos = pos + 1;
+        while data.get(marker_pos) == Some(&0xFF) {
+            marker_pos += 1;
+        }
+        let marker = *data.get(marker_pos).ok_or(MalformedImage)?;
+        let segment_start = marker_pos + 1;
+
+        // 无长度的标记
+        if marker == 0x01 || (0xD0..=0xD8).contains(&marker) {
+            output.extend_from_slice(&data[pos..segment_start]);
+            pos = segment_start;
+            continue;
+        }
+        // 图像结束
+        if marker == 0xD9 {
+            output.extend_from_slice(&data[pos..]);
+            break;
+        }
+
+        let len = data
+            .get(segment_start..segment_start + 2)
+            .map(|v| u16::from_be_bytes([v[0], v[1]]) as usize)
+            .ok_or(MalformedImage)?;
+        if len < 2 {
+            return Err(MalformedImage);
+        }
+        let segment_end = segment_start + len;
+        let payload = data
+            .get(segment_start + 2..segment_end)
+            .ok_or(MalformedImage)?;
+
+        // 扫描数据开始, 之后为压缩数据
+        if marker == 0xDA {
+            output.extend_from_slice(&data[pos..]);
+            break;
+        }
+
+        let is_metadata = marker == 0xE1
+            && (payload.starts_with(b"Exif\0") || payload.starts_with(b"http://ns.adobe.com/xap/"));
+        if !is_metadata {
+            output.extend_from_slice(&data[pos..segment_end]);
+        }
+        pos = segment_end;
+    }
+    Ok(output)
+}
+
+/// PNG: 移除 eXIf、文本及时间块
+fn strip_png(data: &[u8]) -> Result<Vec<u8>, MalformedImage> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    const METADATA_CHUNKS: [&[u8]; 5] = [b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+    if !data.starts_with(SIGNATURE) {
+        return Err(MalformedImage);
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(SIGNATURE);
+
+    let mut pos = SIGNATURE.len();
+    while pos < data.len() {
+        let header = data.get(pos..pos + 8).ok_or(MalformedImage)?;
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        // 长度 + 类型 + 数据 + CRC
+        let chunk_end = pos + 12 + len;
+        if chunk_end > data.len() {
+            return Err(MalformedImage);
+        }
+        if !METADATA_CHUNKS.contains(&kind) {
+            output.extend_from_slice(&data[pos..chunk_end]);
+        }
+        pos = chunk_end;
+        if kind == b"IEND" {
+            break;
+        }
+    }
+    Ok(output)
+}
+
+/// WebP: 移除 EXIF 及 XMP 块, 并清除 VP8X 中对应的标志位
+fn strip_webp(data: &[u8]) -> Result<Vec<u8>, MalformedImage> {
+    const EXIF_FLAG: u8 = 0x08;
+    const XMP_FLAG: u8 = 0x04;
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return Err(MalformedImage);
+    }
+    let mut output = Vec::with_capacity(data.len());
+    output.extend_from_slice(&data[..12]);
+
+    let mut pos = 12;
+    while pos < data.len() {
+        let header = data.get(pos..pos + 8).ok_or(MalformedImage)?;
+        let kind = &header[..4];
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        // 块数据按偶数字节对齐
+        let chunk_end = (pos + 8 + len + (len & 1)).min(data.len());
+        if pos + 8 + len > data.len() {
+            return Err(MalformedImage);
+        }
+
+        match kind {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let start = output.len();
+                output.extend_from_slice(&data[pos..chunk_end]);
+                if let Some(flags) = output.get_mut(start + 8) {
+                    *flags &= !(EXIF_FLAG | XMP_FLAG);
+                }
+            }
+            _ => output.extend_from_slice(&data[pos..chunk_end]),
+        }
+        pos = chunk_end;
+    }
+
+    let riff_size = u32::try_from(output.len() - 8).map_err(|_| MalformedImage)?;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jpeg() {
+        let mut data = vec![0xFF, 0xD8];
+        // APP0
+        data.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x07, b'J', b'F', b'I', b'F', 0x00]);
+        // APP1 EXIF
+        data.extend_from_slice(&[0xFF, 0xE1, 0x00, 0x0A, b'E', b'x', b'i', b'f', 0, 0, 1, 2]);
+        // SOS 及压缩数据
+        let scan = [0xFF, 0xDA, 0x00, 0x04, 0x01, 0x02, 0x11, 0x22, 0xFF, 0xD9];
+        data.extend_from_slice(&scan);
+
+        let output = strip("image/jpeg", &data).unwrap();
+        let mut expected = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x07];
+        expected.extend_from_slice(b"JFIF\0");
+        expected.extend_from_slice(&scan);
+        assert_eq!(output, expected);
+
+        assert_eq!(
+            strip("image/jpeg", b"\xFF\xD8\xFF\xE1\x00"),
+            Err(MalformedImage)
+        );
+    }
+
+    fn png_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        chunk
+    }
+
+    #[test]
+    fn test_strip_png() {
+        let ihdr = png_chunk(b"IHDR", &[0; 13]);
+        let idat = png_chunk(b"IDAT", &[1, 2, 3]);
+        let iend = png_chunk(b"IEND", &[]);
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        data.extend_from_slice(&ihdr);
+        data.extend_from_slice(&png_chunk(b"eXIf", &[9; 6]));
+        data.extend_from_slice(&png_chunk(b"tEXt", b"Author\0me"));
+        data.extend_from_slice(&idat);
+        data.extend_from_slice(&iend);
+
+        let output = strip("image/png", &data).unwrap();
+        let mut expected = b"\x89PNG\r\n\x1a\n".to_vec();
+        expected.extend_from_slice(&ihdr);
+        expected.extend_from_slice(&idat);
+        expected.extend_from_slice(&iend);
+        assert_eq!(output, expected);
+    }
+
+    fn webp_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = kind.to_vec();
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            chunk.push(0);
+        }
+        chunk
+    }
+
+    fn webp(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body = chunks.concat();
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&((body.len() + 4) as u32).to_le_bytes());
+        data.extend_from_slice(b"WEBP");
+        data.extend_from_slice(&body);
+        data
+    }
+
+    #[test]
+    fn test_strip_webp() {
+        let mut vp8x = [0u8; 10];
+        vp8x[0] = 0x08 | 0x04 | 0x10;
+        let image = webp_chunk(b"VP8L", &[1, 2, 3]);
+        let data = webp(&[
+            webp_chunk(b"VP8X", &vp8x),
+            image.clone(),
+            webp_chunk(b"EXIF", &[7; 5]),
+            webp_chunk(b"XMP ", b"<x/>"),
+        ]);
+
+        let output = strip("image/webp", &data).unwrap();
+        vp8x[0] = 0x10;
+        assert_eq!(output, webp(&[webp_chunk(b"VP8X", &vp8x), image]));
+    }
+
+    #[test]
+    fn test_strip_other() {
+        assert_eq!(strip("application/pdf", b"%PDF").unwrap(), b"%PDF");
+    }
+}
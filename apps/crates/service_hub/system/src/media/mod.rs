@@ -0,0 +1,6 @@
+//! 图片处理
+//!
+//! 上传时移除图片元数据、校验 SVG, 下载时按需生成缩略图.
+pub mod exif;
+pub mod svg;
+pub mod thumbnail;
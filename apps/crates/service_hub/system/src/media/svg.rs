@@ -0,0 +1,112 @@
+//! SVG 安全校验
+//!
+//! SVG 可包含脚本, 直接访问时会在当前域名下执行, 因此拒绝包含脚本的文件.
+
+use quick_xml::{Reader, events::Event};
+
+/// 禁止使用的元素
+const FORBIDDEN_ELEMENTS: [&str; 6] = [
+    "script",
+    "foreignobject",
+    "iframe",
+    "embed",
+    "object",
+    "handler",
+];
+
+/// 校验 SVG 是否安全, 返回不安全的原因
+pub fn check(data: &[u8]) -> Result<(), String> {
+    let text = std::str::from_utf8(data).map_err(|_| "SVG 编码错误".to_string())?;
+    let mut reader = Reader::from_str(text);
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|err| format!("SVG 格式错误, {err}"))?;
+        match event {
+            Event::Start(element) | Event::Empty(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).to_lowercase();
+                if FORBIDDEN_ELEMENTS.contains(&name.as_str()) {
+                    return Err(format!("SVG 不允许包含 {name} 元素"));
+                }
+
+                for attr in element.attributes() {
+                    let attr = attr.map_err(|err| format!("SVG 格式错误, {err}"))?;
+                    let key =
+                        String::from_utf8_lossy(attr.key.local_name().as_ref()).to_lowercase();
+                    if key.starts_with("on") {
+                        return Err(format!("SVG 不允许包含 {key} 事件属性"));
+                    }
+                    let value = attr
+                        .unescape_value()
+                        .map_err(|err| format!("SVG 格式错误, {err}"))?;
+                    if is_unsafe_url(&value) {
+                        return Err(format!("SVG 属性 {key} 包含不安全的链接"));
+                    }
+                }
+            }
+            // 禁止 DTD, 避免实体扩展
+            Event::DocType(_) => return Err("SVG 不允许包含 DOCTYPE".to_string()),
+            Event::PI(_) => return Err("SVG 不允许包含处理指令".to_string()),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// 是否为可执行脚本的链接
+fn is_unsafe_url(value: &str) -> bool {
+    let value = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    value.starts_with("javascript:")
+        || value.starts_with("vbscript:")
+        || (value.starts_with("data:") && !is_safe_data_url(&value))
+        || value.contains("url(javascript:")
+}
+
+/// 仅允许内嵌位图
+fn is_safe_data_url(value: &str) -> bool {
+    [
+        "data:image/png",
+        "data:image/jpeg",
+        "data:image/gif",
+        "data:image/webp",
+    ]
+    .iter()
+    .any(|prefix| value.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let safe = br##"<?xml version="1.0"?>
+            <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+                <style>.a { fill: red; }</style>
+                <rect class="a" width="10" height="10"/>
+                <image xlink:href="data:image/png;base64,AAAA"/>
+                <use href="#a"/>
+            </svg>"##;
+        assert_eq!(check(safe), Ok(()));
+
+        let unsafe_svgs: [&[u8]; 7] = [
+            br#"<svg><script>alert(1)</script></svg>"#,
+            br#"<svg><svg:script xmlns:svg="http://www.w3.org/2000/svg"/></svg>"#,
+            br#"<svg onload="alert(1)"></svg>"#,
+            br#"<svg><a href="&#106;avascript:alert(1)"><rect/></a></svg>"#,
+            br#"<svg><a xlink:href=" java script:alert(1)"/></svg>"#,
+            br#"<svg><foreignObject><div/></foreignObject></svg>"#,
+            br#"<!DOCTYPE svg [<!ENTITY a "b">]><svg/>"#,
+        ];
+        for svg in unsafe_svgs {
+            assert!(check(svg).is_err(), "{}", String::from_utf8_lossy(svg));
+        }
+        assert!(check(br#"<svg><image href="data:image/svg+xml;base64,AAAA"/></svg>"#).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! 缩略图
+//!
+//! 按比例缩放图片, WebP 缩略图输出为 PNG.
+use std::io::Cursor;
+
+use image::{
+    ImageError, ImageOutputFormat,
+    io::{Limits, Reader},
+};
+
+/// 原图最大边长, 避免解码过大的图片
+const SOURCE_MAX_SIZE: u32 = 16384;
+/// JPEG 缩略图质量
+const JPEG_QUALITY: u8 = 85;
+
+/// 是否支持生成缩略图
+pub fn is_supported(mime_type: &str) -> bool {
+    matches!(mime_type, "image/png" | "image/jpeg" | "image/webp")
+}
+
+/// 缩略图的内容类型及扩展名
+pub fn output_type(mime_type: &str) -> (&'static str, &'static str) {
+    match mime_type {
+        "image/jpeg" => ("image/jpeg", "jpg"),
+        _ => ("image/png", "png"),
+    }
+}
+
+/// 生成缩略图, 缩放至不超过指定宽高, 宽或高为 0 时不限制, 不放大图片
+pub fn render(
+    data: &[u8],
+    mime_type: &str,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, ImageError> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(SOURCE_MAX_SIZE);
+    limits.max_image_height = Some(SOURCE_MAX_SIZE);
+
+    let mut reader = Reader::new(Cursor::new(data)).with_guessed_format()?;
+    reader.limits(limits);
+    let image = reader.decode()?;
+
+    let bound = |size: u32, limit: u32| if limit == 0 { size } else { size.min(limit) };
+    let image = image.thumbnail(bound(image.width(), width), bound(image.height(), height));
+
+    let format = match output_type(mime_type) {
+        ("image/jpeg", _) => ImageOutputFormat::Jpeg(JPEG_QUALITY),
+        _ => ImageOutputFormat::Png,
+    };
+    let mut output = Cursor::new(Vec::new());
+    image.write_to(&mut output, format)?;
+    Ok(output.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, RgbImage};
+
+    use super::*;
+
+    fn encode(format: ImageOutputFormat) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(100, 50));
+        let mut output = Cursor::new(Vec::new());
+        image.write_to(&mut output, format).unwrap();
+        output.into_inner()
+    }
+
+    fn dimensions(data: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory(data).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn test_render() {
+        let png = encode(ImageOutputFormat::Png);
+        let output = render(&png, "image/png", 20, 0).unwrap();
+        assert!(infer::is(&output, "png"));
+        assert_eq!(dimensions(&output), (20, 10));
+
+        let output = render(&png, "image/png", 40, 10).unwrap();
+        assert_eq!(dimensions(&output), (20, 10));
+
+        // 不放大图片
+        let output = render(&png, "image/png", 400, 400).unwrap();
+        assert_eq!(dimensions(&output), (100, 50));
+
+        let jpeg = encode(ImageOutputFormat::Jpeg(90));
+        let output = render(&jpeg, "image/jpeg", 0, 25).unwrap();
+        assert!(infer::is(&output, "jpg"));
+        assert_eq!(dimensions(&output), (50, 25));
+
+        assert!(render(b"not an image", "image/png", 10, 10).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! HTTP Range 请求
+//!
+//! 仅支持单个字节区间, 多个区间时返回完整内容.
+
+/// 字节区间, 包含首尾
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Range 请求解析结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// 返回完整内容
+    Full,
+    /// 返回部分内容
+    Partial(ByteRange),
+    /// 区间无法满足
+    NotSatisfiable,
+}
+
+impl ByteRange {
+    /// 区间长度
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// 区间是否为空
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Content-Range 响应头
+    pub fn content_range(&self, size: u64) -> String {
+        format!("bytes {}-{}/{size}", self.start, self.end)
+    }
+}
+
+impl RangeRequest {
+    /// 解析 Range 请求头, 如 `bytes=0-499`, `bytes=500-`, `bytes=-500`
+    pub fn parse(header: &str, size: u64) -> Self {
+        let Some(spec) = header.trim().strip_prefix("bytes=") else {
+            return RangeRequest::Full;
+        };
+        if spec.contains(',') {
+            return RangeRequest::Full;
+        }
+        let Some((start, end)) = spec.trim().split_once('-') else {
+            return RangeRequest::Full;
+        };
+
+        let range = match (start.trim(), end.trim()) {
+            ("", "") => return RangeRequest::Full,
+            // 最后 N 个字节
+            ("", suffix) => {
+                let Ok(suffix) = suffix.parse::<u64>() else {
+                    return RangeRequest::Full;
+                };
+                if suffix == 0 || size == 0 {
+                    return RangeRequest::NotSatisfiable;
+                }
+                ByteRange {
+                    start: size.saturating_sub(suffix),
+                    end: size - 1,
+                }
+            }
+            (start, end) => {
+                let Ok(start) = start.parse::<u64>() else {
+                    return RangeRequest::Full;
+                };
+                let end = match end {
+                    "" => u64::MAX,
+                    end => match end.parse::<u64>() {
+                        Ok(v) => v,
+                        Err(_) => return RangeRequest::Full,
+                    },
+                };
+                if end < start {
+                    return RangeRequest::Full;
+                }
+                if start >= size {
+                    return RangeRequest::NotSatisfiable;
+                }
+                ByteRange {
+                    start,
+                    end: end.min(size - 1),
+                }
+            }
+        };
+        RangeRequest::Partial(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let partial = |start, end| RangeRequest::Partial(ByteRange { start, end });
+
+        assert_eq!(RangeRequest::parse("bytes=0-499", 1000), partial(0, 499));
+        assert_eq!(RangeRequest::parse("bytes=500-", 1000), partial(500, 999));
+        assert_eq!(RangeRequest::parse("bytes=-200", 1000), partial(800, 999));
+        assert_eq!(RangeRequest::parse("bytes=-2000", 1000), partial(0, 999));
+        assert_eq!(
+            RangeRequest::parse("bytes=900-2000", 1000),
+            partial(900, 999)
+        );
+
+        assert_eq!(
+            RangeRequest::parse("bytes=1000-", 1000),
+            RangeRequest::NotSatisfiable
+        );
+        assert_eq!(
+            RangeRequest::parse("bytes=-0", 1000),
+            RangeRequest::NotSatisfiable
+        );
+
+        // 不支持的格式返回完整内容
+        assert_eq!(RangeRequest::parse("items=0-1", 1000), RangeRequest::Full);
+        assert_eq!(
+            RangeRequest::parse("bytes=0-1,5-6", 1000),
+            RangeRequest::Full
+        );
+        assert_eq!(RangeRequest::parse("bytes=5-1", 1000), RangeRequest::Full);
+        assert_eq!(RangeRequest::parse("bytes=a-1", 1000), RangeRequest::Full);
+
+        assert_eq!(ByteRange { start: 0, end: 499 }.len(), 500);
+        assert_eq!(
+            ByteRange { start: 0, end: 499 }.content_range(1000),
+            "bytes 0-499/1000"
+        );
+    }
+}
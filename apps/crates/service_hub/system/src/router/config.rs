@@ -2,7 +2,7 @@
 
 use axum::{
     Router,
-    routing::{get, put},
+    routing::{get, post, put},
 };
 
 use crate::controller::config::ConfigController;
@@ -21,6 +21,10 @@ impl ConfigRouter {
                     get(ConfigController::list).post(ConfigController::create),
                 )
                 .route("/tree", get(ConfigController::tree))
+                .route("/by-code/{code}", get(ConfigController::info_by_code))
+                .route("/export", get(ConfigController::export))
+                .route("/import", post(ConfigController::import))
+                .route("/import/preview", post(ConfigController::preview_import))
                 .route(
                     "/{id}",
                     get(ConfigController::info)
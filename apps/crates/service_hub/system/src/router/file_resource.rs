@@ -0,0 +1,27 @@
+//! 文件资源管理
+
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::controller::file_resource::{FileResourceController, upload_body_limit};
+
+/// 路由器
+pub struct FileResourceRouter;
+
+impl FileResourceRouter {
+    /// 注册`文件资源管理`路由
+    pub fn register() -> Router {
+        Router::new().nest(
+            "/file-resources",
+            Router::new()
+                .route(
+                    "/",
+                    post(FileResourceController::upload).layer(upload_body_limit()),
+                )
+                .route("/{hash}", get(FileResourceController::download))
+                .route("/{hash}/thumbnail", get(FileResourceController::thumbnail)),
+        )
+    }
+}
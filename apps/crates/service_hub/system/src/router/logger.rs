@@ -0,0 +1,28 @@
+//! 日志级别管理
+
+use axum::{
+    Router,
+    middleware::from_fn,
+    routing::{get, put},
+};
+
+use user::guard::admin_guard_layer;
+
+use crate::controller::logger::LoggerController;
+
+/// 路由器
+pub struct LoggerRouter;
+
+impl LoggerRouter {
+    /// 注册`日志级别管理`路由
+    pub fn register() -> Router {
+        // 仅管理员可访问
+        let admin_router = Router::new()
+            .route("/logger", put(LoggerController::update))
+            .route_layer(from_fn(admin_guard_layer));
+
+        Router::new()
+            .route("/logger", get(LoggerController::list))
+            .merge(admin_router)
+    }
+}
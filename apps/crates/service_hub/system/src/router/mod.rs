@@ -1,5 +1,9 @@
 //! 路由层
 pub mod config;
+pub mod dict_data;
+pub mod dict_dimension;
+pub mod file_resource;
+pub mod logger;
 
 use axum::Router;
 
@@ -11,7 +15,12 @@ impl SystemRouter {
     pub fn register() -> Router {
         Router::new().nest(
             "/system",
-            Router::new().merge(config::ConfigRouter::register()), // 配置管理
+            Router::new()
+                .merge(config::ConfigRouter::register()) // 配置管理
+                .merge(dict_dimension::DictDimensionRouter::register()) // 字典维度管理
+                .merge(dict_data::DictDataRouter::register()) // 字典数据管理
+                .merge(file_resource::FileResourceRouter::register()) // 文件资源管理
+                .merge(logger::LoggerRouter::register()), // 日志级别管理
         )
     }
 }
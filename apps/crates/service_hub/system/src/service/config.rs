@@ -1,4 +1,5 @@
 //! 配置管理
+use std::collections::{HashMap, HashSet};
 
 use log::error;
 use nject::injectable;
@@ -9,11 +10,15 @@ use entity::system::config;
 use err_code::{Error, ErrorMsg};
 
 use crate::{
+    cache::ConfigCache,
+    constant::ConfigDefinition,
     dao::config::ConfigDao,
     dto::config::{
-        CreateConfigReq, DeleteConfigReq, GetConfigReq, GetConfigsReq, UpdateConfigReq,
-        UpdateConfigStatusReq,
+        ConfigDiff, ConfigDiffAction, ConfigFieldChange, ConfigImportPlan, ConfigNode,
+        CreateConfigReq, DeleteConfigReq, ExportConfigsReq, GetConfigReq, GetConfigsReq,
+        ImportConfigItem, ImportConfigsReq, UpdateConfigReq, UpdateConfigStatusReq,
     },
+    enums::config::ConfigFormat,
 };
 
 /// 服务层
@@ -76,6 +81,8 @@ impl ConfigService {
     pub async fn create(&self, req: CreateConfigReq) -> Result<config::Model, ErrorMsg> {
         // 查询配置编码是否存在
         self.check_code_exist(req.code.clone(), None).await?;
+        // 校验配置值类型
+        Self::check_value(&req.code, req.value.as_deref())?;
 
         let model = config::ActiveModel {
             pid: Set(req.pid),
@@ -91,6 +98,7 @@ impl ConfigService {
             error!("添加配置信息失败, err: {:#?}", err);
             Error::DbAddError.into_err_with_msg("添加配置信息失败")
         })?;
+        ConfigCache::clear();
 
         Ok(result)
     }
@@ -100,6 +108,8 @@ impl ConfigService {
         // 查询配置编码是否存在且不属于当前ID
         self.check_code_exist(req.code.clone(), Some(req.id))
             .await?;
+        // 校验配置值类型
+        Self::check_value(&req.code, req.value.as_deref())?;
 
         let model = config::ActiveModel {
             id: Set(req.id),
@@ -117,6 +127,7 @@ impl ConfigService {
             error!("更新配置失败, err: {:#?}", err);
             Error::DbUpdateError.into_err_with_msg("更新配置失败")
         })?;
+        ConfigCache::clear();
 
         Ok(result)
     }
@@ -144,6 +155,17 @@ impl ConfigService {
         Ok(())
     }
 
+    /// 校验已声明配置的配置值类型
+    fn check_value(code: &str, value: Option<&str>) -> Result<(), ErrorMsg> {
+        let (Some(definition), Some(value)) = (ConfigDefinition::find(code), value) else {
+            return Ok(());
+        };
+        definition.value_type.check(value).map_err(|err| {
+            error!("配置值类型错误, code: {code}, err: {err}");
+            Error::InvalidParameter(err).into_err()
+        })
+    }
+
     /// 更新数据状态
     pub async fn update_status(&self, req: UpdateConfigStatusReq) -> Result<(), ErrorMsg> {
         self.config_dao
@@ -158,6 +180,7 @@ impl ConfigService {
                 error!("更新配置状态失败, err: {:#?}", err);
                 Error::DbUpdateError.into_err_with_msg("更新配置状态失败")
             })?;
+        ConfigCache::clear();
 
         Ok(())
     }
@@ -180,7 +203,390 @@ impl ConfigService {
             error!("删除配置信息失败, err: {:#?}", err);
             Error::DbDeleteError.into_err_with_msg("删除配置信息失败")
         })?;
+        ConfigCache::clear();
 
         Ok(result)
     }
+
+    /// 导出配置树, 指定配置编码时导出该配置的子树
+    pub async fn export(&self, req: ExportConfigsReq) -> Result<String, ErrorMsg> {
+        let (results, _total) = self.config_dao.all().await.map_err(|err| {
+            error!("查询配置列表失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询配置列表失败")
+        })?;
+
+        let roots: Vec<&config::Model> = match &req.code {
+            Some(code) => {
+                let root = results.iter().find(|v| &v.code == code).ok_or_else(|| {
+                    error!("配置不存在, code: {code}");
+                    Error::DbQueryEmptyError.into_err_with_msg("配置不存在")
+                })?;
+                vec![root]
+            }
+            // 父节点不存在的配置同样作为根节点导出
+            None => {
+                let ids: HashSet<i32> = results.iter().map(|v| v.id).collect();
+                results
+                    .iter()
+                    .filter(|v| v.pid.is_none_or(|pid| !ids.contains(&pid)))
+                    .collect()
+            }
+        };
+        let nodes: Vec<ConfigNode> = roots
+            .into_iter()
+            .map(|root| {
+                let mut tree = GenericTree::new(root);
+                tree.children = GenericTree::to_tree(&results, Some(root.id));
+                ConfigNode::from(tree)
+            })
+            .collect();
+
+        let content = match req.format {
+            ConfigFormat::Yaml => serde_yaml::to_string(&nodes).map_err(|err| err.to_string()),
+            ConfigFormat::Json => {
+                serde_json::to_string_pretty(&nodes).map_err(|err| err.to_string())
+            }
+        }
+        .map_err(|err| {
+            error!("导出配置失败, err: {err}");
+            Error::JsonSerialization(err).into_err_with_msg("导出配置失败")
+        })?;
+
+        Ok(content)
+    }
+
+    /// 预览导入配置的变更
+    pub async fn preview_import(
+        &self,
+        req: ImportConfigsReq,
+    ) -> Result<ConfigImportPlan, ErrorMsg> {
+        let (plan, _items) = self.import_plan(req).await?;
+        Ok(plan)
+    }
+
+    /// 导入配置
+    ///
+    /// 按配置编码新增或更新配置, 不删除导入内容之外的配置.
+    pub async fn import(&self, req: ImportConfigsReq) -> Result<ConfigImportPlan, ErrorMsg> {
+        let (plan, items) = self.import_plan(req).await?;
+        if items.is_empty() {
+            return Ok(plan);
+        }
+
+        self.config_dao.import(items).await.map_err(|err| {
+            error!("导入配置失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("导入配置失败")
+        })?;
+        ConfigCache::clear();
+
+        Ok(plan)
+    }
+
+    /// 解析导入内容并与现有配置对比, 返回变更及待写入的配置
+    async fn import_plan(
+        &self,
+        req: ImportConfigsReq,
+    ) -> Result<(ConfigImportPlan, Vec<ImportConfigItem>), ErrorMsg> {
+        let nodes =
+            match req.format {
+                ConfigFormat::Yaml => serde_yaml::from_str::<Vec<ConfigNode>>(&req.content)
+                    .map_err(|err| err.to_string()),
+                ConfigFormat::Json => serde_json::from_str::<Vec<ConfigNode>>(&req.content)
+                    .map_err(|err| err.to_string()),
+            }
+            .map_err(|err| {
+                error!("解析导入内容失败, err: {err}");
+                Error::InvalidParameter(err).into_err_with_msg("解析导入内容失败")
+            })?;
+
+        let (results, _total) = self.config_dao.all().await.map_err(|err| {
+            error!("查询配置列表失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询配置列表失败")
+        })?;
+        let by_code: HashMap<&str, &config::Model> =
+            results.iter().map(|v| (v.code.as_str(), v)).collect();
+        let codes: HashMap<i32, &str> = results.iter().map(|v| (v.id, v.code.as_str())).collect();
+        let parent_code = |model: &config::Model| {
+            model
+                .pid
+                .and_then(|pid| codes.get(&pid))
+                .map(|v| v.to_string())
+        };
+
+        let mut items = Vec::new();
+        for node in nodes {
+            // 根节点保留现有的父节点
+            let parent = by_code.get(node.code.as_str()).and_then(|v| parent_code(v));
+            Self::flatten(node, parent, &mut items);
+        }
+
+        let mut seen = HashSet::new();
+        for item in &items {
+            if item.code.trim().is_empty() || item.name.trim().is_empty() {
+                error!("配置名称或编码为空");
+                return Err(Error::InvalidParameter("配置名称或编码为空".to_string()).into_err());
+            }
+            if !seen.insert(item.code.as_str()) {
+                error!("配置编码重复, code: {}", item.code);
+                return Err(
+                    Error::InvalidParameter(item.code.clone()).into_err_with_msg("配置编码重复")
+                );
+            }
+            Self::check_value(&item.code, item.value.as_deref())?;
+        }
+
+        // 检查导入后的层级是否存在循环
+        let mut parents: HashMap<&str, Option<String>> = results
+            .iter()
+            .map(|v| (v.code.as_str(), parent_code(v)))
+            .collect();
+        for item in &items {
+            parents.insert(item.code.as_str(), item.parent_code.clone());
+        }
+        for item in &items {
+            let mut current = item.parent_code.as_deref();
+            let mut depth = 0;
+            while let Some(code) = current {
+                depth += 1;
+                if code == item.code || depth > parents.len() {
+                    error!("配置层级存在循环, code: {}", item.code);
+                    return Err(Error::InvalidParameter(item.code.clone())
+                        .into_err_with_msg("配置层级存在循环"));
+                }
+                current = parents.get(code).and_then(|v| v.as_deref());
+            }
+        }
+
+        let mut plan = ConfigImportPlan::default();
+        let mut changed = Vec::new();
+        for item in items {
+            let (action, changes) = match by_code.get(item.code.as_str()) {
+                Some(model) => {
+                    let changes = Self::diff(&[
+                        ("parent_code", parent_code(model), item.parent_code.clone()),
+                        ("name", Some(model.name.clone()), Some(item.name.clone())),
+                        ("value", model.value.clone(), item.value.clone()),
+                        (
+                            "sort",
+                            model.sort.map(|v| v.to_string()),
+                            item.sort.map(|v| v.to_string()),
+                        ),
+                        ("desc", model.desc.clone(), item.desc.clone()),
+                        (
+                            "status",
+                            Some(model.status.to_string()),
+                            Some(item.status.to_string()),
+                        ),
+                    ]);
+                    if changes.is_empty() {
+                        plan.unchanged += 1;
+                        continue;
+                    }
+                    plan.updated += 1;
+                    (ConfigDiffAction::Update, changes)
+                }
+                None => {
+                    plan.created += 1;
+                    let changes = Self::diff(&[
+                        ("parent_code", None, item.parent_code.clone()),
+                        ("name", None, Some(item.name.clone())),
+                        ("value", None, item.value.clone()),
+                        ("sort", None, item.sort.map(|v| v.to_string())),
+                        ("desc", None, item.desc.clone()),
+                        ("status", None, Some(item.status.to_string())),
+                    ]);
+                    (ConfigDiffAction::Create, changes)
+                }
+            };
+            plan.items.push(ConfigDiff {
+                code: item.code.clone(),
+                action,
+                changes,
+            });
+            changed.push(item);
+        }
+
+        Ok((plan, changed))
+    }
+
+    /// 按先父后子的顺序展开配置节点
+    fn flatten(node: ConfigNode, parent_code: Option<String>, items: &mut Vec<ImportConfigItem>) {
+        let code = node.code.clone();
+        items.push(ImportConfigItem {
+            parent_code,
+            name: node.name,
+            code: node.code,
+            value: node.value,
+            sort: node.sort,
+            desc: node.desc,
+            status: node.status,
+        });
+        for child in node.children {
+            Self::flatten(child, Some(code.clone()), items);
+        }
+    }
+
+    /// 对比字段变更
+    fn diff(fields: &[(&str, Option<String>, Option<String>)]) -> Vec<ConfigFieldChange> {
+        fields
+            .iter()
+            .filter(|(_, before, after)| before != after)
+            .map(|(field, before, after)| ConfigFieldChange {
+                field: field.to_string(),
+                before: before.clone(),
+                after: after.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use inject::InjectProvider;
+
+    use super::*;
+
+    async fn provider() -> InjectProvider {
+        InjectProvider::mock().await
+    }
+
+    async fn create(service: &ConfigService, pid: Option<i32>, code: &str, value: &str) -> i32 {
+        service
+            .create(CreateConfigReq {
+                pid,
+                name: code.to_string(),
+                code: code.to_string(),
+                value: Some(value.to_string()),
+                sort: None,
+                desc: None,
+                status: true,
+            })
+            .await
+            .expect("create config")
+            .id
+    }
+
+    fn import_req(format: ConfigFormat, content: &str) -> ImportConfigsReq {
+        ImportConfigsReq {
+            format,
+            content: content.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_import() {
+        let provider = provider().await;
+        let service: ConfigService = provider.provide();
+        let mail_id = create(&service, None, "mail", "").await;
+        create(&service, Some(mail_id), "mail_host", "smtp.local").await;
+        create(&service, None, "other", "1").await;
+
+        // 导出子树
+        let content = service
+            .export(ExportConfigsReq {
+                code: Some("mail".to_string()),
+                format: ConfigFormat::Json,
+            })
+            .await
+            .expect("export configs");
+        let nodes: Vec<ConfigNode> = serde_json::from_str(&content).expect("parse export");
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].children[0].code, "mail_host");
+
+        // 导出全部
+        let content = service
+            .export(ExportConfigsReq::default())
+            .await
+            .expect("export configs");
+        let nodes: Vec<ConfigNode> = serde_yaml::from_str(&content).expect("parse export");
+        assert_eq!(nodes.len(), 2);
+
+        let content = r#"
+- name: mail
+  code: mail
+  value: ""
+  children:
+    - name: mail_host
+      code: mail_host
+      value: smtp.example.com
+    - name: mail_port
+      code: mail_port
+      value: "465"
+"#;
+        // 预览不写入数据
+        let plan = service
+            .preview_import(import_req(ConfigFormat::Yaml, content))
+            .await
+            .expect("preview import");
+        assert_eq!((plan.created, plan.updated, plan.unchanged), (1, 1, 1));
+        let update = plan
+            .items
+            .iter()
+            .find(|v| v.action == ConfigDiffAction::Update)
+            .expect("update item");
+        assert_eq!(update.code, "mail_host");
+        assert_eq!(update.changes[0].field, "value");
+        assert_eq!(
+            update.changes[0].after,
+            Some("smtp.example.com".to_string())
+        );
+        let (_, total) = service.config_dao.all().await.expect("all configs");
+        assert_eq!(total, 3);
+
+        let result = service
+            .import(import_req(ConfigFormat::Yaml, content))
+            .await
+            .expect("import configs");
+        assert_eq!(result, plan);
+        let port = service
+            .config_dao
+            .info_by_code("mail_port".to_string())
+            .await
+            .expect("query config")
+            .expect("mail_port exists");
+        assert_eq!(port.pid, Some(mail_id));
+        let host = service
+            .config_dao
+            .info_by_code("mail_host".to_string())
+            .await
+            .expect("query config")
+            .expect("mail_host exists");
+        assert_eq!(host.value.as_deref(), Some("smtp.example.com"));
+
+        // 再次导入无变更
+        let plan = service
+            .import(import_req(ConfigFormat::Yaml, content))
+            .await
+            .expect("import configs");
+        assert_eq!((plan.created, plan.updated, plan.unchanged), (0, 0, 3));
+    }
+
+    #[tokio::test]
+    async fn test_import_invalid() {
+        let provider = provider().await;
+        let service: ConfigService = provider.provide();
+        let parent_id = create(&service, None, "parent", "").await;
+        create(&service, Some(parent_id), "child", "").await;
+
+        let invalid = [
+            // 编码重复
+            r#"[{"name": "a", "code": "a"}, {"name": "a", "code": "a"}]"#,
+            // 已声明配置的值类型错误
+            r#"[{"name": "a", "code": "login_max_failures", "value": "many"}]"#,
+            // 层级循环
+            r#"[{"name": "child", "code": "child", "children": [{"name": "parent", "code": "parent"}]}]"#,
+            // 格式错误
+            r#"{"name": "a"}"#,
+        ];
+        for content in invalid {
+            let err = service
+                .import(import_req(ConfigFormat::Json, content))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+        }
+
+        let (_, total) = service.config_dao.all().await.expect("all configs");
+        assert_eq!(total, 2);
+    }
 }
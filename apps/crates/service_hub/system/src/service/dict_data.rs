@@ -0,0 +1,335 @@
+//! 字典数据管理
+
+use log::error;
+use nject::injectable;
+use sea_orm::{DbErr::RecordNotUpdated, Set};
+
+use entity::system::dict_data;
+use err_code::{Error, ErrorMsg};
+
+use crate::{
+    cache::DictOptionsCache,
+    dao::{dict_data::DictDataDao, dict_dimension::DictDimensionDao},
+    dto::dict_data::{
+        CreateDictDataReq, DeleteDictDataReq, DictOption, GetDictDataReq, GetDictDatasReq,
+        GetDictOptionsReq, UpdateDictDataReq, UpdateDictDataStatusReq,
+    },
+};
+
+/// 服务层
+#[injectable]
+pub struct DictDataService {
+    dict_data_dao: DictDataDao,
+    dict_dimension_dao: DictDimensionDao,
+}
+
+impl DictDataService {
+    /// 获取列表数据
+    pub async fn list(
+        &self,
+        req: GetDictDatasReq,
+    ) -> Result<(Vec<dict_data::Model>, u64), ErrorMsg> {
+        // 获取所有数据
+        if let Some(true) = req.all {
+            return self.dict_data_dao.all(req.dim_id).await.map_err(|err| {
+                error!("查询字典数据列表失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询字典数据列表失败")
+            });
+        }
+
+        let (results, total) = self.dict_data_dao.list(req).await.map_err(|err| {
+            error!("查询字典数据列表失败, err: {:#?}", err);
+            Error::DbQueryError.into_err_with_msg("查询字典数据列表失败")
+        })?;
+
+        Ok((results, total))
+    }
+
+    /// 获取详情数据
+    pub async fn info(&self, req: GetDictDataReq) -> Result<dict_data::Model, ErrorMsg> {
+        let result = self
+            .dict_data_dao
+            .info(req.id)
+            .await
+            .map_err(|err| {
+                error!("查询字典数据信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询字典数据信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("字典数据不存在");
+                Error::DbQueryEmptyError.into_err_with_msg("字典数据不存在")
+            })?;
+
+        Ok(result)
+    }
+
+    /// 获取字典维度下已启用的字典选项
+    ///
+    /// 字典维度不存在时返回错误, 字典维度已停用时返回空列表.
+    pub async fn options(&self, req: GetDictOptionsReq) -> Result<Vec<DictOption>, ErrorMsg> {
+        let (cached, version) = DictOptionsCache::get(&req.code);
+        if let Some(options) = cached {
+            return Ok(options);
+        }
+
+        let dimension = self
+            .dict_dimension_dao
+            .info_by_code(req.code.clone())
+            .await
+            .map_err(|err| {
+                error!("查询字典维度信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询字典维度信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("字典维度不存在, code: {}", req.code);
+                Error::DbQueryEmptyError.into_err_with_msg("字典维度不存在")
+            })?;
+
+        let options = if dimension.status {
+            self.dict_data_dao
+                .enabled_by_dim_id(dimension.id)
+                .await
+                .map_err(|err| {
+                    error!("查询字典选项失败, err: {:#?}", err);
+                    Error::DbQueryError.into_err_with_msg("查询字典选项失败")
+                })?
+                .into_iter()
+                .map(DictOption::from)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        DictOptionsCache::set(req.code, options.clone(), version);
+        Ok(options)
+    }
+
+    /// 添加数据
+    pub async fn create(&self, req: CreateDictDataReq) -> Result<dict_data::Model, ErrorMsg> {
+        // 查询字典维度是否存在及字典项值是否重复
+        self.check_dimension_exist(req.dim_id).await?;
+        self.check_value_exist(req.dim_id, req.value.clone(), None)
+            .await?;
+
+        let model = dict_data::ActiveModel {
+            dim_id: Set(req.dim_id),
+            label: Set(req.label),
+            value: Set(req.value),
+            sort: Set(req.sort),
+            desc: Set(req.desc),
+            status: Set(true),
+            ..Default::default()
+        };
+        let result = self.dict_data_dao.create(model).await.map_err(|err| {
+            error!("添加字典数据信息失败, err: {:#?}", err);
+            Error::DbAddError.into_err_with_msg("添加字典数据信息失败")
+        })?;
+
+        DictOptionsCache::clear();
+        Ok(result)
+    }
+
+    /// 更新字典数据
+    pub async fn update(&self, req: UpdateDictDataReq) -> Result<u64, ErrorMsg> {
+        // 查询字典维度是否存在及字典项值是否重复
+        self.check_dimension_exist(req.dim_id).await?;
+        self.check_value_exist(req.dim_id, req.value.clone(), Some(req.id))
+            .await?;
+
+        let model = dict_data::ActiveModel {
+            id: Set(req.id),
+            dim_id: Set(req.dim_id),
+            label: Set(req.label),
+            value: Set(req.value),
+            sort: Set(req.sort),
+            desc: Set(req.desc),
+            status: Set(req.status),
+            ..Default::default()
+        };
+
+        let result = self.dict_data_dao.update(model).await.map_err(|err| {
+            error!("更新字典数据失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("更新字典数据失败")
+        })?;
+
+        DictOptionsCache::clear();
+        Ok(result)
+    }
+
+    /// 检查字典维度是否存在
+    async fn check_dimension_exist(&self, dim_id: i32) -> Result<(), ErrorMsg> {
+        self.dict_dimension_dao
+            .info(dim_id)
+            .await
+            .map_err(|err| {
+                error!("查询字典维度信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询字典维度信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("字典维度不存在");
+                Error::DbQueryEmptyError.into_err_with_msg("字典维度不存在")
+            })?;
+
+        Ok(())
+    }
+
+    /// 检查字典维度下的字典项值是否存在
+    async fn check_value_exist(
+        &self,
+        dim_id: i32,
+        value: String,
+        current_id: Option<i32>,
+    ) -> Result<(), ErrorMsg> {
+        let result = self
+            .dict_data_dao
+            .info_by_value(dim_id, value)
+            .await
+            .map_err(|err| {
+                error!("查询字典项值失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询字典项值失败")
+            })?;
+
+        // 存在
+        if let Some(model) = result
+            && (current_id.is_none() || Some(model.id) != current_id)
+        {
+            error!("字典项值已存在");
+            return Err(Error::DbDataExistError.into_err_with_msg("字典项值已存在"));
+        }
+
+        // 不存在
+        Ok(())
+    }
+
+    /// 更新数据状态
+    pub async fn update_status(&self, req: UpdateDictDataStatusReq) -> Result<(), ErrorMsg> {
+        self.dict_data_dao
+            .update_status(req.id, req.status)
+            .await
+            .map_err(|err| {
+                if err == RecordNotUpdated {
+                    error!("更新字典数据状态失败, 该字典数据不存在");
+                    return Error::DbUpdateError
+                        .into_err_with_msg("更新字典数据状态失败, 该字典数据不存在");
+                }
+                error!("更新字典数据状态失败, err: {:#?}", err);
+                Error::DbUpdateError.into_err_with_msg("更新字典数据状态失败")
+            })?;
+
+        DictOptionsCache::clear();
+        Ok(())
+    }
+
+    /// 删除数据
+    pub async fn delete(&self, req: DeleteDictDataReq) -> Result<u64, ErrorMsg> {
+        let result = self.dict_data_dao.delete(req.id).await.map_err(|err| {
+            error!("删除字典数据信息失败, err: {:#?}", err);
+            Error::DbDeleteError.into_err_with_msg("删除字典数据信息失败")
+        })?;
+
+        DictOptionsCache::clear();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use err_code::Error;
+    use inject::InjectProvider;
+
+    use super::*;
+    use crate::{
+        dto::dict_dimension::{CreateDictDimensionReq, DeleteDictDimensionReq},
+        service::dict_dimension::DictDimensionService,
+    };
+
+    async fn provider() -> InjectProvider {
+        InjectProvider::mock().await
+    }
+
+    async fn create_data(service: &DictDataService, dim_id: i32, label: &str, sort: i32) -> i32 {
+        service
+            .create(CreateDictDataReq {
+                dim_id,
+                label: label.to_string(),
+                value: label.to_lowercase(),
+                sort: Some(sort),
+                desc: None,
+            })
+            .await
+            .expect("create dict data")
+            .id
+    }
+
+    fn labels(options: &[DictOption]) -> Vec<&str> {
+        options.iter().map(|v| v.label.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_options() {
+        let provider = provider().await;
+        let dimension_service: DictDimensionService = provider.provide();
+        let data_service: DictDataService = provider.provide();
+
+        let dimension = dimension_service
+            .create(CreateDictDimensionReq {
+                name: "测试状态".to_string(),
+                code: "test_options_status".to_string(),
+                sort: None,
+                desc: None,
+            })
+            .await
+            .expect("create dict dimension");
+        create_data(&data_service, dimension.id, "Closed", 3).await;
+        let disabled_id = create_data(&data_service, dimension.id, "Pending", 2).await;
+        create_data(&data_service, dimension.id, "Open", 1).await;
+
+        // 字典项值在维度内唯一
+        let err = data_service
+            .create(CreateDictDataReq {
+                dim_id: dimension.id,
+                label: "Open2".to_string(),
+                value: "open".to_string(),
+                sort: None,
+                desc: None,
+            })
+            .await
+            .expect_err("duplicate value");
+        assert_eq!(err.code(), Error::DbDataExistError.code());
+
+        let req = || GetDictOptionsReq {
+            code: "test_options_status".to_string(),
+        };
+        let options = data_service.options(req()).await.expect("options");
+        assert_eq!(labels(&options), ["Open", "Pending", "Closed"]);
+        assert_eq!(options[0].value, "open");
+
+        // 停用的字典数据不返回, 写入后缓存失效
+        data_service
+            .update_status(UpdateDictDataStatusReq {
+                id: disabled_id,
+                status: false,
+            })
+            .await
+            .expect("update status");
+        let options = data_service.options(req()).await.expect("options");
+        assert_eq!(labels(&options), ["Open", "Closed"]);
+
+        // 删除字典维度时同时删除字典数据
+        dimension_service
+            .delete(DeleteDictDimensionReq { id: dimension.id })
+            .await
+            .expect("delete dict dimension");
+        let err = data_service.options(req()).await.expect_err("not found");
+        assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+        let (_, total) = data_service
+            .list(GetDictDatasReq {
+                dim_id: Some(dimension.id),
+                all: Some(true),
+                ..Default::default()
+            })
+            .await
+            .expect("list dict data");
+        assert_eq!(total, 0);
+    }
+}
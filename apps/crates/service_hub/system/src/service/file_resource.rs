@@ -0,0 +1,578 @@
+//! 文件资源管理
+
+use std::path::PathBuf;
+
+use log::error;
+use nject::injectable;
+use sea_orm::Set;
+use tokio::{fs::File, io::AsyncReadExt};
+
+use config::server::Upload;
+use entity::system::file_resource::{self, enums::StorageType};
+use err_code::{Error, ErrorMsg};
+use utils::crypto::{sha256_hex, sha256_reader};
+
+use crate::{
+    dao::file_resource::FileResourceDao,
+    dto::file_resource::{FileBody, GetThumbnailReq},
+    media::{exif, svg, thumbnail},
+    range::ByteRange,
+    storage::{LocalStorage, upload_config},
+};
+
+/// 文件名称最大长度
+const FILE_NAME_MAX_LEN: usize = 32;
+/// 识别文件类型时读取的文件头长度
+const FILE_HEAD_LEN: usize = 8192;
+
+/// 上传的文件内容
+pub enum UploadData {
+    /// 内存数据
+    Bytes(Vec<u8>),
+    /// 临时文件
+    File(PathBuf),
+}
+
+/// 服务层
+#[injectable]
+pub struct FileResourceService {
+    file_resource_dao: FileResourceDao,
+}
+
+impl FileResourceService {
+    /// 通过文件HASH值获取文件
+    pub async fn info_by_hash(&self, hash: String) -> Result<file_resource::Model, ErrorMsg> {
+        self.file_resource_dao
+            .info_by_hash(hash.clone())
+            .await
+            .map_err(|err| {
+                error!("查询文件资源失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询文件资源失败")
+            })?
+            .ok_or_else(|| {
+                error!("hash: {hash}, 文件不存在");
+                Error::DbQueryEmptyError.into_err_with_msg("文件不存在")
+            })
+    }
+
+    /// 读取文件内容, 未指定区间时读取完整内容
+    pub async fn read(
+        &self,
+        model: &file_resource::Model,
+        range: Option<ByteRange>,
+    ) -> Result<FileBody, ErrorMsg> {
+        let size = model.size as u64;
+        let range = range.unwrap_or(ByteRange {
+            start: 0,
+            end: size.saturating_sub(1),
+        });
+
+        match StorageType::from(model.storage.as_str()) {
+            StorageType::Db => {
+                let start = (range.start as usize).min(model.data.len());
+                let end = (range.end as usize + 1).min(model.data.len());
+                Ok(FileBody::Bytes(model.data[start..end].to_vec()))
+            }
+            StorageType::Local => {
+                let path = model.path.clone().unwrap_or_default();
+                let storage = LocalStorage::new(upload_config().filepath);
+                let file = storage
+                    .open(&path, range.start, range.len())
+                    .await
+                    .map_err(|err| {
+                        error!("读取文件失败, path: {path}, err: {:#?}", err);
+                        Error::FsReadFileError(path.clone()).into_err_with_msg("读取文件失败")
+                    })?;
+                Ok(FileBody::File(file))
+            }
+        }
+    }
+
+    /// 保存文件, 相同内容的文件仅保存一份
+    ///
+    /// 文件类型根据文件内容识别, 无法识别的文件将被拒绝
+    pub async fn upload(
+        &self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Result<file_resource::Model, ErrorMsg> {
+        self.save(&upload_config(), file_name, UploadData::Bytes(data), None)
+            .await
+    }
+
+    /// 保存上传的临时文件
+    pub async fn upload_file(
+        &self,
+        file_name: String,
+        path: PathBuf,
+        desc: Option<String>,
+    ) -> Result<file_resource::Model, ErrorMsg> {
+        self.save(&upload_config(), file_name, UploadData::File(path), desc)
+            .await
+    }
+
+    /// 保存文件
+    ///
+    /// 不超过 `db_max_size` 的文件保存到数据库, 否则保存到上传文件路径
+    pub async fn save(
+        &self,
+        config: &Upload,
+        file_name: String,
+        data: UploadData,
+        desc: Option<String>,
+    ) -> Result<file_resource::Model, ErrorMsg> {
+        let size = Self::size(&data).await?;
+        if size == 0 {
+            error!("上传的文件为空");
+            return Err(Error::UploadFileError("文件为空".to_string()).into_err());
+        }
+        if size > config.max_bytes() {
+            error!("上传的文件过大, size: {size}");
+            return Err(
+                Error::UploadFileError(format!("文件不能超过{}MB", config.max_size)).into_err(),
+            );
+        }
+
+        let head = Self::head(&data).await?;
+        let (mime_type, extension) = Self::sniff(&head).ok_or_else(|| {
+            error!("{file_name} 无法识别的文件类型");
+            Error::UploadFileError("不支持的文件类型".to_string()).into_err()
+        })?;
+        if !config.is_allowed(&mime_type) {
+            error!("{file_name} 不允许上传的文件类型, mime_type: {mime_type}");
+            return Err(
+                Error::UploadFileError(format!("不允许上传{mime_type}类型的文件")).into_err(),
+            );
+        }
+
+        // 移除图片元数据, 校验 SVG
+        let data = Self::sanitize(&file_name, &mime_type, data).await?;
+        let size = Self::size(&data).await?;
+
+        let hash = Self::hash(&data).await?;
+        if let Some(model) = self.exist(&hash).await? {
+            return Ok(model);
+        }
+
+        let mut model = file_resource::ActiveModel {
+            file_name: Set(file_name.chars().take(FILE_NAME_MAX_LEN).collect()),
+            hash: Set(hash.clone()),
+            extension: Set(extension.clone()),
+            content_type: Set(mime_type),
+            size: Set(size as i64),
+            desc: Set(desc),
+            ..Default::default()
+        };
+        if size <= config.db_max_bytes() {
+            let data = match data {
+                UploadData::Bytes(v) => v,
+                UploadData::File(path) => tokio::fs::read(&path).await.map_err(|err| {
+                    error!("读取上传文件失败, err: {:#?}", err);
+                    Error::UploadFileError("读取文件失败".to_string()).into_err()
+                })?,
+            };
+            model.data = Set(data);
+            model.storage = Set(StorageType::Db.into());
+        } else {
+            let storage = LocalStorage::new(&config.filepath);
+            let relative = LocalStorage::relative_path(&hash, &extension);
+            let result = match &data {
+                UploadData::Bytes(v) => storage.save_bytes(&relative, v).await,
+                UploadData::File(path) => storage.save_file(&relative, path).await,
+            };
+            result.map_err(|err| {
+                error!("保存文件失败, path: {relative}, err: {:#?}", err);
+                Error::FsWriterFileError(relative.clone()).into_err_with_msg("保存文件失败")
+            })?;
+            model.data = Set(Vec::new());
+            model.storage = Set(StorageType::Local.into());
+            model.path = Set(Some(relative));
+        }
+
+        match self.file_resource_dao.create(model).await {
+            Ok(v) => Ok(v),
+            Err(err) => {
+                // 并发上传相同文件
+                if let Some(model) = self.exist(&hash).await? {
+                    return Ok(model);
+                }
+                error!("添加文件资源失败, err: {:#?}", err);
+                Err(Error::DbAddError.into_err_with_msg("添加文件资源失败"))
+            }
+        }
+    }
+
+    /// 获取图片缩略图, 返回缩略图数据及内容类型
+    ///
+    /// 缩略图生成后缓存到上传文件路径下
+    pub async fn thumbnail(
+        &self,
+        hash: String,
+        req: GetThumbnailReq,
+    ) -> Result<(Vec<u8>, &'static str), ErrorMsg> {
+        let (width, height) = (req.width.unwrap_or(0), req.height.unwrap_or(0));
+        if width == 0 && height == 0 {
+            error!("缩略图宽高均未指定");
+            return Err(Error::InvalidParameter("请指定缩略图宽度或高度".to_string()).into_err());
+        }
+        self.render_thumbnail(&upload_config(), hash, width, height)
+            .await
+    }
+
+    /// 生成缩略图, 优先读取缓存
+    async fn render_thumbnail(
+        &self,
+        config: &Upload,
+        hash: String,
+        width: u32,
+        height: u32,
+    ) -> Result<(Vec<u8>, &'static str), ErrorMsg> {
+        let model = self.info_by_hash(hash).await?;
+        if !thumbnail::is_supported(&model.content_type) {
+            error!("hash: {}, 不支持生成缩略图的文件类型", model.hash);
+            return Err(Error::InvalidParameter("该文件不支持生成缩略图".to_string()).into_err());
+        }
+        let (content_type, extension) = thumbnail::output_type(&model.content_type);
+
+        // 读取缓存
+        let storage = LocalStorage::new(&config.filepath);
+        let relative = LocalStorage::thumbnail_path(&model.hash, width, height, extension);
+        if let Ok(data) = tokio::fs::read(storage.full_path(&relative)).await {
+            return Ok((data, content_type));
+        }
+
+        let data = match StorageType::from(model.storage.as_str()) {
+            StorageType::Db => model.data,
+            StorageType::Local => {
+                let path = model.path.unwrap_or_default();
+                tokio::fs::read(storage.full_path(&path))
+                    .await
+                    .map_err(|err| {
+                        error!("读取文件失败, path: {path}, err: {:#?}", err);
+                        Error::FsReadFileError(path.clone()).into_err_with_msg("读取文件失败")
+                    })?
+            }
+        };
+        let mime_type = model.content_type;
+        let data = tokio::task::spawn_blocking(move || {
+            thumbnail::render(&data, &mime_type, width, height)
+        })
+        .await
+        .map_err(|err| {
+            error!("生成缩略图失败, err: {:#?}", err);
+            Error::InternalServer("生成缩略图失败".to_string()).into_err()
+        })?
+        .map_err(|err| {
+            error!("生成缩略图失败, err: {:#?}", err);
+            Error::InternalServer("生成缩略图失败".to_string()).into_err()
+        })?;
+
+        // 缓存失败不影响返回
+        if let Err(err) = storage.save_bytes(&relative, &data).await {
+            error!("缓存缩略图失败, path: {relative}, err: {:#?}", err);
+        }
+        Ok((data, content_type))
+    }
+
+    /// 查询相同HASH值的文件
+    async fn exist(&self, hash: &str) -> Result<Option<file_resource::Model>, ErrorMsg> {
+        self.file_resource_dao
+            .info_by_hash(hash.to_string())
+            .await
+            .map_err(|err| {
+                error!("查询文件资源失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询文件资源失败")
+            })
+    }
+
+    /// 文件大小
+    async fn size(data: &UploadData) -> Result<u64, ErrorMsg> {
+        match data {
+            UploadData::Bytes(v) => Ok(v.len() as u64),
+            UploadData::File(path) => {
+                tokio::fs::metadata(path)
+                    .await
+                    .map(|v| v.len())
+                    .map_err(|err| {
+                        error!("读取上传文件失败, err: {:#?}", err);
+                        Error::UploadFileError("读取文件失败".to_string()).into_err()
+                    })
+            }
+        }
+    }
+
+    /// 移除 JPEG/PNG/WebP 的元数据, 拒绝包含脚本的 SVG
+    async fn sanitize(
+        file_name: &str,
+        mime_type: &str,
+        data: UploadData,
+    ) -> Result<UploadData, ErrorMsg> {
+        let is_svg = mime_type == "image/svg+xml";
+        if !is_svg && !thumbnail::is_supported(mime_type) {
+            return Ok(data);
+        }
+
+        let data = match data {
+            UploadData::Bytes(v) => v,
+            UploadData::File(path) => tokio::fs::read(&path).await.map_err(|err| {
+                error!("读取上传文件失败, err: {:#?}", err);
+                Error::UploadFileError("读取文件失败".to_string()).into_err()
+            })?,
+        };
+        if is_svg {
+            svg::check(&data).map_err(|msg| {
+                error!("{file_name} SVG 校验失败, {msg}");
+                Error::UploadFileError(msg).into_err()
+            })?;
+            return Ok(UploadData::Bytes(data));
+        }
+
+        let data = exif::strip(mime_type, &data).map_err(|_| {
+            error!("{file_name} 图片格式错误, mime_type: {mime_type}");
+            Error::UploadFileError("图片格式错误".to_string()).into_err()
+        })?;
+        Ok(UploadData::Bytes(data))
+    }
+
+    /// 读取文件头
+    async fn head(data: &UploadData) -> Result<Vec<u8>, ErrorMsg> {
+        match data {
+            UploadData::Bytes(v) => Ok(v[..v.len().min(FILE_HEAD_LEN)].to_vec()),
+            UploadData::File(path) => {
+                let mut head = Vec::with_capacity(FILE_HEAD_LEN);
+                let file = File::open(path).await.map_err(|err| {
+                    error!("读取上传文件失败, err: {:#?}", err);
+                    Error::UploadFileError("读取文件失败".to_string()).into_err()
+                })?;
+                file.take(FILE_HEAD_LEN as u64)
+                    .read_to_end(&mut head)
+                    .await
+                    .map_err(|err| {
+                        error!("读取上传文件失败, err: {:#?}", err);
+                        Error::UploadFileError("读取文件失败".to_string()).into_err()
+                    })?;
+                Ok(head)
+            }
+        }
+    }
+
+    /// 根据文件头识别文件类型, 返回内容类型及扩展名
+    fn sniff(head: &[u8]) -> Option<(String, String)> {
+        // SVG 为文本格式, 无法通过魔数识别, 且会被识别为 XML
+        let text = String::from_utf8_lossy(head);
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if (text.starts_with("<?xml") || text.starts_with("<svg")) && text.contains("<svg") {
+            return Some(("image/svg+xml".to_string(), "svg".to_string()));
+        }
+
+        infer::get(head).map(|kind| (kind.mime_type().to_string(), kind.extension().to_string()))
+    }
+
+    /// 计算文件HASH值
+    async fn hash(data: &UploadData) -> Result<String, ErrorMsg> {
+        match data {
+            UploadData::Bytes(v) => Ok(sha256_hex(v)),
+            UploadData::File(path) => {
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || {
+                    std::fs::File::open(path).and_then(sha256_reader)
+                })
+                .await
+                .map_err(|err| {
+                    error!("计算文件HASH值失败, err: {:#?}", err);
+                    Error::UploadFileError("计算文件HASH值失败".to_string()).into_err()
+                })?
+                .map_err(|err| {
+                    error!("计算文件HASH值失败, err: {:#?}", err);
+                    Error::UploadFileError("计算文件HASH值失败".to_string()).into_err()
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use err_code::Error;
+    use inject::InjectProvider;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    /// PNG 文件头
+    const PNG_HEAD: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    async fn service() -> FileResourceService {
+        let provider = InjectProvider::mock().await;
+        provider.provide()
+    }
+
+    fn png_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0, 0, 0, 0]);
+        chunk
+    }
+
+    /// 指定大小的 PNG, 包含 eXIf 元数据
+    fn png(size: usize) -> Vec<u8> {
+        let exif = png_chunk(b"eXIf", b"MM\0*");
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        data.extend_from_slice(&png_chunk(b"IHDR", &[0; 13]));
+        data.extend_from_slice(&exif);
+        let padding = size - data.len() - 12 - 12;
+        data.extend_from_slice(&png_chunk(b"IDAT", &vec![7; padding]));
+        data.extend_from_slice(&png_chunk(b"IEND", &[]));
+        data
+    }
+
+    fn strip_exif(data: &[u8]) -> Vec<u8> {
+        exif::strip("image/png", data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save() {
+        let service = service().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = Upload {
+            filepath: dir.path().to_string_lossy().to_string(),
+            max_size: 1,
+            db_max_size: 1,
+            allowed_types: vec!["image/*".to_string()],
+        };
+
+        // 小文件保存到数据库, 相同内容仅保存一份, 图片元数据被移除
+        let small = png(512);
+        let stripped = strip_exif(&small);
+        assert!(stripped.len() < small.len());
+        let model = service
+            .save(
+                &config,
+                "a.png".to_string(),
+                UploadData::Bytes(small.clone()),
+                None,
+            )
+            .await
+            .expect("save small file");
+        assert_eq!(model.storage, "db");
+        assert_eq!(model.hash, sha256_hex(&stripped));
+        assert_eq!(model.content_type, "image/png");
+        assert_eq!(model.size, stripped.len() as i64);
+        assert_eq!(model.data, stripped);
+        let again = service
+            .save(&config, "b.png".to_string(), UploadData::Bytes(small), None)
+            .await
+            .expect("save small file");
+        assert_eq!(again.id, model.id);
+
+        // 大文件保存到上传文件路径
+        let large = png(4096);
+        let stripped = strip_exif(&large);
+        let tmp = dir.path().join("upload.tmp");
+        std::fs::write(&tmp, &large).unwrap();
+        let model = service
+            .save(&config, "c.png".to_string(), UploadData::File(tmp), None)
+            .await
+            .expect("save large file");
+        assert_eq!(model.storage, "local");
+        assert!(model.data.is_empty());
+        let path = model.path.clone().expect("path");
+        assert_eq!(std::fs::read(dir.path().join(&path)).unwrap(), stripped);
+
+        let storage = LocalStorage::new(dir.path());
+        let mut content = Vec::new();
+        storage
+            .open(&path, 100, 10)
+            .await
+            .unwrap()
+            .read_to_end(&mut content)
+            .await
+            .unwrap();
+        assert_eq!(content, stripped[100..110]);
+
+        // 文件大小及类型限制
+        let code = Error::UploadFileError(String::new()).code();
+        for data in [
+            Vec::new(),
+            png(2 * 1024 * 1024),
+            b"%PDF-1.4\n".to_vec(),
+            b"hello".to_vec(),
+            // 格式错误的图片
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec(),
+            // 包含脚本的 SVG
+            br#"<svg xmlns="http://www.w3.org/2000/svg" onload="alert(1)"/>"#.to_vec(),
+        ] {
+            let err = service
+                .save(&config, "d".to_string(), UploadData::Bytes(data), None)
+                .await
+                .expect_err("rejected");
+            assert_eq!(err.code(), code);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_thumbnail() {
+        let service = service().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = Upload {
+            filepath: dir.path().to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let image = image::DynamicImage::ImageRgb8(image::RgbImage::new(100, 50));
+        let mut data = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut data, image::ImageOutputFormat::Png)
+            .unwrap();
+        let model = service
+            .save(
+                &config,
+                "a.png".to_string(),
+                UploadData::Bytes(data.into_inner()),
+                None,
+            )
+            .await
+            .expect("save image");
+
+        let (data, content_type) = service
+            .render_thumbnail(&config, model.hash.clone(), 20, 0)
+            .await
+            .expect("thumbnail");
+        assert_eq!(content_type, "image/png");
+        let thumbnail = image::load_from_memory(&data).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (20, 10));
+
+        // 缩略图缓存到上传文件路径
+        let cached = dir
+            .path()
+            .join(LocalStorage::thumbnail_path(&model.hash, 20, 0, "png"));
+        assert_eq!(std::fs::read(cached).unwrap(), data);
+
+        let err = service
+            .thumbnail(
+                model.hash,
+                GetThumbnailReq {
+                    width: None,
+                    height: None,
+                },
+            )
+            .await
+            .expect_err("missing size");
+        assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+    }
+
+    #[test]
+    fn test_sniff() {
+        let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"></svg>"#;
+        assert_eq!(
+            FileResourceService::sniff(svg),
+            Some(("image/svg+xml".to_string(), "svg".to_string()))
+        );
+        assert_eq!(
+            FileResourceService::sniff(PNG_HEAD),
+            Some(("image/png".to_string(), "png".to_string()))
+        );
+        assert_eq!(FileResourceService::sniff(b"hello"), None);
+    }
+}
@@ -0,0 +1,55 @@
+//! 日志级别管理
+
+use std::time::Duration;
+
+use log::{error, info};
+use nject::injectable;
+
+use err_code::{Error, ErrorMsg};
+use logger::filter::{LayerFilter, LoggerFilter};
+
+use crate::dto::logger::UpdateLoggerFilterReq;
+
+/// 服务层
+#[injectable]
+pub struct LoggerService {}
+
+impl LoggerService {
+    /// 获取各日志输出层的过滤器
+    pub fn list(&self) -> Result<(Vec<LayerFilter>, u64), ErrorMsg> {
+        let results = Self::filter()?.filters();
+        let total = results.len() as u64;
+        Ok((results, total))
+    }
+
+    /// 更新日志输出层的过滤指令
+    pub fn update(&self, req: UpdateLoggerFilterReq) -> Result<LayerFilter, ErrorMsg> {
+        let filter = Self::filter()?;
+        let result = match req.directives.filter(|v| !v.trim().is_empty()) {
+            Some(directives) => {
+                let revert_after = req.revert_after.map(Duration::from_secs);
+                filter.update(req.layer, &directives, revert_after)
+            }
+            None => filter.reset(req.layer),
+        }
+        .map_err(|err| {
+            error!("更新日志过滤器失败, err: {:#?}", err);
+            Error::InvalidParameter(format!("{:?}", err)).into_err_with_msg("更新日志过滤器失败")
+        })?;
+
+        info!(
+            "日志过滤器已更新, layer: {:?}, directives: {}, revert_at: {:?}",
+            result.layer, result.directives, result.revert_at
+        );
+        Ok(result)
+    }
+
+    /// 获取全局日志过滤器
+    fn filter() -> Result<&'static LoggerFilter, ErrorMsg> {
+        LoggerFilter::global().ok_or_else(|| {
+            error!("日志过滤器未初始化");
+            Error::InternalServer("logger filter not initialized".to_string())
+                .into_err_with_msg("日志过滤器未初始化")
+        })
+    }
+}
@@ -1,2 +1,7 @@
 //! 服务层
 pub mod config;
+pub mod dict_data;
+pub mod dict_dimension;
+pub mod file_resource;
+pub mod logger;
+pub mod system_config;
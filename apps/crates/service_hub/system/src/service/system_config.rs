@@ -0,0 +1,198 @@
+//! 系统配置读取
+//!
+//! 按配置编码读取已启用的配置值, 未配置或已停用时使用声明的默认值.
+use log::error;
+use nject::injectable;
+use serde::de::DeserializeOwned;
+
+use err_code::{Error, ErrorMsg};
+
+use crate::{
+    cache::ConfigCache, constant::ConfigDefinition, dao::config::ConfigDao,
+    dto::config::ConfigValue, enums::config::parse_bool,
+};
+
+/// 系统配置
+#[injectable]
+pub struct SystemConfig {
+    config_dao: ConfigDao,
+}
+
+impl SystemConfig {
+    /// 获取配置值
+    pub async fn value(&self, code: &str) -> Result<ConfigValue, ErrorMsg> {
+        let definition = ConfigDefinition::find(code);
+        let (value, is_default) = match self.load(code).await? {
+            Some(value) => (value, false),
+            None => {
+                let Some(definition) = definition else {
+                    error!("配置不存在, code: {code}");
+                    return Err(Error::DbQueryEmptyError.into_err_with_msg("配置不存在"));
+                };
+                (definition.default.to_string(), true)
+            }
+        };
+
+        Ok(ConfigValue {
+            code: code.to_string(),
+            value,
+            value_type: definition.map(|v| v.value_type),
+            is_default,
+        })
+    }
+
+    /// 获取字符串配置
+    pub async fn get_string(&self, code: &str) -> Result<String, ErrorMsg> {
+        Ok(self.value(code).await?.value)
+    }
+
+    /// 获取布尔配置
+    pub async fn get_bool(&self, code: &str) -> Result<bool, ErrorMsg> {
+        let value = self.get_string(code).await?;
+        parse_bool(&value).ok_or_else(|| {
+            error!("配置值不是有效的布尔值, code: {code}, value: {value}");
+            Error::ConvertType(format!("{code}: {value}"))
+                .into_err_with_msg("配置值不是有效的布尔值")
+        })
+    }
+
+    /// 获取整数配置
+    pub async fn get_i64(&self, code: &str) -> Result<i64, ErrorMsg> {
+        let value = self.get_string(code).await?;
+        value.trim().parse::<i64>().map_err(|err| {
+            error!("配置值不是有效的整数, code: {code}, err: {:#?}", err);
+            Error::ConvertType(format!("{code}: {value}")).into_err_with_msg("配置值不是有效的整数")
+        })
+    }
+
+    /// 获取 JSON 配置
+    pub async fn get_json<T: DeserializeOwned>(&self, code: &str) -> Result<T, ErrorMsg> {
+        let value = self.get_string(code).await?;
+        serde_json::from_str(&value).map_err(|err| {
+            error!("配置值解析失败, code: {code}, err: {:#?}", err);
+            Error::JsonDeserialization(err.to_string()).into_err_with_msg("配置值解析失败")
+        })
+    }
+
+    /// 读取已启用的配置值, 优先读取缓存
+    async fn load(&self, code: &str) -> Result<Option<String>, ErrorMsg> {
+        let (cached, version) = ConfigCache::get(code);
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+
+        let result = self
+            .config_dao
+            .info_by_code(code.to_string())
+            .await
+            .map_err(|err| {
+                error!("查询配置信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询配置信息失败")
+            })?;
+        let value = result.filter(|v| v.status).and_then(|v| v.value);
+
+        ConfigCache::set(code.to_string(), value.clone(), version);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use inject::InjectProvider;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        dto::config::{CreateConfigReq, UpdateConfigReq, UpdateConfigStatusReq},
+        service::config::ConfigService,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Banner {
+        text: String,
+    }
+
+    #[tokio::test]
+    async fn test_system_config() {
+        let provider = InjectProvider::mock().await;
+        let config_service: ConfigService = provider.provide();
+        let system_config: SystemConfig = provider.provide();
+
+        // 未配置时使用声明的默认值
+        assert!(
+            system_config
+                .get_bool("user_register_enabled")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            system_config.get_i64("login_max_failures").await.unwrap(),
+            5
+        );
+        let value = system_config.value("site_name").await.unwrap();
+        assert!(value.is_default);
+        let err = system_config.value("not_declared").await.unwrap_err();
+        assert_eq!(err.code(), Error::DbQueryEmptyError.code());
+
+        // 写入时校验已声明配置的值类型
+        let req = |code: &str, value: &str| CreateConfigReq {
+            pid: None,
+            name: code.to_string(),
+            code: code.to_string(),
+            value: Some(value.to_string()),
+            sort: None,
+            desc: None,
+            status: true,
+        };
+        let err = config_service
+            .create(req("login_max_failures", "many"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), Error::InvalidParameter(String::new()).code());
+
+        let model = config_service
+            .create(req("login_max_failures", "3"))
+            .await
+            .expect("create config");
+        config_service
+            .create(req("notice_banner", r#"{"text": "hello"}"#))
+            .await
+            .expect("create config");
+        assert_eq!(
+            system_config.get_i64("login_max_failures").await.unwrap(),
+            3
+        );
+        let banner: Banner = system_config.get_json("notice_banner").await.unwrap();
+        assert_eq!(banner.text, "hello");
+
+        // 更新及停用后缓存失效
+        config_service
+            .update(UpdateConfigReq {
+                id: model.id,
+                pid: None,
+                name: model.name.clone(),
+                code: model.code.clone(),
+                value: Some("8".to_string()),
+                sort: None,
+                desc: None,
+                status: true,
+            })
+            .await
+            .expect("update config");
+        assert_eq!(
+            system_config.get_i64("login_max_failures").await.unwrap(),
+            8
+        );
+
+        config_service
+            .update_status(UpdateConfigStatusReq {
+                id: model.id,
+                status: false,
+            })
+            .await
+            .expect("update config status");
+        let value = system_config.value("login_max_failures").await.unwrap();
+        assert_eq!(value.value, "5");
+        assert!(value.is_default);
+    }
+}
@@ -0,0 +1,83 @@
+//! 文件存储
+//!
+//! 小文件保存到数据库, 大文件保存到上传文件路径下, 按HASH值分目录存储.
+use std::{
+    io::{self, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use tokio::{
+    fs::{self, File},
+    io::{AsyncReadExt, AsyncSeekExt, Take},
+};
+
+use config::{AppConfig, server::Upload};
+
+/// 获取上传文件配置, 全局配置未初始化时使用默认配置
+pub fn upload_config() -> Upload {
+    AppConfig::instance()
+        .map(|config| config.server.upload.clone())
+        .unwrap_or_default()
+}
+
+/// 本地文件存储
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// 创建对象
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    /// 文件相对路径, 如 `ab/cd/abcd...ef.png`
+    pub fn relative_path(hash: &str, extension: &str) -> String {
+        format!("{}/{}/{hash}.{extension}", &hash[..2], &hash[2..4])
+    }
+
+    /// 缩略图相对路径, 如 `thumbnails/ab/abcd...ef-200x0.png`
+    pub fn thumbnail_path(hash: &str, width: u32, height: u32, extension: &str) -> String {
+        format!(
+            "thumbnails/{}/{hash}-{width}x{height}.{extension}",
+            &hash[..2]
+        )
+    }
+
+    /// 文件完整路径
+    pub fn full_path(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    /// 复制文件到存储路径
+    pub async fn save_file(&self, relative: &str, src: &Path) -> io::Result<()> {
+        let (tmp, dst) = self.prepare(relative).await?;
+        fs::copy(src, &tmp).await?;
+        fs::rename(&tmp, &dst).await
+    }
+
+    /// 写入数据到存储路径
+    pub async fn save_bytes(&self, relative: &str, data: &[u8]) -> io::Result<()> {
+        let (tmp, dst) = self.prepare(relative).await?;
+        fs::write(&tmp, data).await?;
+        fs::rename(&tmp, &dst).await
+    }
+
+    /// 打开文件, 读取 `offset` 起的 `len` 个字节
+    pub async fn open(&self, relative: &str, offset: u64, len: u64) -> io::Result<Take<File>> {
+        let mut file = File::open(self.full_path(relative)).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok(file.take(len))
+    }
+
+    /// 创建目录, 先写入临时文件再重命名, 避免读取到未写完的文件
+    async fn prepare(&self, relative: &str) -> io::Result<(PathBuf, PathBuf)> {
+        let dst = self.full_path(relative);
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let mut tmp = dst.clone().into_os_string();
+        tmp.push(".tmp");
+        Ok((tmp.into(), dst))
+    }
+}
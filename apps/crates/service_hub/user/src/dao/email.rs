@@ -65,6 +65,14 @@ impl EmailDao {
             .await
     }
 
+    /// 通过用户ID获取详情信息
+    pub async fn info_by_user_id(&self, user_id: i32) -> Result<Option<email::Model>, DbErr> {
+        EmailEntity::find()
+            .filter(email::Column::UserId.eq(user_id))
+            .one(self.db.db())
+            .await
+    }
+
     /// 添加详情信息
     pub async fn create(&self, active_model: email::ActiveModel) -> Result<email::Model, DbErr> {
         active_model.insert(self.db.db()).await
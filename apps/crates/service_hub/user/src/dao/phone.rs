@@ -65,6 +65,14 @@ impl PhoneDao {
             .await
     }
 
+    /// 通过用户ID获取详情信息
+    pub async fn info_by_user_id(&self, user_id: i32) -> Result<Option<phone::Model>, DbErr> {
+        PhoneEntity::find()
+            .filter(phone::Column::UserId.eq(user_id))
+            .one(self.db.db())
+            .await
+    }
+
     /// 添加详情信息
     pub async fn create(&self, active_model: phone::ActiveModel) -> Result<phone::Model, DbErr> {
         active_model.insert(self.db.db()).await
@@ -4,12 +4,16 @@ use std::sync::Arc;
 
 use nject::injectable;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    QuerySelect, QueryTrait,
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait, RelationTrait,
 };
 
 use database::{Pagination, PoolTrait};
-use entity::user::{UserRoleRelEntity, user_role_rel};
+use entity::user::{
+    UserRoleRelEntity,
+    role::{self, ADMIN_ROLE_NAME},
+    user_role_rel,
+};
 
 use crate::dto::user_role_rel::GetUserRoleRelsReq;
 
@@ -115,4 +119,16 @@ impl UserRoleRelDao {
         let total = results.len() as u64;
         Ok((results, total))
     }
+
+    /// 用户是否拥有有效的管理员角色
+    pub async fn is_admin(&self, user_id: i32) -> Result<bool, DbErr> {
+        let total = UserRoleRelEntity::find()
+            .join(JoinType::InnerJoin, user_role_rel::Relation::Role.def())
+            .filter(user_role_rel::Column::UserId.eq(user_id))
+            .filter(role::Column::Name.eq(ADMIN_ROLE_NAME))
+            .filter(role::Column::Status.eq(true))
+            .count(self.db.db())
+            .await?;
+        Ok(total > 0)
+    }
 }
@@ -135,6 +135,16 @@ pub struct UpdateUserBaseStatusReq {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateUserBaseStatusResp {}
 
+/// 重置用户密码 请求体
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct ResetUserBasePasswordReq {
+    /// 用户ID
+    pub id: i32,
+    /// 新密码
+    #[validate(length(min = 6, message = "密码至少需要6个字符"))]
+    pub password: String,
+}
+
 /// 删除用户 请求体
 #[derive(Debug, Default, Deserialize, Validate)]
 pub struct DeleteUserBaseReq {
@@ -0,0 +1,116 @@
+//! 访问守卫
+
+use axum::{Extension, extract::Request, middleware::Next, response::IntoResponse};
+use log::error;
+
+use axum_context::Context;
+use axum_response::ResponseErr;
+use err_code::Error;
+use inject::AInjectProvider;
+
+use crate::service::user_role_rel::UserRoleRelService;
+
+/// 管理员访问守卫, 非管理员用户拒绝访问
+///
+/// 需要放在 `ContextLayer` 与 `JwtLayer` 之后, 通过 `route_layer` 作用于需要管理员权限的路由
+/// ```ignore
+/// use axum::{Router, middleware::from_fn, routing::post};
+///
+/// Router::new()
+///     .route("/purge", post(handler))
+///     .route_layer(from_fn(admin_guard_layer))
+/// ```
+pub async fn admin_guard_layer(
+    Extension(provider): Extension<AInjectProvider>,
+    ctx: Context,
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ResponseErr> {
+    let user_id = ctx.get_user_id();
+    let user_role_rel_service: UserRoleRelService = provider.provide();
+    if !user_role_rel_service.is_admin(user_id).await? {
+        error!(
+            "非管理员访问, user_id: {user_id}, path: {}",
+            request.uri().path()
+        );
+        return Err(Error::AuthIllegalRequest
+            .into_err_with_msg("仅管理员可访问")
+            .into());
+    }
+
+    let resp = next.run(request).await;
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        middleware::from_fn,
+        routing::post,
+    };
+    use entity::user::{
+        RoleEntity,
+        role::{self, ADMIN_ROLE_NAME},
+        user_role_rel,
+    };
+    use inject::InjectProvider;
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const ADMIN: i32 = 1;
+    const USER: i32 = 2;
+
+    async fn call(provider: AInjectProvider, user_id: i32) -> String {
+        let app = Router::new()
+            .route("/purge", post(|| async { "ok" }))
+            .route_layer(from_fn(admin_guard_layer))
+            .layer(from_fn(move |mut req: Request, next: Next| async move {
+                let mut ctx = Context::default();
+                ctx.set_user_id(user_id);
+                req.extensions_mut().insert(ctx);
+                next.run(req).await
+            }))
+            .layer(Extension(provider));
+        let req = Request::post("/purge")
+            .body(Body::empty())
+            .expect("build request");
+        let resp = app.oneshot(req).await.expect("call");
+        let bytes = to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        String::from_utf8(bytes.to_vec()).expect("utf8")
+    }
+
+    #[tokio::test]
+    async fn test_admin_guard_layer() {
+        let provider = Arc::new(InjectProvider::mock().await);
+        let db: Arc<dyn database::PoolTrait> = provider.provide();
+        let admin_role = RoleEntity::find()
+            .filter(role::Column::Name.eq(ADMIN_ROLE_NAME))
+            .one(db.db())
+            .await
+            .expect("query role")
+            .expect("admin role exists");
+        user_role_rel::ActiveModel {
+            user_id: Set(ADMIN),
+            role_id: Set(admin_role.id),
+            ..Default::default()
+        }
+        .insert(db.db())
+        .await
+        .expect("grant admin role");
+
+        assert_eq!(call(provider.clone(), ADMIN).await, "ok");
+
+        let body = call(provider, USER).await;
+        let body: Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(body["code"], Error::AuthIllegalRequest.code());
+    }
+}
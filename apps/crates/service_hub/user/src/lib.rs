@@ -1,6 +1,7 @@
 //! 用户信息管理
 pub mod dto;
 pub mod enums;
+pub mod guard;
 
 pub(crate) mod dao;
 pub use dao::{
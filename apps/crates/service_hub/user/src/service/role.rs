@@ -57,6 +57,24 @@ impl RoleService {
         Ok(result)
     }
 
+    /// 通过角色名称获取详情数据
+    pub async fn info_by_name(&self, name: String) -> Result<role::Model, ErrorMsg> {
+        let result = self
+            .role_dao
+            .info_by_name(name.clone())
+            .await
+            .map_err(|err| {
+                error!("查询角色信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询角色信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("角色不存在, name: {name}");
+                Error::DbQueryEmptyError.into_err_with_msg("角色不存在")
+            })?;
+
+        Ok(result)
+    }
+
     /// 添加数据
     pub async fn create(&self, req: CreateRoleReq) -> Result<role::Model, ErrorMsg> {
         // 检查角色名称是否已存在
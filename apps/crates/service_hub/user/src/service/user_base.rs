@@ -12,7 +12,8 @@ use crate::{
     dao::{user_base::UserBaseDao, user_role_rel::UserRoleRelDao},
     dto::user_base::{
         CreateUserBaseReq, DeleteUserBaseReq, GetCheckUsernameReq, GetUserBaseReq, GetUserBasesReq,
-        ProfileResp, RolesReq, UpdateUserBaseReq, UpdateUserBaseStatusReq,
+        ProfileResp, ResetUserBasePasswordReq, RolesReq, UpdateUserBaseReq,
+        UpdateUserBaseStatusReq,
     },
 };
 
@@ -75,6 +76,45 @@ impl UserBaseService {
         Ok(())
     }
 
+    /// 通过用户名获取详情数据
+    pub async fn info_by_username(&self, username: String) -> Result<user_base::Model, ErrorMsg> {
+        let mut result = self
+            .user_base_dao
+            .info_by_username(username)
+            .await
+            .map_err(|err| {
+                error!("查询用户信息失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户信息失败")
+            })?
+            .ok_or_else(|| {
+                error!("用户不存在");
+                Error::DbQueryEmptyError.into_err_with_msg("用户不存在")
+            })?;
+
+        // 屏蔽敏感信息
+        result.password = "".to_string();
+        Ok(result)
+    }
+
+    /// 重置密码
+    pub async fn reset_password(&self, req: ResetUserBasePasswordReq) -> Result<(), ErrorMsg> {
+        let model = user_base::ActiveModel {
+            id: Set(req.id),
+            password: Set(sha2_256(&req.password)),
+            ..Default::default()
+        };
+        let rows = self.user_base_dao.update(model).await.map_err(|err| {
+            error!("重置用户密码失败, err: {:#?}", err);
+            Error::DbUpdateError.into_err_with_msg("重置用户密码失败")
+        })?;
+        if rows == 0 {
+            error!("用户不存在, id: {}", req.id);
+            return Err(Error::DbQueryEmptyError.into_err_with_msg("用户不存在"));
+        }
+
+        Ok(())
+    }
+
     /// 删除数据
     pub async fn delete(&self, req: DeleteUserBaseReq) -> Result<u64, ErrorMsg> {
         let result = self.user_base_dao.delete(req.id).await.map_err(|err| {
@@ -79,4 +79,18 @@ impl UserRoleRelService {
 
         Ok(result)
     }
+
+    /// 用户是否为管理员
+    pub async fn is_admin(&self, user_id: i32) -> Result<bool, ErrorMsg> {
+        let result = self
+            .user_role_rel_dao
+            .is_admin(user_id)
+            .await
+            .map_err(|err| {
+                error!("查询用户角色失败, err: {:#?}", err);
+                Error::DbQueryError.into_err_with_msg("查询用户角色失败")
+            })?;
+
+        Ok(result)
+    }
 }
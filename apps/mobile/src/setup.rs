@@ -4,7 +4,6 @@ use std::{path::Path, sync::Arc};
 
 use log::info;
 use tauri::{App, Manager, path::BaseDirectory};
-use tracing_appender::non_blocking::WorkerGuard;
 
 use admin::server::HttpServer;
 use app_state::mobile::{AppDirector, AppState};
@@ -12,11 +11,13 @@ use config::AppConfig;
 use database::Mdb;
 use err_code::Error;
 use inject::InjectProvider;
+use logger::LoggerGuard;
 
 use crate::utils::app_dir::{init_dir, print_app_dir};
 
 const CONFIG_FILE: &str = "config.yaml";
 const DATA_DAT_FILE: &str = "data.dat";
+const ADMIN_CREDENTIAL_FILE: &str = "admin_credential.txt";
 
 pub struct Setup {}
 
@@ -29,8 +30,16 @@ impl Setup {
         Self::init_resources(app, &app_dir).expect("初始化资源文件失败");
 
         // 加载配置文件
-        let app_config = AppConfig::new(app_dir.join(CONFIG_FILE).to_string_lossy().as_ref())
-            .expect("加载配置文件失败");
+        let mut app_config =
+            AppConfig::new(app_dir.join(CONFIG_FILE).to_string_lossy().as_ref())
+                .expect("加载配置文件失败");
+        // 移动端无法查看标准输出, 初始管理员随机密码默认写入应用目录
+        if app_config.initialize.credential_file.is_empty() {
+            app_config.initialize.credential_file = app_dir
+                .join(ADMIN_CREDENTIAL_FILE)
+                .to_string_lossy()
+                .to_string();
+        }
 
         // 初始化日志
         let log_guards = Self::init_logger(&app_dir, &app_config).expect("初始化日志失败");
@@ -38,8 +47,13 @@ impl Setup {
         // 加载数据库
         let db_pool = Self::load_data_dat(&app_dir, &app_config);
 
+        // 初始化邮件发送器
+        let mailer = mailer::build(&app_config.mailer).expect("初始化邮件发送器失败");
+        // 初始化短信发送器
+        let sms = sms::build(&app_config.sms).expect("初始化短信发送器失败");
+
         // Using an Arc to share the provider across multiple threads.
-        let inject_provider = Arc::new(InjectProvider::new(db_pool.clone()));
+        let inject_provider = Arc::new(InjectProvider::new(db_pool.clone(), mailer, sms));
 
         // 全局状态
         let state = Arc::new(AppState {
@@ -67,7 +81,7 @@ impl Setup {
     }
 
     /// 初始化日志
-    pub fn init_logger(app_dir: &Path, app_config: &AppConfig) -> Result<Vec<WorkerGuard>, Error> {
+    pub fn init_logger(app_dir: &Path, app_config: &AppConfig) -> Result<Vec<LoggerGuard>, Error> {
         let log_dir = app_dir.join("logs").to_string_lossy().to_string();
 
         let mut logger = app_config.logger.clone();